@@ -0,0 +1,160 @@
+//! Runtime checks for conflicting accesses through raw partial references.
+
+use std::any::type_name;
+#[cfg(debug_assertions)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+
+use crate::{Const, HasPart, HasTarget, Mut, Part, PartialRef, PartialRefTarget, Ref};
+
+/// *(internal)* List the parts of a partial reference for a [`PartClaim`].
+pub unsafe trait ClaimParts: HasTarget {
+    /// The name of the target type.
+    fn target_name() -> &'static str;
+
+    /// Append the name of each part together with whether it is a mutable part.
+    fn part_claims(claims: &mut Vec<(&'static str, bool)>);
+}
+
+/// *(internal)* An empty reference claims nothing.
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> ClaimParts for Ref<'a, Target> {
+    fn target_name() -> &'static str {
+        type_name::<Target>()
+    }
+
+    fn part_claims(_claims: &mut Vec<(&'static str, bool)>) {}
+}
+
+/// *(internal)* Claim a constant part and the remaining parts.
+unsafe impl<'a, SomePart, Reference> ClaimParts for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: PartialRef<'a> + ClaimParts,
+    Reference::Target: HasPart<SomePart>,
+{
+    fn target_name() -> &'static str {
+        Reference::target_name()
+    }
+
+    fn part_claims(claims: &mut Vec<(&'static str, bool)>) {
+        Reference::part_claims(claims);
+        claims.push((type_name::<SomePart>(), false));
+    }
+}
+
+/// *(internal)* Claim a mutable part and the remaining parts.
+unsafe impl<'a, SomePart, Reference> ClaimParts for Mut<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: PartialRef<'a> + ClaimParts,
+    Reference::Target: HasPart<SomePart>,
+{
+    fn target_name() -> &'static str {
+        Reference::target_name()
+    }
+
+    fn part_claims(claims: &mut Vec<(&'static str, bool)>) {
+        Reference::part_claims(claims);
+        claims.push((type_name::<SomePart>(), true));
+    }
+}
+
+#[cfg(debug_assertions)]
+struct Claim {
+    id: usize,
+    address: usize,
+    target: &'static str,
+    part: &'static str,
+    mutable: bool,
+}
+
+#[cfg(debug_assertions)]
+static CLAIMS: Mutex<Vec<Claim>> = Mutex::new(Vec::new());
+
+#[cfg(debug_assertions)]
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Record the parts of `Reference` for the value at `address`, returning the id of the claim.
+#[cfg(debug_assertions)]
+fn register_claim<Reference: ClaimParts>(address: usize) -> usize {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let target = Reference::target_name();
+    let mut parts = vec![];
+    Reference::part_claims(&mut parts);
+
+    let mut claims = CLAIMS.lock().unwrap_or_else(|err| err.into_inner());
+    for (part, mutable) in parts {
+        let conflict = claims.iter().any(|claim| {
+            claim.address == address
+                && claim.target == target
+                && claim.part == part
+                && (claim.mutable || mutable)
+        });
+        if conflict {
+            claims.retain(|claim| claim.id != id);
+            drop(claims);
+            panic!("conflicting claims for part `{}` of `{}`", part, target);
+        }
+        claims.push(Claim {
+            id,
+            address,
+            target,
+            part,
+            mutable,
+        });
+    }
+    id
+}
+
+/// A claim on the parts of a partial reference, checked at runtime in debug builds.
+///
+/// When debug assertions are enabled, creating a claim records the parts of the partial reference
+/// type `Reference` for the referenced value until the claim is dropped. Creating a claim panics if
+/// one of its parts was already claimed for the same value, including by the same claim, and either
+/// of the two claims is for a mutable part. Without debug assertions, claims do nothing.
+///
+/// This helps to catch misuse of [`from_raw`](PartialRef::from_raw) or
+/// [`RawPartialRef::revive`](crate::RawPartialRef::revive) in tests: keeping a claim alive for as
+/// long as a partial reference created from a raw pointer is used ensures that no other claimed
+/// reference accesses the same parts in a conflicting way. Only claimed references are checked and
+/// parts are compared by type, so a nested part doesn't conflict with the part containing it.
+#[must_use]
+pub struct PartClaim {
+    #[cfg(debug_assertions)]
+    id: usize,
+}
+
+impl PartClaim {
+    /// Claim the parts of `Reference` for the value pointed to by `ptr`.
+    ///
+    /// # Panics
+    /// Panics in debug builds if a part conflicts with a part of an existing claim.
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    pub fn new<Reference: ClaimParts>(
+        ptr: *const <Reference::Target as PartialRefTarget>::RawTarget,
+    ) -> Self {
+        PartClaim {
+            #[cfg(debug_assertions)]
+            id: register_claim::<Reference>(ptr.cast::<u8>() as usize),
+        }
+    }
+
+    /// Claim the parts of a partial reference.
+    ///
+    /// # Panics
+    /// Panics in debug builds if a part conflicts with a part of an existing claim.
+    pub fn of<'a, Reference: PartialRef<'a> + ClaimParts>(reference: &Reference) -> Self {
+        Self::new::<Reference>(reference.get_raw())
+    }
+}
+
+impl Drop for PartClaim {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            let mut claims = CLAIMS.lock().unwrap_or_else(|err| err.into_inner());
+            claims.retain(|claim| claim.id != self.id);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Partial references extended by a field stored outside of the target.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::{FieldPartType, HasSubset, HasTarget, PartSpec, PartialRef};
+
+/// A partial reference extended by an external field.
+///
+/// This combines a partial reference with a mutable reference to a value that is not part of the
+/// target, so both can be passed around as a single value. The external field is identified by the
+/// part `ExtraPart`, which has to be a [`Field`](crate::Field) part of the field's type. It can be
+/// created using [`extend_with`](PartialRef::extend_with).
+///
+/// The extended partial reference dereferences to the wrapped partial reference, so all of its
+/// parts are accessed as usual.
+pub struct Extended<'a, ExtraPart: FieldPartType, Reference> {
+    reference: Reference,
+    field: &'a mut ExtraPart::FieldType,
+}
+
+impl<'a, ExtraPart: FieldPartType, Reference> Extended<'a, ExtraPart, Reference> {
+    /// Extend a partial reference by an external field.
+    pub fn new(reference: Reference, field: &'a mut ExtraPart::FieldType) -> Self {
+        Extended { reference, field }
+    }
+
+    /// Access the external field.
+    ///
+    /// The part parameter is only present for type inference, its value is ignored.
+    #[inline(always)]
+    pub fn extra_part<FieldPartSpec>(&self, _part: FieldPartSpec) -> &ExtraPart::FieldType
+    where
+        FieldPartSpec: PartSpec<ExtraPart>,
+    {
+        self.field
+    }
+
+    /// Mutable access to the external field.
+    ///
+    /// The part parameter is only present for type inference, its value is ignored.
+    #[inline(always)]
+    pub fn extra_part_mut<FieldPartSpec>(
+        &mut self,
+        _part: FieldPartSpec,
+    ) -> &mut ExtraPart::FieldType
+    where
+        FieldPartSpec: PartSpec<ExtraPart>,
+    {
+        self.field
+    }
+
+    /// Mutable access to the external field and the wrapped partial reference at the same time.
+    #[inline(always)]
+    pub fn split_extra_part(&mut self) -> (&mut ExtraPart::FieldType, &mut Reference) {
+        (self.field, &mut self.reference)
+    }
+
+    /// Partially re-borrows the wrapped partial reference, keeping the external field.
+    ///
+    /// This is equivalent to [`PartialRef::borrow`] for the wrapped partial reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn borrow<'b, BorrowedRef, SubsetIndex>(
+        &'b mut self,
+    ) -> Extended<'b, ExtraPart, BorrowedRef>
+    where
+        Reference: HasSubset<'b, BorrowedRef, SubsetIndex>,
+        BorrowedRef: PartialRef<'b, Target = <Reference as HasTarget>::Target>,
+    {
+        Extended {
+            reference: self.reference.borrow(),
+            field: self.field,
+        }
+    }
+
+    /// Split into the wrapped partial reference and the external field.
+    #[inline(always)]
+    pub fn into_inner(self) -> (Reference, &'a mut ExtraPart::FieldType) {
+        (self.reference, self.field)
+    }
+}
+
+impl<'a, ExtraPart: FieldPartType, Reference> Deref for Extended<'a, ExtraPart, Reference> {
+    type Target = Reference;
+
+    #[inline(always)]
+    fn deref(&self) -> &Reference {
+        &self.reference
+    }
+}
+
+impl<'a, ExtraPart: FieldPartType, Reference> DerefMut for Extended<'a, ExtraPart, Reference> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Reference {
+        &mut self.reference
+    }
+}
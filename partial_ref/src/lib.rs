@@ -114,8 +114,87 @@
 //!
 //! This covers the basic functionality of this library. Beyond that this library also supports:
 //!
-//! * Partial references to nested structs using [`Nested`] and [`nested_part`].
-//! * Generic functions with bounds on available parts using [`HasPart`].
+//! * Accessing multiple parts at once using [`parts`](PartialRef::parts).
+//! * Iterating over the elements of a collection part while using the remaining parts with
+//!   [`for_each_element_mut`](PartialRef::for_each_element_mut).
+//! * Accessing parts by indexing with the part, e.g. `g_ref[Colors]`.
+//! * Partial references to nested structs using [`Nested`] and [`nested_part`], or to structs in
+//!   `Option` fields using [`try_split_part_mut`](PartialRef::try_split_part_mut).
+//! * Converting between partial references with differently nested but equivalent nested parts
+//!   using [`reassociate`](PartialRef::reassociate).
+//! * Generic functions with bounds on available parts using [`HasPart`] and [`HasParts`].
+//! * Accepting any partial reference having the given parts using [`IntoSubset`].
+//! * Requiring a partial reference to have exactly the given parts using [`SameParts`].
+//! * Naming the parts of partial references in generic code using [`PartSet`] and [`part_set`].
+//! * Computing properties of partial reference types by folding over their parts using
+//!   [`FoldParts`].
+//! * Listing the parts of partial reference types and whether they are held mutably at runtime
+//!   using [`HeldParts`].
+//! * Converting partial references into a type with a canonical order of parts using
+//!   [`canonicalize`](PartialRef::canonicalize).
+//! * Enum targets with parts for variant fields, which are accessed using a [`Witness`]. This
+//!   also works for enum fields using nested parts like `Connection | Sent`.
+//! * Parts covering a group of fields using [`FieldGroup`].
+//! * Virtual parts computed by an accessor instead of being a field using [`VirtualPart`].
+//! * Per-thread scratch space as parts of a target using [`ThreadLocalPart`].
+//! * Tracking abstract parts in different typestates using [`Typestate`] parts.
+//! * Abstract parts for resources outside of the target, which are added to a partial reference by
+//!   borrowing a token declared using [`part_token`].
+//! * Structs of plain references to all fields using [`to_view`](PartialRef::to_view).
+//! * Parts for the values pointed to by `Box` and `&mut` fields, including trait objects such as
+//!   `Box<dyn Trait>`, see [`DerefField`], and shared access to the values pointed to by `Rc`,
+//!   `Arc` and `&` fields using [`SharedField`] parts.
+//! * Structurally pinned fields using [`PinnedField`].
+//! * Union targets with unsafe access to parts of type [`UnionField`].
+//! * Packed structs with access to unaligned fields using parts of type [`UnalignedField`].
+//! * Bit ranges of integer fields as disjoint parts using [`BitPart`].
+//! * Columns of slices of rows as disjoint parts using [`ColumnPart`].
+//! * Several instances of one part declaration distinguished by a tag using [`TaggedPart`].
+//! * Lock-guarded `Mutex` and `RwLock` fields using parts of type [`LockedField`].
+//! * `RefCell` fields with borrows tracked at runtime using parts of type [`RefCellField`].
+//! * Storing partial references without a lifetime using [`RawPartialRef`] and checking for
+//!   conflicting uses of such references in debug builds using [`PartClaim`].
+//! * Raw pointers to single parts for FFI using [`part_ptr`](PartialRef::part_ptr) and
+//!   [`part_ptr_mut`](PartialRef::part_ptr_mut).
+//! * Owning a boxed target restricted to a set of parts using [`OwnedPartialRef`].
+//! * Accessing global state stored in a `static` through partial references using
+//!   [`StaticTarget`].
+//! * Targets with a custom raw representation using [`RawTargetOf`].
+//! * Swapping the fields of parts of two partial references using [`swap_parts`], or of two parts
+//!   of the same reference using [`swap_between`](PartialRef::swap_between).
+//! * Sharing a constant view of a partial reference between threads using
+//!   [`freeze`](PartialRef::freeze).
+//! * Converting a partial reference with all parts back into a mutable reference using
+//!   [`into_full`](PartialRef::into_full).
+//! * Passing an external field along with a partial reference using [`Extended`], e.g. a
+//!   sub-field of a part selected using [`map_part_mut`](PartialRef::map_part_mut).
+//! * Pairing partial references to two different targets using [`PartialPair`].
+//! * Splitting a partial reference into several partial references at once using
+//!   [`split_n`](PartialRef::split_n).
+//! * Splitting a partial reference into its constant and its mutable parts using
+//!   [`split_const`](PartialRef::split_const).
+//! * Splitting a slice part into two independently usable halves using
+//!   [`split_slice_part_at`](PartialRef::split_slice_part_at), or accessing several of its elements
+//!   using [`get_disjoint_part_mut`](PartialRef::get_disjoint_part_mut).
+//! * Partitioning a slice part into fixed ranges or elements using [`RangePart`] and [`ElemPart`]
+//!   parts, or into ranges selected at runtime using
+//!   [`split_chunks_part_mut`](PartialRef::split_chunks_part_mut).
+//! * Mutable access to the values of distinct keys of a map part using
+//!   [`get_disjoint_keys_part_mut`](PartialRef::get_disjoint_keys_part_mut) and [`KeyPart`].
+//! * Naming the remainder of a split in types using [`RemainderOf`].
+//! * Joining the two partial references returned by a split using [`join`](PartialRef::join).
+//! * Copying the values of parts between partial references using
+//!   [`copy_parts_from`](PartialRef::copy_parts_from) and
+//!   [`clone_parts_into`](PartialRef::clone_parts_into).
+//! * Resetting parts to their default values using [`reset_part`](PartialRef::reset_part) and
+//!   [`reset_parts`](PartialRef::reset_parts).
+//! * Comparing the values of parts of two partial references using
+//!   [`parts_eq`](PartialRef::parts_eq) and hashing them using
+//!   [`hash_parts`](PartialRef::hash_parts).
+//! * Visiting the fields of all parts of a target using [`VisitParts`] or of a partial reference
+//!   using [`for_each_part`](PartialRef::for_each_part).
+//! * Serializing the fields of the parts of a partial reference using `SerializeParts` and
+//!   `DeserializeParts`, which requires the `serde` feature.
 //!
 //! # Notes
 //!
@@ -131,30 +210,184 @@
 //!
 //! [interprocedural-conflicts]: http://smallcultfollowing.com/babysteps/blog/2018/11/01/after-nll-interprocedural-conflicts/
 
+// See the notes above about unsafe traits.
+#![allow(clippy::missing_safety_doc)]
+
 #[macro_use]
 mod macros;
 
-pub use partial_ref_derive::PartialRefTarget;
+mod claim;
+mod extended;
+mod owned;
+mod pair;
+mod part_set;
+mod raw_ref;
+mod static_target;
+mod thread_local;
+mod token;
+mod typestate;
+
+#[cfg(feature = "serde")]
+mod serde_parts;
+
+pub use claim::{ClaimParts, PartClaim};
+pub use extended::Extended;
+pub use owned::OwnedPartialRef;
+pub use pair::{Left, PairPart, PairPartMut, PartialPair, Right};
+pub use part_set::{
+    AccessNone, AddPartAccess, CanonicalPart, CanonicalParts, CanonicalRef, CanonicalTarget,
+    Canonicalize, ComparePositions, EmptyParts, FoldParts, PartAccess, PartFolder, PartSet,
+    PartSetRef, PartialRefFor, PartsOf, PositionIndex, RemainderAccess, RemainderOf,
+    RemainderParts, WithConst, WithMut,
+};
+pub use partial_ref_derive::{partial_ref_target, PartialRefTarget};
+pub use raw_ref::RawPartialRef;
+pub use static_target::StaticTarget;
+pub use thread_local::{ThreadLocalField, ThreadLocalPart};
+pub use token::{PartToken, TokenPart, TokenSeal};
+pub use typestate::{StatefulAbstractPart, Typestate};
+
+#[cfg(feature = "serde")]
+pub use serde_parts::{DeserializeParts, SerdePart, SerializeParts};
 
+use std::any::type_name;
+use std::cell::{self, Cell, RefCell, UnsafeCell};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash;
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, IndexMut, Range};
+use std::pin::Pin;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{Arc, LockResult, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 /// A type that can be the target of partial references.
 ///
 /// Implementations for this trait should be derived, see [`partial_ref_derive`].
+///
+/// The parts that the derive declares for fields are never more visible than the target, so that
+/// partial references of a private target can't be named where the target can't be named:
+///
+/// ```compile_fail
+/// mod outer {
+///     pub mod inner {
+///         use partial_ref::*;
+///
+///         #[derive(PartialRefTarget)]
+///         pub(super) struct Counter {
+///             #[part(auto)]
+///             pub count: u32,
+///         }
+///     }
+/// }
+///
+/// use outer::inner::Count;
+/// ```
 pub trait PartialRefTarget {
     /// A partial reference will be represented by a pointer to this associated type.
     ///
-    /// In most cases this is the implementing type itself. This is not a requirement though. In the
-    /// future, some features will introduce PartialRefTargets that have a different associated
-    /// `RawTarget` type.
+    /// In most cases this is the implementing type itself. The derive allows using a different type
+    /// with the `#[partial_ref(raw = "...")]` attribute, which has to implement [`RawTargetOf`]
+    /// for the target.
     type RawTarget: ?Sized;
 }
 
+/// A raw representation of the reference target `Target`.
+///
+/// This is used by derived implementations to convert between pointers to a target's
+/// [`RawTarget`](PartialRefTarget::RawTarget) and pointers to the target itself. It is
+/// implemented for every type as a representation of itself and for [`UnsafeCell`] and [`Cell`]
+/// containing the target.
+///
+/// # Safety
+/// The pointers returned by `target_ptr` and `target_ptr_mut` must point to the `Target` stored
+/// in the raw representation pointed to by `ptr`. The function `from_target_ptr` must be the
+/// inverse of `target_ptr_mut`.
+pub unsafe trait RawTargetOf<Target> {
+    /// Given a constant pointer to the raw representation, produce a pointer to the target.
+    fn target_ptr(ptr: *const Self) -> *const Target;
+    /// Given a mutable pointer to the raw representation, produce a pointer to the target.
+    fn target_ptr_mut(ptr: *mut Self) -> *mut Target;
+    /// Given a pointer to a target, produce a pointer to its raw representation.
+    fn from_target_ptr(ptr: *mut Target) -> *mut Self;
+}
+
+unsafe impl<Target> RawTargetOf<Target> for Target {
+    #[inline(always)]
+    fn target_ptr(ptr: *const Self) -> *const Target {
+        ptr
+    }
+
+    #[inline(always)]
+    fn target_ptr_mut(ptr: *mut Self) -> *mut Target {
+        ptr
+    }
+
+    #[inline(always)]
+    fn from_target_ptr(ptr: *mut Target) -> *mut Self {
+        ptr
+    }
+}
+
+unsafe impl<Target> RawTargetOf<Target> for UnsafeCell<Target> {
+    #[inline(always)]
+    fn target_ptr(ptr: *const Self) -> *const Target {
+        UnsafeCell::raw_get(ptr)
+    }
+
+    #[inline(always)]
+    fn target_ptr_mut(ptr: *mut Self) -> *mut Target {
+        UnsafeCell::raw_get(ptr)
+    }
+
+    #[inline(always)]
+    fn from_target_ptr(ptr: *mut Target) -> *mut Self {
+        // UnsafeCell is repr(transparent)
+        ptr as *mut Self
+    }
+}
+
+unsafe impl<Target> RawTargetOf<Target> for Cell<Target> {
+    #[inline(always)]
+    fn target_ptr(ptr: *const Self) -> *const Target {
+        // Cell is repr(transparent)
+        ptr as *const Target
+    }
+
+    #[inline(always)]
+    fn target_ptr_mut(ptr: *mut Self) -> *mut Target {
+        ptr as *mut Target
+    }
+
+    #[inline(always)]
+    fn from_target_ptr(ptr: *mut Target) -> *mut Self {
+        ptr as *mut Self
+    }
+}
+
+/// A reference target that can be recovered from a partial reference having all its parts.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget, except for
+/// targets with structurally pinned fields.
+///
+/// # Safety
+/// A partial reference of type `FullRef` must have mutable access to all of the target and
+/// `target_ptr_mut` must return a pointer to the target stored in the raw representation.
+pub unsafe trait FullRefTarget<'a>: PartialRefTarget {
+    /// The partial reference having every part of the target as mutable part.
+    type FullRef: PartialRef<'a, Target = Self>;
+
+    /// Given a mutable pointer to the raw representation, produce a pointer to the target.
+    fn target_ptr_mut(ptr: *mut Self::RawTarget) -> *mut Self;
+}
+
 /// Helper trait to associate the target type with a [`PartialRef`] without needing a lifetime.
 pub trait HasTarget {
     /// The referenced type.
     ///
-    // TODO add a warning that Target::RawTarget could be != Target when such a feature lands)
+    /// Note that the pointer stored in a partial reference points to `Target::RawTarget`, which
+    /// can differ from `Target`.
     type Target: PartialRefTarget + ?Sized;
 }
 
@@ -197,9 +430,170 @@ pub trait PartialRef<'a>: HasTarget + Sized {
         unsafe { BorrowedRef::from_raw(self.get_raw()) }
     }
 
+    /// Partially re-borrows a partial reference as constant reference.
+    ///
+    /// This is equivalent to [`borrow`](PartialRef::borrow) for a target type that only contains
+    /// constant parts, but requires only a shared borrow of `self`. This allows re-borrowing without
+    /// a mutable binding and from contexts that only have shared access.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn borrow_const<BorrowedRef, SubsetIndex>(&'a self) -> BorrowedRef
+    where
+        BorrowedRef: PartialRef<'a, Target = Self::Target> + ConstOnly,
+        Self: HasSubset<'a, BorrowedRef, SubsetIndex>,
+    {
+        unsafe { BorrowedRef::from_raw(self.get_raw()) }
+    }
+
+    /// Converts a partial reference into a partial reference having a subset of its parts.
+    ///
+    /// This is equivalent to [`borrow`](PartialRef::borrow), but consumes `self` instead of
+    /// re-borrowing it, so the returned reference keeps the lifetime of the original reference.
+    /// This is useful when storing the converted reference, e.g. in a struct field, or to narrow
+    /// a binding to fewer parts for the rest of its scope, e.g.
+    /// `let r: partial!(Graph, Colors) = r.convert();`, so that the binding's type documents the
+    /// remaining parts.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn convert<ConvertedRef, SubsetIndex>(self) -> ConvertedRef
+    where
+        ConvertedRef: PartialRef<'a, Target = Self::Target>,
+        Self: HasSubset<'a, ConvertedRef, SubsetIndex>,
+    {
+        unsafe { ConvertedRef::from_raw(self.get_raw()) }
+    }
+
+    /// Converts a partial reference into a partial reference with equivalent nested parts.
+    ///
+    /// A nested part can be written with different nestings, e.g. `Nested<Nested<A, B>, C>` as
+    /// written by `A | B | C` and `Nested<A, Nested<B, C>>` as produced by generic code writing
+    /// `A | Inner` for `Inner = B | C`. These refer to the same field, but are different types, so
+    /// [`convert`](PartialRef::convert) doesn't consider them equal. This is equivalent to
+    /// `convert`, but first rewrites every nested part of both partial references into the form
+    /// `Nested<Nested<A, B>, C>`.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn reassociate<ConvertedRef, SubsetIndex>(self) -> ConvertedRef
+    where
+        Self: NormalizeNestedParts<'a>,
+        ConvertedRef: NormalizeNestedParts<'a, Target = Self::Target>,
+        Self::Normalized: HasSubset<'a, ConvertedRef::Normalized, SubsetIndex>,
+    {
+        unsafe { ConvertedRef::from_raw(self.get_raw()) }
+    }
+
+    /// Partially re-borrows a partial reference with equivalent nested parts.
+    ///
+    /// This is equivalent to [`borrow`](PartialRef::borrow), but allows different nestings of the
+    /// same nested parts, as described for [`reassociate`](PartialRef::reassociate).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn borrow_reassociated<BorrowedRef, SubsetIndex>(&'a mut self) -> BorrowedRef
+    where
+        Self: NormalizeNestedParts<'a>,
+        BorrowedRef: NormalizeNestedParts<'a, Target = Self::Target>,
+        Self::Normalized: HasSubset<'a, BorrowedRef::Normalized, SubsetIndex>,
+    {
+        unsafe { BorrowedRef::from_raw(self.get_raw()) }
+    }
+
+    /// Re-borrows a partial reference with all its parts.
+    ///
+    /// This is equivalent to [`borrow`](PartialRef::borrow) with a target type that lists the same
+    /// parts, but doesn't require spelling out that type. The returned reference borrows `self`
+    /// only for the lifetime of the re-borrow, so the original reference can be used again
+    /// afterwards, e.g. to pass it to several functions in sequence.
+    #[inline(always)]
+    fn reborrow(&'a mut self) -> <Self as ShortenParts<'a>>::ShortRef
+    where
+        Self: ShortenParts<'a>,
+    {
+        unsafe { <Self as ShortenParts<'a>>::ShortRef::from_raw(self.get_raw()) }
+    }
+
+    /// Re-borrows a partial reference with all parts as constant parts.
+    ///
+    /// This is equivalent to [`borrow`](PartialRef::borrow) with a target type that lists every
+    /// part as constant part, but doesn't require spelling out that type. The returned reference
+    /// can be copied.
+    #[inline(always)]
+    fn as_const(&'a self) -> <Self as ConstParts<'a>>::ConstRef
+    where
+        Self: ConstParts<'a>,
+    {
+        unsafe { <Self as ConstParts<'a>>::ConstRef::from_raw(self.get_raw()) }
+    }
+
+    /// Mutably borrows a partial reference and exposes it with all parts as constant parts.
+    ///
+    /// The returned guard dereferences to the same reference as [`as_const`](PartialRef::as_const)
+    /// would return, but keeps this reference borrowed mutably until it is dropped. After that the
+    /// mutable parts can be used again.
+    #[inline(always)]
+    fn downgrade_scope(&'a mut self) -> DowngradeGuard<'a, Self>
+    where
+        Self: ConstParts<'a>,
+    {
+        DowngradeGuard {
+            reference: unsafe { <Self as ConstParts<'a>>::ConstRef::from_raw(self.get_raw()) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Mutably borrows a partial reference and exposes it as a constant reference that can be
+    /// shared between threads.
+    ///
+    /// The returned [`Frozen`] reference has all parts as constant parts and can be copied. It
+    /// implements [`Send`] and [`Sync`] when all parts are [`Field`] parts of types implementing
+    /// [`Sync`]. Once all copies are gone, the mutable parts of this reference can be used again.
+    #[inline(always)]
+    fn freeze(&'a mut self) -> Frozen<<Self as ConstParts<'a>>::ConstRef>
+    where
+        Self: ConstParts<'a>,
+    {
+        Frozen {
+            reference: unsafe { <Self as ConstParts<'a>>::ConstRef::from_raw(self.get_raw()) },
+        }
+    }
+
+    /// Converts a partial reference having every part of the target as mutable part into a
+    /// mutable reference to the target.
+    ///
+    /// This is useful for calling functions that expect a plain mutable reference. Targets with
+    /// structurally pinned fields don't support this.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn into_full<SubsetIndex>(self) -> &'a mut <Self as HasTarget>::Target
+    where
+        <Self as HasTarget>::Target: FullRefTarget<'a>,
+        Self:
+            HasSubset<'a, <<Self as HasTarget>::Target as FullRefTarget<'a>>::FullRef, SubsetIndex>,
+    {
+        unsafe { &mut *<Self as HasTarget>::Target::target_ptr_mut(self.get_raw()) }
+    }
+
+    /// Convert into a partial reference having the same parts listed in canonical order.
+    ///
+    /// Partial references having the same parts listed in a different order are converted into
+    /// the same type. See [`Canonicalize`] for details.
+    #[inline(always)]
+    fn canonicalize(self) -> <Self as Canonicalize<'a>>::Canonical
+    where
+        Self: Canonicalize<'a>,
+    {
+        unsafe { <Self as Canonicalize<'a>>::Canonical::from_raw(self.get_raw()) }
+    }
+
     /// Access a part of the referenced value.
     ///
-    /// This returns a plain reference to a single part.
+    /// This returns a plain reference to a single part. The returned reference only borrows `self`
+    /// for as long as it is used, not for the whole lifetime `'a` of the partial reference, so this
+    /// can be used repeatedly in generic code where `'a` is a lifetime parameter.
     ///
     /// The parameter is only present for type inference, its value is ignored. As all parts
     /// implement [`Default`] it is always possible to pass a default value, which is useful in
@@ -207,11 +601,12 @@ pub trait PartialRef<'a>: HasTarget + Sized {
     ///
     /// Usually the type parameters can be inferred.
     #[inline(always)]
-    fn part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
-        &'a self,
+    fn part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
         _part: FieldPartSpec,
-    ) -> &'a FieldType
+    ) -> &'b FieldType
     where
+        'a: 'b,
         FieldType: ?Sized,
         FieldPart: Part<PartType = Field<FieldType>>,
         FieldPartSpec: PartSpec<FieldPart>,
@@ -219,11 +614,33 @@ pub trait PartialRef<'a>: HasTarget + Sized {
         Self::Target: HasPart<FieldPart> + 'a,
     {
         unsafe {
-            <Const<FieldPart, Ref<'a, Self::Target>> as PartialRef<'a>>::from_raw(self.get_raw())
+            <Const<FieldPart, Ref<'b, Self::Target>> as PartialRef<'b>>::from_raw(self.get_raw())
                 .get_part()
         }
     }
 
+    /// Copy the value of a part of the referenced value.
+    ///
+    /// This is equivalent to dereferencing the result of [`part`](PartialRef::part) for field
+    /// types implementing [`Copy`], but only borrows `self` for the duration of the call. This
+    /// allows reading a part while the partial reference is used mutably in the same expression.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn part_copied<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &self,
+        _part: FieldPartSpec,
+    ) -> FieldType
+    where
+        FieldType: Copy,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart>,
+    {
+        unsafe { *<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw()) }
+    }
+
     /// Mutable access to a part of the referenced value.
     ///
     /// This returns a plain mutable reference to a single part.
@@ -234,23 +651,162 @@ pub trait PartialRef<'a>: HasTarget + Sized {
     ///
     /// Usually the type parameters can be inferred.
     #[inline(always)]
-    fn part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
-        &'a mut self,
+    fn part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b mut self,
         _part: FieldPartSpec,
-    ) -> &'a mut FieldType
+    ) -> &'b mut FieldType
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            <Mut<FieldPart, Ref<'b, Self::Target>> as PartialRef<'b>>::from_raw(self.get_raw())
+                .get_part_mut()
+        }
+    }
+
+    /// Raw pointer to a part of the referenced value.
+    ///
+    /// This is useful for FFI and intrusive data structures. The pointer is valid for the lifetime
+    /// `'a` of the partial reference. Reading through it is only allowed while the part isn't
+    /// mutably accessed through another reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn part_ptr<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &self,
+        _part: FieldPartSpec,
+    ) -> *const FieldType
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart>,
+    {
+        unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw()) }
+    }
+
+    /// Raw mutable pointer to a part of the referenced value.
+    ///
+    /// This is the mutable counterpart of [`part_ptr`](PartialRef::part_ptr). Writing through the
+    /// pointer is only allowed while the part isn't accessed through another reference, including
+    /// references returned by this partial reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn part_ptr_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &mut self,
+        _part: FieldPartSpec,
+    ) -> *mut FieldType
     where
         FieldType: ?Sized,
         FieldPart: Part<PartType = Field<FieldType>>,
         FieldPartSpec: PartSpec<FieldPart>,
         Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart>,
+    {
+        unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr_mut(self.get_raw()) }
+    }
+
+    /// Access a part of the referenced value, selected by a type argument.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) but doesn't require a value of the part,
+    /// e.g. `part_of::<Colors, _>()`. This is useful in generic code and for parts that cannot be
+    /// named as values.
+    #[inline(always)]
+    fn part_of<'b, FieldPart, PartIndex>(&'b self) -> &'b FieldPart::FieldType
+    where
+        'a: 'b,
+        FieldPart: FieldPartType,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            <Const<FieldPart, Ref<'b, Self::Target>> as PartialRef<'b>>::from_raw(self.get_raw())
+                .get_part()
+        }
+    }
+
+    /// Mutable access to a part of the referenced value, selected by a type argument.
+    ///
+    /// This is equivalent to [`part_mut`](PartialRef::part_mut) but doesn't require a value of
+    /// the part, e.g. `part_mut_of::<Colors, _>()`.
+    #[inline(always)]
+    fn part_mut_of<'b, FieldPart, PartIndex>(&'b mut self) -> &'b mut FieldPart::FieldType
+    where
+        'a: 'b,
+        FieldPart: FieldPartType,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
         Self::Target: HasPart<FieldPart> + 'a,
     {
         unsafe {
-            <Mut<FieldPart, Ref<'a, Self::Target>> as PartialRef<'a>>::from_raw(self.get_raw())
+            <Mut<FieldPart, Ref<'b, Self::Target>> as PartialRef<'b>>::from_raw(self.get_raw())
                 .get_part_mut()
         }
     }
 
+    /// Fallible access to a part of the referenced value.
+    ///
+    /// This works for all parts whose part type implements [`TryPartType`]. It returns `None` when
+    /// the part's field is currently absent. For parts of type [`Field`] this always succeeds.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn try_part<'b, FieldPartSpec, FieldPart, PartIndex>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> Option<&'b <FieldPart::PartType as TryPartType>::FieldType>
+    where
+        'a: 'b,
+        FieldPart: Part,
+        FieldPart::PartType: TryPartType,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            <FieldPart::PartType as TryPartType>::try_field_ptr(<Self::Target as HasPart<
+                FieldPart,
+            >>::part_ptr(
+                self.get_raw()
+            ))
+            .map(|ptr| &*ptr)
+        }
+    }
+
+    /// Fallible mutable access to a part of the referenced value.
+    ///
+    /// This is the mutable counterpart of [`try_part`](PartialRef::try_part).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn try_part_mut<'b, FieldPartSpec, FieldPart, PartIndex>(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> Option<&'b mut <FieldPart::PartType as TryPartType>::FieldType>
+    where
+        'a: 'b,
+        FieldPart: Part,
+        FieldPart::PartType: TryPartType,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            <FieldPart::PartType as TryPartType>::try_field_ptr_mut(<Self::Target as HasPart<
+                FieldPart,
+            >>::part_ptr_mut(
+                self.get_raw()
+            ))
+            .map(|ptr| &mut *ptr)
+        }
+    }
+
     /// Partially re-borrows a partial reference, splitting off the remaining parts.
     ///
     /// This is equivalent to [`borrow`](PartialRef::borrow) but also returns a second partial
@@ -272,6 +828,79 @@ pub trait PartialRef<'a>: HasTarget + Sized {
         unsafe { (BorrowedRef::from_raw(ptr), Self::Remainder::from_raw(ptr)) }
     }
 
+    /// Splits a partial reference into its constant parts and its mutable parts.
+    ///
+    /// This returns a copyable partial reference having all constant parts of `self` and a partial
+    /// reference having all mutable parts of `self`, without listing the parts. Both can be used
+    /// at the same time.
+    #[inline(always)]
+    fn split_const(
+        &'a mut self,
+    ) -> (
+        <Self as SplitConst<'a>>::ConstRef,
+        <Self as SplitConst<'a>>::MutRef,
+    )
+    where
+        Self: SplitConst<'a>,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                <Self as SplitConst<'a>>::ConstRef::from_raw(ptr),
+                <Self as SplitConst<'a>>::MutRef::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Partially re-borrows a partial reference multiple times at once, splitting off the remaining
+    /// parts.
+    ///
+    /// The re-borrowed references are given as a tuple type, e.g.
+    /// `r.split_n::<(partial!(_, mut X), partial!(_, mut Y)), _>()`, and this returns a tuple of the
+    /// re-borrowed references followed by the remaining reference. This is equivalent to repeatedly
+    /// calling [`split_borrow`](PartialRef::split_borrow) on the remaining reference. Tuples of 2
+    /// to 6 references are supported.
+    ///
+    /// Usually the index type parameter can be inferred.
+    #[inline(always)]
+    fn split_n<BorrowedRefs, SplitIndex>(&'a mut self) -> BorrowedRefs::Output
+    where
+        BorrowedRefs: SplitTuple<'a, Self, SplitIndex>,
+    {
+        unsafe { BorrowedRefs::split_raw(self.get_raw()) }
+    }
+
+    /// Joins a partial reference with the remainder of a split, undoing the split.
+    ///
+    /// The partial reference `other` has to be the remainder of splitting the returned reference
+    /// into `self`, as returned by [`split_borrow`](PartialRef::split_borrow). Additionally all
+    /// constant parts of `self` have to be constant parts of the returned reference, as there is no
+    /// way to tell whether a constant part is shared with another reference. Thus a mutable part
+    /// that was re-borrowed as constant part cannot be joined.
+    ///
+    /// The joined reference has the lifetime of `self`, which usually is the shorter lifetime of the
+    /// re-borrowed reference.
+    ///
+    /// Usually the type parameters can be inferred from the expected result.
+    ///
+    /// # Panics
+    /// Panics if `self` and `other` don't reference the same value.
+    #[inline(always)]
+    fn join<Other, Joined, SubsetIndex, ConstIndex>(self, other: Other) -> Joined
+    where
+        Self: Sized + ConstPartsIn<Joined, ConstIndex>,
+        Other: ShortenParts<'a, Target = <Self as HasTarget>::Target>,
+        Joined: PartialRef<'a, Target = <Self as HasTarget>::Target>
+            + HasSubset<'a, Self, SubsetIndex, Remainder = Other::ShortRef>,
+    {
+        let ptr = self.get_raw();
+        assert!(
+            ptr::eq(ptr, other.get_raw()),
+            "cannot join partial references to different values"
+        );
+        unsafe { Joined::from_raw(ptr) }
+    }
+
     /// Access a part of the referenced value, splitting off the remaining parts.
     ///
     /// This is equivalent to [`part`](PartialRef::part) but also returns a partial reference as
@@ -323,622 +952,5800 @@ pub trait PartialRef<'a>: HasTarget + Sized {
             )
         }
     }
-}
-
-/// Construction of partial references.
-///
-/// See also [`IntoPartialRefMut`].
-///
-/// This trait gets an automatic implementation for references (mutable or immutable) to any type
-/// that has a derive statement for [`PartialRefTarget`]. Usually there is no need to implement this
-/// trait manually.
-pub trait IntoPartialRef<'a> {
-    type Ref: PartialRef<'a>;
 
-    /// Convert a mutable or immutable reference into a partial reference.
+    /// Access to a target stored in an optional field, splitting off the remaining parts.
+    ///
+    /// The part's field has to be an `Option` of a target that can be converted into a partial
+    /// reference with all parts (see [`FullRefTarget`]). When the option is `Some`, this returns a
+    /// partial reference with all parts of the contained target as constant parts.
+    /// Otherwise it returns `None`. In both cases the remaining parts are returned as described in
+    /// [`split_borrow`](PartialRef::split_borrow).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn try_split_part<FieldPartSpec, FieldPart, PartIndex, InnerTarget>(
+        &'a mut self,
+        part: FieldPartSpec,
+    ) -> (
+        Option<<InnerTarget::FullRef as ConstParts<'a>>::ConstRef>,
+        Self::Remainder,
+    )
+    where
+        FieldPart: Part<PartType = Field<Option<InnerTarget>>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        InnerTarget: FullRefTarget<'a> + PartialRefTarget<RawTarget = InnerTarget> + 'a,
+        InnerTarget::FullRef: ConstParts<'a>,
+    {
+        let (field, remainder) = self.split_part(part);
+        let inner = field.as_ref().map(|inner| unsafe {
+            <InnerTarget::FullRef as ConstParts<'a>>::ConstRef::from_raw(
+                inner as *const InnerTarget as *mut InnerTarget,
+            )
+        });
+        (inner, remainder)
+    }
+
+    /// Mutable access to a target stored in an optional field, splitting off the remaining parts.
+    ///
+    /// This is the mutable counterpart of [`try_split_part`](PartialRef::try_split_part). The
+    /// returned partial reference has all parts of the contained target as mutable parts.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn try_split_part_mut<FieldPartSpec, FieldPart, PartIndex, InnerTarget>(
+        &'a mut self,
+        part: FieldPartSpec,
+    ) -> (Option<InnerTarget::FullRef>, Self::Remainder)
+    where
+        FieldPart: Part<PartType = Field<Option<InnerTarget>>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        InnerTarget: FullRefTarget<'a> + PartialRefTarget<RawTarget = InnerTarget> + 'a,
+    {
+        let (field, remainder) = self.split_part_mut(part);
+        let inner = field
+            .as_mut()
+            .map(|inner| unsafe { InnerTarget::FullRef::from_raw(inner) });
+        (inner, remainder)
+    }
+
+    /// Mutable access to two halves of a slice part, splitting off the remaining parts.
+    ///
+    /// The part's field has to be a slice, a `Vec` or an array (see [`SliceField`]). The field is
+    /// split into the elements before `mid` and the elements starting at `mid`, which can be used
+    /// independently of each other and of the remaining parts.
+    ///
+    /// # Panics
+    /// Panics if `mid` is larger than the length of the field.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn split_slice_part_at<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        part: FieldPartSpec,
+        mid: usize,
+    ) -> (
+        &'a mut [FieldType::Element],
+        &'a mut [FieldType::Element],
+        Self::Remainder,
+    )
+    where
+        FieldType: SliceField + ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let (field, rest) = self.split_part_mut(part);
+        let (head, tail) = field.as_mut_slice().split_at_mut(mid);
+        (head, tail, rest)
+    }
+
+    /// Mutable access to several disjoint chunks of a slice part, splitting off the remaining parts.
+    ///
+    /// The part's field has to be a slice, a `Vec` or an array (see [`SliceField`]). This returns
+    /// mutable references to the elements of each of the `ranges`, which are selected at runtime,
+    /// e.g. to split a worklist between threads. It fails if a range is out of bounds or if two
+    /// ranges overlap. The remaining parts are returned in either case. A chunk can be passed along
+    /// with a partial reference using [`Extended`] with the part [`Chunk<FieldPart>`](Chunk).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn split_chunks_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        part: FieldPartSpec,
+        ranges: &[Range<usize>],
+    ) -> (
+        Result<Vec<&'a mut [FieldType::Element]>, DisjointMutError>,
+        Self::Remainder,
+    )
+    where
+        FieldType: SliceField + ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let (field, rest) = self.split_part_mut(part);
+        (split_chunks(field.as_mut_slice(), ranges), rest)
+    }
+
+    /// Splits a slice part into range parts, splitting off the remaining parts.
+    ///
+    /// The part's field has to be a slice, a `Vec` or an array (see [`SliceField`]). This returns
+    /// a partial reference `RangesRef` having only [`RangePart`]s and [`ElemPart`]s of the field,
+    /// e.g. `partial!(Mixer, mut RangePart<Samples, 0, 512>, mut RangePart<Samples, 512, 1024>)`.
+    /// The ranges have to be listed in increasing order without overlapping, which is checked at
+    /// compile time. An element part counts as the range containing only its element.
+    ///
+    /// # Panics
+    /// Panics if the end of the last range is larger than the length of the field.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn split_range_parts_mut<RangesRef, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        part: FieldPartSpec,
+    ) -> (RangesRef, Self::Remainder)
+    where
+        FieldType: SliceField + ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        RangesRef: PartialRef<'a, Target = Self::Target> + RangePartList<FieldPart>,
+    {
+        let ptr = self.get_raw();
+        let (field, rest) = self.split_part_mut(part);
+        assert!(
+            RangesRef::END <= field.as_mut_slice().len(),
+            "range part out of bounds"
+        );
+        (unsafe { RangesRef::from_raw(ptr) }, rest)
+    }
+
+    /// Mutable access to several distinct elements of a slice part, splitting off the remaining
+    /// parts.
+    ///
+    /// The part's field has to be a slice, a `Vec` or an array (see [`SliceField`]). This returns
+    /// mutable references to the elements at `indices`, which fails with
+    /// [`IndexOutOfBounds`](DisjointMutError::IndexOutOfBounds) if an index is out of bounds and
+    /// with [`OverlappingIndices`](DisjointMutError::OverlappingIndices) if an index is given more
+    /// than once. The remaining parts are returned in either case.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn get_disjoint_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType, const N: usize>(
+        &'a mut self,
+        part: FieldPartSpec,
+        indices: [usize; N],
+    ) -> (
+        Result<[&'a mut FieldType::Element; N], DisjointMutError>,
+        Self::Remainder,
+    )
+    where
+        FieldType: SliceField + ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let (field, rest) = self.split_part_mut(part);
+        (get_disjoint_mut(field.as_mut_slice(), indices), rest)
+    }
+
+    /// Mutable access to the values of several distinct keys of a map part, splitting off the
+    /// remaining parts.
+    ///
+    /// The part's field has to be a `HashMap` or a `BTreeMap` (see [`MapField`]). This returns
+    /// mutable references to the values for `keys`, which fails with
+    /// [`OverlappingIndices`](DisjointMutError::OverlappingIndices) if a key is given more than
+    /// once and with [`IndexOutOfBounds`](DisjointMutError::IndexOutOfBounds) if a key is
+    /// missing. The remaining parts are returned in either case. A value can be passed along with a
+    /// partial reference using [`Extended`] with the part [`KeyPart<FieldPart>`](KeyPart).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn get_disjoint_keys_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType, const N: usize>(
+        &'a mut self,
+        part: FieldPartSpec,
+        keys: [&FieldType::Key; N],
+    ) -> (
+        Result<[&'a mut FieldType::Value; N], DisjointMutError>,
+        Self::Remainder,
+    )
+    where
+        FieldType: MapField + ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let (field, rest) = self.split_part_mut(part);
+        for (index, key) in keys.iter().enumerate() {
+            if keys[..index].contains(key) {
+                return (Err(DisjointMutError::OverlappingIndices), rest);
+            }
+        }
+        let values = field.get_disjoint_values_mut(keys);
+        if values.iter().any(Option::is_none) {
+            return (Err(DisjointMutError::IndexOutOfBounds), rest);
+        }
+        (Ok(values.map(Option::unwrap)), rest)
+    }
+
+    /// Swap the fields of two distinct mutable parts of the same type.
+    ///
+    /// This is equivalent to splitting off the first part using
+    /// [`split_part_mut`](PartialRef::split_part_mut) and calling [`std::mem::swap`] with the
+    /// second part of the remainder. Passing the same part twice fails to compile.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn swap_between<
+        FieldPartSpecA,
+        FieldPartA,
+        PartIndexA,
+        FieldPartSpecB,
+        FieldPartB,
+        PartIndexB,
+        FieldType,
+    >(
+        &'a mut self,
+        part_a: FieldPartSpecA,
+        _part_b: FieldPartSpecB,
+    ) where
+        FieldPartA: Part<PartType = Field<FieldType>>,
+        FieldPartSpecA: PartSpec<FieldPartA>,
+        FieldPartB: Part<PartType = Field<FieldType>>,
+        FieldPartSpecB: PartSpec<FieldPartB>,
+        Self: PluckMut<'a, FieldPartA, PartIndexA>,
+        Self::Remainder: PluckMut<'a, FieldPartB, PartIndexB>,
+        Self::Target: HasPart<FieldPartA> + HasPart<FieldPartB> + 'a,
+        FieldType: 'a,
+    {
+        let (field_a, remainder) = self.split_part_mut(part_a);
+        let field_b = unsafe {
+            <Mut<FieldPartB, Ref<'a, Self::Target>> as PartialRef<'a>>::from_raw(
+                remainder.get_raw(),
+            )
+            .get_part_mut()
+        };
+        std::mem::swap(field_a, field_b)
+    }
+
+    /// Access multiple parts of the referenced value at once.
+    ///
+    /// The parts are given as a tuple, where a part wrapped in [`MutPart`] is accessed mutably and
+    /// any other part is accessed as constant. This returns a tuple of the corresponding references.
+    /// The same checks as for [`borrow`](PartialRef::borrow) apply, so a mutable part cannot be
+    /// accessed together with any overlapping part. Tuples of up to 8 parts are supported.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn parts<Parts, FieldParts, SubsetIndex>(&'a mut self, _parts: Parts) -> Parts::Output
+    where
+        Parts: PartsTuple<'a, <Self as HasTarget>::Target, FieldParts>,
+        Self: HasSubset<'a, Parts::Ref, SubsetIndex>,
+    {
+        unsafe { Parts::access_parts(self.get_raw()) }
+    }
+
+    /// Extends a partial reference by an external field.
+    ///
+    /// The returned [`Extended`] reference contains this partial reference as well as the passed
+    /// mutable reference, which is accessed as the part `ExtraPart`, e.g.
+    /// `r.extend_with::<Scratch>(&mut scratch)`.
+    #[inline(always)]
+    fn extend_with<'b, ExtraPart>(
+        self,
+        field: &'b mut ExtraPart::FieldType,
+    ) -> Extended<'b, ExtraPart, Self>
+    where
+        ExtraPart: FieldPartType,
+    {
+        Extended::new(self, field)
+    }
+
+    /// Adds the abstract part of a token to a partial reference.
+    ///
+    /// The returned partial reference has the mutable part [`TokenPart<Token>`](TokenPart) in
+    /// addition to the parts of this reference. The token stays borrowed for as long as the part is
+    /// used, so no other partial reference can have the same part at the same time.
+    #[inline(always)]
+    fn attach_token<'t, Token>(self, _token: &'t mut Token) -> Mut<TokenPart<'t, Token>, Self>
+    where
+        Token: PartToken,
+    {
+        unsafe { Mut::from_raw(self.get_raw()) }
+    }
+
+    /// Adds the abstract part of a token to a partial reference as a constant part.
+    ///
+    /// This is like [`attach_token`](PartialRef::attach_token), but only borrows the token
+    /// immutably, so the same token can be attached to several partial references at once.
+    #[inline(always)]
+    fn attach_token_const<'t, Token>(self, _token: &'t Token) -> Const<TokenPart<'t, Token>, Self>
+    where
+        Token: PartToken,
+    {
+        unsafe { Const::from_raw(self.get_raw()) }
+    }
+
+    /// Replaces a mutable abstract part by the same part in the typestate `State`.
+    ///
+    /// The returned partial reference has the mutable part [`Typestate<StatePart,
+    /// State>`](Typestate) in place of `StatePart`. The typestate is usually inferred from the
+    /// function the returned reference is passed to.
+    #[inline(always)]
+    fn enter_state<State, StatePartSpec, StatePart, PartIndex>(
+        self,
+        _part: StatePartSpec,
+    ) -> Mut<Typestate<StatePart, State>, Self::Remainder>
+    where
+        StatePart: Part<PartType = AbstractPart>,
+        StatePartSpec: PartSpec<StatePart>,
+        Self: PluckMut<'a, StatePart, PartIndex>,
+        Self::Target: HasPart<StatePart> + 'a,
+    {
+        unsafe { Mut::from_raw(self.get_raw()) }
+    }
+
+    /// Moves an abstract part from one typestate to another.
+    ///
+    /// The part [`Typestate<StatePart, OldState>`](Typestate) has to be a mutable part and is
+    /// replaced by the mutable part `Typestate<StatePart, NewState>`. As this consumes the partial
+    /// reference, the old typestate cannot be used afterwards. The typestates are usually inferred.
+    #[inline(always)]
+    fn transition_state<NewState, OldState, StatePartSpec, StatePart, PartIndex>(
+        self,
+        _part: StatePartSpec,
+    ) -> Mut<Typestate<StatePart, NewState>, Self::Remainder>
+    where
+        StatePart: Part<PartType = AbstractPart>,
+        StatePartSpec: PartSpec<StatePart>,
+        Self: PluckMut<'a, Typestate<StatePart, OldState>, PartIndex>,
+        Self::Target: HasPart<StatePart> + 'a,
+    {
+        unsafe { Mut::from_raw(self.get_raw()) }
+    }
+
+    /// Projects a mutable part onto a sub-field of its field, splitting off the remaining parts.
+    ///
+    /// The closure `f` selects the sub-field, e.g. `r.map_part_mut(Config, |c| &mut c.limits)`.
+    /// The returned [`Extended`] reference contains the remaining parts and the sub-field as the
+    /// part `MappedPart`, which only needs to be a [`Field`] part of the sub-field's type, e.g.
+    /// declared using [`part!`](crate::part). This avoids deriving [`PartialRefTarget`] for the
+    /// field's type just to access one of its fields.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn map_part_mut<MappedPart, FieldPartSpec, FieldPart, PartIndex, FieldType, F>(
+        &'a mut self,
+        part: FieldPartSpec,
+        f: F,
+    ) -> Extended<'a, MappedPart, Self::Remainder>
+    where
+        MappedPart: FieldPartType,
+        MappedPart::FieldType: 'a,
+        FieldType: ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnOnce(&'a mut FieldType) -> &'a mut MappedPart::FieldType,
+    {
+        let (field, remainder) = self.split_part_mut(part);
+        Extended::new(remainder, f(field))
+    }
+
+    /// Calls a closure with a part of the referenced value and the remaining parts.
+    ///
+    /// This is equivalent to calling `f` with the results of [`split_part`](PartialRef::split_part)
+    /// and returns the result of `f`. The partial reference can be used again after `f` returns.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn with_part<FieldPartSpec, FieldPart, PartIndex, FieldType, F, R>(
+        &'a mut self,
+        part: FieldPartSpec,
+        f: F,
+    ) -> R
+    where
+        FieldType: ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnOnce(&'a FieldType, Self::Remainder) -> R,
+    {
+        let (field, rest) = self.split_part(part);
+        f(field, rest)
+    }
+
+    /// Calls a closure with mutable access to a part of the referenced value and the remaining
+    /// parts.
+    ///
+    /// This is equivalent to calling `f` with the results of
+    /// [`split_part_mut`](PartialRef::split_part_mut) and returns the result of `f`. The partial
+    /// reference can be used again after `f` returns.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn with_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType, F, R>(
+        &'a mut self,
+        part: FieldPartSpec,
+        f: F,
+    ) -> R
+    where
+        FieldType: ?Sized + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnOnce(&'a mut FieldType, Self::Remainder) -> R,
+    {
+        let (field, rest) = self.split_part_mut(part);
+        f(field, rest)
+    }
+
+    /// Calls a closure with access to a thread-local part and the remaining parts.
+    ///
+    /// The value of the [`ThreadLocalPart`] for the current thread is borrowed for the duration of
+    /// the call as by [`RefCell::borrow`](std::cell::RefCell::borrow). Returns the result of `f`.
+    ///
+    /// Usually the type parameters can be inferred.
+    ///
+    /// # Panics
+    /// Panics if the value is currently mutably borrowed, e.g. using a partial reference to another
+    /// target, or if the thread-local storage was already destroyed (see
+    /// [`LocalKey::with`](std::thread::LocalKey::with)).
+    #[inline(always)]
+    fn with_local_part<FieldPartSpec, FieldPart, PartIndex, F, R>(
+        &'a mut self,
+        _part: FieldPartSpec,
+        f: F,
+    ) -> R
+    where
+        FieldPart: ThreadLocalPart,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnOnce(&FieldPart::FieldType, Self::Remainder) -> R,
+    {
+        let rest = unsafe { Self::Remainder::from_raw(self.get_raw()) };
+        FieldPart::local_key().with(|cell| f(&cell.borrow(), rest))
+    }
+
+    /// Calls a closure with mutable access to a thread-local part and the remaining parts.
+    ///
+    /// The value of the [`ThreadLocalPart`] for the current thread is borrowed for the duration of
+    /// the call as by [`RefCell::borrow_mut`](std::cell::RefCell::borrow_mut). Returns the result of
+    /// `f`.
+    ///
+    /// Usually the type parameters can be inferred.
+    ///
+    /// # Panics
+    /// Panics if the value is currently borrowed, e.g. using a partial reference to another target,
+    /// or if the thread-local storage was already destroyed (see
+    /// [`LocalKey::with`](std::thread::LocalKey::with)).
+    #[inline(always)]
+    fn with_local_part_mut<FieldPartSpec, FieldPart, PartIndex, F, R>(
+        &'a mut self,
+        _part: FieldPartSpec,
+        f: F,
+    ) -> R
+    where
+        FieldPart: ThreadLocalPart,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnOnce(&mut FieldPart::FieldType, Self::Remainder) -> R,
+    {
+        let rest = unsafe { Self::Remainder::from_raw(self.get_raw()) };
+        FieldPart::local_key().with(|cell| f(&mut cell.borrow_mut(), rest))
+    }
+
+    /// Calls a closure for each element of a mutable collection part, together with the remaining
+    /// parts.
+    ///
+    /// The part's field has to be a collection whose mutable reference can be iterated, e.g. a
+    /// `Vec<T>`. For each element, `f` is called with the element and a mutable reference to the
+    /// partial reference of the remaining parts, as returned by
+    /// [`split_part_mut`](PartialRef::split_part_mut). The remaining parts can thus be re-borrowed
+    /// for each element. The partial reference can be used again after this returns.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn for_each_element_mut<FieldPartSpec, FieldPart, PartIndex, FieldType, F>(
+        &'a mut self,
+        part: FieldPartSpec,
+        mut f: F,
+    ) where
+        FieldType: ?Sized + 'a,
+        &'a mut FieldType: IntoIterator,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        F: FnMut(<&'a mut FieldType as IntoIterator>::Item, &mut Self::Remainder),
+    {
+        let (field, mut rest) = self.split_part_mut(part);
+        for element in field {
+            f(element, &mut rest);
+        }
+    }
+
+    /// Check whether the referenced enum currently is a specific variant.
+    ///
+    /// The variant is identified by its variant part, which has to be present in the reference.
+    /// When the check succeeds, this returns a [`Witness`], which wraps this partial reference and
+    /// provides access to parts of the variant's fields. Otherwise the partial reference is
+    /// returned unchanged. Use [`reborrow`](PartialRef::reborrow) to keep using the partial
+    /// reference after the witness is dropped.
+    ///
+    /// For an enum field of the target, the variant part is a nested part, e.g.
+    /// `Connection | Connected`. The returned witness then only gives access to the variant fields
+    /// of that field, e.g. `Connection | Address`.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn witness<VariantPartSpec, VariantPart, PartIndex>(
+        self,
+        _variant: VariantPartSpec,
+    ) -> Result<Witness<Self, VariantPart>, Self>
+    where
+        VariantPart: Part<PartType = AbstractPart>,
+        VariantPartSpec: PartSpec<VariantPart>,
+        Self: PluckConst<'a, VariantPart, PartIndex>,
+        Self::Target: HasVariant<VariantPart>,
+    {
+        if unsafe { <Self::Target as HasVariant<VariantPart>>::is_variant(self.get_raw()) } {
+            Ok(Witness {
+                reference: self,
+                phantom: PhantomData,
+            })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Access to a part of the value pointed to by a shared pointer field.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) for parts of type [`SharedField`]. There
+    /// is no mutable access to such parts, even for mutable parts.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn shared_part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> &'b FieldType
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = SharedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { &*<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw()) }
+    }
+
+    /// Access to a part of the value pointed to by a shared pointer field, splitting off the
+    /// remaining parts.
+    ///
+    /// This is equivalent to [`shared_part`](PartialRef::shared_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_shared_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (&'a FieldType, Self::Remainder)
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = SharedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                &*<Self::Target as HasPart<FieldPart>>::part_ptr(ptr),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Pinned access to a part of a pinned field.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) for parts of type [`PinnedField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn pin_part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> Pin<&'b FieldType>
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = PinnedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            Pin::new_unchecked(&*<Self::Target as HasPart<FieldPart>>::part_ptr(
+                self.get_raw(),
+            ))
+        }
+    }
+
+    /// Pinned mutable access to a part of a pinned field.
+    ///
+    /// This is equivalent to [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`PinnedField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn pin_part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> Pin<&'b mut FieldType>
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = PinnedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            Pin::new_unchecked(&mut *<Self::Target as HasPart<FieldPart>>::part_ptr_mut(
+                self.get_raw(),
+            ))
+        }
+    }
+
+    /// Pinned access to a part of a pinned field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`pin_part`](PartialRef::pin_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_pin_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (Pin<&'a FieldType>, Self::Remainder)
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = PinnedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                Pin::new_unchecked(&*<Self::Target as HasPart<FieldPart>>::part_ptr(ptr)),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Pinned mutable access to a part of a pinned field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`pin_part_mut`](PartialRef::pin_part_mut) but also returns a
+    /// partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_pin_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (Pin<&'a mut FieldType>, Self::Remainder)
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = PinnedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                Pin::new_unchecked(&mut *<Self::Target as HasPart<FieldPart>>::part_ptr_mut(
+                    ptr,
+                )),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Access a part of a union field.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) for parts of type [`UnionField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    ///
+    /// # Safety
+    /// The field must be initialized with a valid value of its type. As all fields of a union
+    /// overlap, the returned reference must not be used while a mutable reference to another field
+    /// of the same union exists.
+    #[inline(always)]
+    unsafe fn part_unchecked<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a self,
+        _part: FieldPartSpec,
+    ) -> &'a FieldType
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = UnionField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        &*<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw())
+    }
+
+    /// Mutable access to a part of a union field.
+    ///
+    /// This is equivalent to [`part_mut`](PartialRef::part_mut) for parts of type [`UnionField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    ///
+    /// # Safety
+    /// The field must be initialized with a valid value of its type. As all fields of a union
+    /// overlap, the returned reference must not be used while any reference to another field of
+    /// the same union exists.
+    #[inline(always)]
+    unsafe fn part_unchecked_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> &'a mut FieldType
+    where
+        FieldType: ?Sized,
+        FieldPart: Part<PartType = UnionField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        &mut *<Self::Target as HasPart<FieldPart>>::part_ptr_mut(self.get_raw())
+    }
+
+    /// Access to a part of a possibly unaligned field.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) for parts of type [`UnalignedField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn unaligned_part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> Unaligned<'b, FieldType>
+    where
+        'a: 'b,
+        FieldPart: Part<PartType = UnalignedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        Unaligned {
+            ptr: unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw()) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Mutable access to a part of a possibly unaligned field.
+    ///
+    /// This is equivalent to [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`UnalignedField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn unaligned_part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> UnalignedMut<'b, FieldType>
+    where
+        'a: 'b,
+        FieldPart: Part<PartType = UnalignedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        UnalignedMut {
+            ptr: unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr_mut(self.get_raw()) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Access to a part of a possibly unaligned field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`unaligned_part`](PartialRef::unaligned_part) but also returns a
+    /// partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_unaligned_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (Unaligned<'a, FieldType>, Self::Remainder)
+    where
+        FieldPart: Part<PartType = UnalignedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                Unaligned {
+                    ptr: <Self::Target as HasPart<FieldPart>>::part_ptr(ptr),
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Mutable access to a part of a possibly unaligned field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`unaligned_part_mut`](PartialRef::unaligned_part_mut) but also
+    /// returns a partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_unaligned_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (UnalignedMut<'a, FieldType>, Self::Remainder)
+    where
+        FieldPart: Part<PartType = UnalignedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                UnalignedMut {
+                    ptr: <Self::Target as HasPart<FieldPart>>::part_ptr_mut(ptr),
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Access to a part of a bit range of an integer field.
+    ///
+    /// This is the equivalent of [`part`](PartialRef::part) for parts of type [`BitField`]. It
+    /// returns a proxy that reads the bits of the field covered by the part.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn bit_part<
+        'b,
+        FieldPartSpec,
+        FieldPart,
+        PartIndex,
+        FieldType,
+        const LOW: u32,
+        const HIGH: u32,
+    >(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> Bits<'b, FieldType>
+    where
+        'a: 'b,
+        FieldType: BitsField,
+        FieldPart: Part<PartType = BitField<FieldType, LOW, HIGH>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        Bits {
+            ptr: unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw()) },
+            low: LOW,
+            high: HIGH,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Mutable access to a part of a bit range of an integer field.
+    ///
+    /// This is the equivalent of [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`BitField`]. It returns a proxy that reads and writes the bits of the field covered by the
+    /// part, leaving the other bits unchanged.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn bit_part_mut<
+        'b,
+        FieldPartSpec,
+        FieldPart,
+        PartIndex,
+        FieldType,
+        const LOW: u32,
+        const HIGH: u32,
+    >(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> BitsMut<'b, FieldType>
+    where
+        'a: 'b,
+        FieldType: BitsField,
+        FieldPart: Part<PartType = BitField<FieldType, LOW, HIGH>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        BitsMut {
+            ptr: unsafe { <Self::Target as HasPart<FieldPart>>::part_ptr_mut(self.get_raw()) },
+            low: LOW,
+            high: HIGH,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Access to a part of a bit range of an integer field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`bit_part`](PartialRef::bit_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_bit_part<
+        FieldPartSpec,
+        FieldPart,
+        PartIndex,
+        FieldType,
+        const LOW: u32,
+        const HIGH: u32,
+    >(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (Bits<'a, FieldType>, Self::Remainder)
+    where
+        FieldType: BitsField,
+        FieldPart: Part<PartType = BitField<FieldType, LOW, HIGH>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                Bits {
+                    ptr: <Self::Target as HasPart<FieldPart>>::part_ptr(ptr),
+                    low: LOW,
+                    high: HIGH,
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Mutable access to a part of a bit range of an integer field, splitting off the remaining
+    /// parts.
+    ///
+    /// This is equivalent to [`bit_part_mut`](PartialRef::bit_part_mut) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_bit_part_mut<
+        FieldPartSpec,
+        FieldPart,
+        PartIndex,
+        FieldType,
+        const LOW: u32,
+        const HIGH: u32,
+    >(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (BitsMut<'a, FieldType>, Self::Remainder)
+    where
+        FieldType: BitsField,
+        FieldPart: Part<PartType = BitField<FieldType, LOW, HIGH>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                BitsMut {
+                    ptr: <Self::Target as HasPart<FieldPart>>::part_ptr_mut(ptr),
+                    low: LOW,
+                    high: HIGH,
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Splits an integer part into bit parts, splitting off the remaining parts.
+    ///
+    /// The part's field has to be an unsigned integer (see [`BitsField`]). This returns a partial
+    /// reference `BitsRef` having only [`BitPart`]s of the field, e.g. `partial!(Device, mut
+    /// BitPart<Control, 0, 1>, mut BitPart<Control, 4, 8>)`. The bit ranges have to be non-empty,
+    /// within the width of the field and listed in increasing order without overlapping, which is
+    /// checked at compile time.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn split_bit_parts_mut<BitsRef, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        part: FieldPartSpec,
+    ) -> (BitsRef, Self::Remainder)
+    where
+        FieldType: BitsField + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+        BitsRef: PartialRef<'a, Target = Self::Target> + BitPartList<FieldPart>,
+    {
+        let ptr = self.get_raw();
+        let (_field, rest) = self.split_part_mut(part);
+        // Evaluating `END` checks the bit ranges at compile time.
+        let _end = BitsRef::END;
+        (unsafe { BitsRef::from_raw(ptr) }, rest)
+    }
+
+    /// Access to a column part of a slice of rows.
+    ///
+    /// This is the equivalent of [`part`](PartialRef::part) for parts of type [`ColumnField`]. It
+    /// returns a view of one field of every row.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn column_part<'b, ColumnPartSpec, ColumnPart, PartIndex, Row, FieldPart>(
+        &'b self,
+        _part: ColumnPartSpec,
+    ) -> Column<'b, Row, FieldPart>
+    where
+        'a: 'b,
+        ColumnPart: Part<PartType = ColumnField<Row, FieldPart>>,
+        ColumnPartSpec: PartSpec<ColumnPart>,
+        Self: PluckConst<'a, ColumnPart, PartIndex>,
+        Self::Target: HasPart<ColumnPart> + 'a,
+    {
+        Column {
+            rows: unsafe { <Self::Target as HasPart<ColumnPart>>::part_ptr(self.get_raw()) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Mutable access to a column part of a slice of rows.
+    ///
+    /// This is the equivalent of [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`ColumnField`]. It returns a mutable view of one field of every row.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn column_part_mut<'b, ColumnPartSpec, ColumnPart, PartIndex, Row, FieldPart>(
+        &'b mut self,
+        _part: ColumnPartSpec,
+    ) -> ColumnMut<'b, Row, FieldPart>
+    where
+        'a: 'b,
+        ColumnPart: Part<PartType = ColumnField<Row, FieldPart>>,
+        ColumnPartSpec: PartSpec<ColumnPart>,
+        Self: PluckMut<'a, ColumnPart, PartIndex>,
+        Self::Target: HasPart<ColumnPart> + 'a,
+    {
+        ColumnMut {
+            rows: unsafe { <Self::Target as HasPart<ColumnPart>>::part_ptr_mut(self.get_raw()) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Access to a column part of a slice of rows, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`column_part`](PartialRef::column_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_column_part<ColumnPartSpec, ColumnPart, PartIndex, Row, FieldPart>(
+        &'a mut self,
+        _part: ColumnPartSpec,
+    ) -> (Column<'a, Row, FieldPart>, Self::Remainder)
+    where
+        ColumnPart: Part<PartType = ColumnField<Row, FieldPart>>,
+        ColumnPartSpec: PartSpec<ColumnPart>,
+        Self: PluckConst<'a, ColumnPart, PartIndex>,
+        Self::Target: HasPart<ColumnPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                Column {
+                    rows: <Self::Target as HasPart<ColumnPart>>::part_ptr(ptr),
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Mutable access to a column part of a slice of rows, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`column_part_mut`](PartialRef::column_part_mut) but also returns a
+    /// partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_column_part_mut<ColumnPartSpec, ColumnPart, PartIndex, Row, FieldPart>(
+        &'a mut self,
+        _part: ColumnPartSpec,
+    ) -> (ColumnMut<'a, Row, FieldPart>, Self::Remainder)
+    where
+        ColumnPart: Part<PartType = ColumnField<Row, FieldPart>>,
+        ColumnPartSpec: PartSpec<ColumnPart>,
+        Self: PluckMut<'a, ColumnPart, PartIndex>,
+        Self::Target: HasPart<ColumnPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                ColumnMut {
+                    rows: <Self::Target as HasPart<ColumnPart>>::part_ptr_mut(ptr),
+                    phantom: PhantomData,
+                },
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Splits a slice part into column parts, splitting off the remaining parts.
+    ///
+    /// The part's field has to be a slice, a `Vec` or an array (see [`SliceField`]) of rows, which
+    /// have to be a [`PartialRefTarget`]. This returns a partial reference `ColumnsRef` having only
+    /// [`ColumnPart`]s of the field, e.g. `partial!(Particles, mut ColumnPart<Rows, Position>,
+    /// ColumnPart<Rows, Velocity>)`. The parts of the columns have to be parts that can be borrowed
+    /// together from a partial reference to a row, i.e. `partial!(Particle, mut Position,
+    /// Velocity)`, which is checked at compile time.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn split_column_parts_mut<
+        ColumnsRef,
+        RowsPartSpec,
+        RowsPart,
+        PartIndex,
+        RowsType,
+        SubsetIndex,
+    >(
+        &'a mut self,
+        part: RowsPartSpec,
+    ) -> (ColumnsRef, Self::Remainder)
+    where
+        RowsType: SliceField + ?Sized + 'a,
+        RowsType::Element: FullRefTarget<'a>,
+        <RowsType::Element as FullRefTarget<'a>>::FullRef:
+            HasSubset<'a, ColumnsRef::RowRef, SubsetIndex>,
+        RowsPart: Part<PartType = Field<RowsType>>,
+        RowsPartSpec: PartSpec<RowsPart>,
+        Self: PluckMut<'a, RowsPart, PartIndex>,
+        Self::Target: HasPart<RowsPart> + 'a,
+        ColumnsRef:
+            PartialRef<'a, Target = Self::Target> + ColumnPartList<'a, RowsPart, RowsType::Element>,
+    {
+        let ptr = self.get_raw();
+        let (_field, rest) = self.split_part_mut(part);
+        (unsafe { ColumnsRef::from_raw(ptr) }, rest)
+    }
+
+    /// Lock a part of a lock-guarded field for shared access.
+    ///
+    /// This is the equivalent of [`part`](PartialRef::part) for parts of type [`LockedField`]. It
+    /// acquires a shared lock, i.e. takes a read lock of a `RwLock`, and returns the guard. As a
+    /// `Mutex` only provides exclusive access, this is not supported for `Mutex` fields (see
+    /// [`SharedLockField`]).
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn lock_part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> LockResult<<FieldType as SharedLockField<'b>>::Guard>
+    where
+        'a: 'b,
+        FieldType: SharedLockField<'b> + ?Sized + 'b,
+        FieldPart: Part<PartType = LockedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { (*<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw())).lock_const() }
+    }
+
+    /// Lock a part of a lock-guarded field for mutable access.
+    ///
+    /// This is the equivalent of [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`LockedField`]. It acquires the lock, i.e. locks a `Mutex` or takes a write lock of a
+    /// `RwLock`, and returns the guard.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn lock_part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> LockResult<<FieldType as LockField<'b>>::GuardMut>
+    where
+        'a: 'b,
+        FieldType: LockField<'b> + ?Sized + 'b,
+        FieldPart: Part<PartType = LockedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { (*<Self::Target as HasPart<FieldPart>>::part_ptr_mut(self.get_raw())).lock_mut() }
+    }
+
+    /// Lock a part of a lock-guarded field for shared access, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`lock_part`](PartialRef::lock_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_lock_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (
+        LockResult<<FieldType as SharedLockField<'a>>::Guard>,
+        Self::Remainder,
+    )
+    where
+        FieldType: SharedLockField<'a> + ?Sized + 'a,
+        FieldPart: Part<PartType = LockedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                (*<Self::Target as HasPart<FieldPart>>::part_ptr(ptr)).lock_const(),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Lock a part of a lock-guarded field for mutable access, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`lock_part_mut`](PartialRef::lock_part_mut) but also returns a
+    /// partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_lock_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (
+        LockResult<<FieldType as LockField<'a>>::GuardMut>,
+        Self::Remainder,
+    )
+    where
+        FieldType: LockField<'a> + ?Sized + 'a,
+        FieldPart: Part<PartType = LockedField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                (*<Self::Target as HasPart<FieldPart>>::part_ptr_mut(ptr)).lock_mut(),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Borrow a part of a `RefCell` field.
+    ///
+    /// This is the equivalent of [`part`](PartialRef::part) for parts of type [`RefCellField`]. The
+    /// borrow is tracked at runtime as by [`RefCell::borrow`](std::cell::RefCell::borrow), which
+    /// panics if the value is currently mutably borrowed.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn cell_part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> cell::Ref<'b, FieldType>
+    where
+        'a: 'b,
+        FieldType: ?Sized + 'b,
+        FieldPart: Part<PartType = RefCellField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { (*<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw())).borrow() }
+    }
+
+    /// Mutably borrow a part of a `RefCell` field.
+    ///
+    /// This is the equivalent of [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`RefCellField`]. The borrow is tracked at runtime as by
+    /// [`RefCell::borrow_mut`](std::cell::RefCell::borrow_mut), which panics if the value is
+    /// currently borrowed. This requires the part to be a mutable part, but as the borrow is checked
+    /// at runtime, the partial reference is only borrowed immutably.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn cell_part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> cell::RefMut<'b, FieldType>
+    where
+        'a: 'b,
+        FieldType: ?Sized + 'b,
+        FieldPart: Part<PartType = RefCellField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { (*<Self::Target as HasPart<FieldPart>>::part_ptr(self.get_raw())).borrow_mut() }
+    }
+
+    /// Borrow a part of a `RefCell` field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`cell_part`](PartialRef::cell_part) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_cell_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (cell::Ref<'a, FieldType>, Self::Remainder)
+    where
+        FieldType: ?Sized + 'a,
+        FieldPart: Part<PartType = RefCellField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckConst<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                (*<Self::Target as HasPart<FieldPart>>::part_ptr(ptr)).borrow(),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Mutably borrow a part of a `RefCell` field, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`cell_part_mut`](PartialRef::cell_part_mut) but also returns a
+    /// partial reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_cell_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType>(
+        &'a mut self,
+        _part: FieldPartSpec,
+    ) -> (cell::RefMut<'a, FieldType>, Self::Remainder)
+    where
+        FieldType: ?Sized + 'a,
+        FieldPart: Part<PartType = RefCellField<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                (*<Self::Target as HasPart<FieldPart>>::part_ptr_mut(ptr)).borrow_mut(),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Access the fields covered by a group part.
+    ///
+    /// This returns a struct of plain references to the fields of a [`FieldGroup`] part.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn group<GroupPartSpec, GroupPart, PartIndex, GroupTarget>(
+        &'a self,
+        _part: GroupPartSpec,
+    ) -> GroupPart::Ref
+    where
+        GroupTarget: ?Sized,
+        GroupPart: Part<PartType = FieldGroup<GroupTarget>> + FieldGroupRefs<'a, GroupTarget>,
+        GroupPartSpec: PartSpec<GroupPart>,
+        Self: PluckConst<'a, GroupPart, PartIndex>,
+        Self::Target: HasPart<GroupPart> + 'a,
+    {
+        unsafe {
+            GroupPart::group_ref(<Self::Target as HasPart<GroupPart>>::part_ptr(
+                self.get_raw(),
+            ))
+        }
+    }
+
+    /// Mutable access to the fields covered by a group part.
+    ///
+    /// This returns a struct of plain mutable references to the fields of a [`FieldGroup`] part.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn group_mut<GroupPartSpec, GroupPart, PartIndex, GroupTarget>(
+        &'a mut self,
+        _part: GroupPartSpec,
+    ) -> GroupPart::Mut
+    where
+        GroupTarget: ?Sized,
+        GroupPart: Part<PartType = FieldGroup<GroupTarget>> + FieldGroupRefs<'a, GroupTarget>,
+        GroupPartSpec: PartSpec<GroupPart>,
+        Self: PluckMut<'a, GroupPart, PartIndex>,
+        Self::Target: HasPart<GroupPart> + 'a,
+    {
+        unsafe {
+            GroupPart::group_mut(<Self::Target as HasPart<GroupPart>>::part_ptr_mut(
+                self.get_raw(),
+            ))
+        }
+    }
+
+    /// Access the fields covered by a group part, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`group`](PartialRef::group) but also returns a partial reference as
+    /// described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_group<GroupPartSpec, GroupPart, PartIndex, GroupTarget>(
+        &'a mut self,
+        _part: GroupPartSpec,
+    ) -> (GroupPart::Ref, Self::Remainder)
+    where
+        GroupTarget: ?Sized,
+        GroupPart: Part<PartType = FieldGroup<GroupTarget>> + FieldGroupRefs<'a, GroupTarget>,
+        GroupPartSpec: PartSpec<GroupPart>,
+        Self: PluckConst<'a, GroupPart, PartIndex>,
+        Self::Target: HasPart<GroupPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                GroupPart::group_ref(<Self::Target as HasPart<GroupPart>>::part_ptr(ptr)),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Mutable access to the fields covered by a group part, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`group_mut`](PartialRef::group_mut) but also returns a partial
+    /// reference as described in [`split_borrow`](PartialRef::split_borrow).
+    #[inline(always)]
+    fn split_group_mut<GroupPartSpec, GroupPart, PartIndex, GroupTarget>(
+        &'a mut self,
+        _part: GroupPartSpec,
+    ) -> (GroupPart::Mut, Self::Remainder)
+    where
+        GroupTarget: ?Sized,
+        GroupPart: Part<PartType = FieldGroup<GroupTarget>> + FieldGroupRefs<'a, GroupTarget>,
+        GroupPartSpec: PartSpec<GroupPart>,
+        Self: PluckMut<'a, GroupPart, PartIndex>,
+        Self::Target: HasPart<GroupPart> + 'a,
+    {
+        let ptr = self.get_raw();
+        unsafe {
+            (
+                GroupPart::group_mut(<Self::Target as HasPart<GroupPart>>::part_ptr_mut(ptr)),
+                Self::Remainder::from_raw(ptr),
+            )
+        }
+    }
+
+    /// Access the fields of all field parts of the target using a struct of plain references.
+    ///
+    /// This requires the target to have a view struct, declared using
+    /// `#[partial_ref(view)]`, and the partial reference to contain all parts covered by the
+    /// view struct.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn to_view<SubsetIndex>(&'a self) -> <Self::Target as TargetView<'a>>::View
+    where
+        Self::Target: TargetView<'a>,
+        Self: HasSubset<'a, <<Self as HasTarget>::Target as TargetView<'a>>::ViewRef, SubsetIndex>,
+    {
+        unsafe { <Self::Target as TargetView<'a>>::view(self.get_raw()) }
+    }
+
+    /// Mutable access to the fields of all field parts of the target using a struct of plain
+    /// mutable references.
+    ///
+    /// This is equivalent to [`to_view`](PartialRef::to_view) but requires all parts covered by
+    /// the view struct to be mutable parts of the partial reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn to_view_mut<SubsetIndex>(&'a mut self) -> <Self::Target as TargetView<'a>>::ViewMut
+    where
+        Self::Target: TargetView<'a>,
+        Self:
+            HasSubset<'a, <<Self as HasTarget>::Target as TargetView<'a>>::ViewMutRef, SubsetIndex>,
+    {
+        unsafe { <Self::Target as TargetView<'a>>::view_mut(self.get_raw()) }
+    }
+
+    /// Copy the values of all mutable parts from another partial reference.
+    ///
+    /// The source reference can have a different target, but has to contain every mutable part of
+    /// this reference. The mutable parts have to be [`Field`] parts of a type implementing
+    /// [`Clone`] and are updated using [`clone_from`](Clone::clone_from). Constant parts are left
+    /// unchanged.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn copy_parts_from<'b, Source, CopyIndex>(&mut self, source: &Source)
+    where
+        Source: PartialRef<'b>,
+        Self: CopyPartsFrom<'b, Source, CopyIndex>,
+    {
+        unsafe { Self::copy_parts_from_raw(self.get_raw(), source.get_raw()) }
+    }
+
+    /// Reset a mutable part to its default value.
+    ///
+    /// This is equivalent to assigning [`Default::default()`] to the result of
+    /// [`part_mut`](PartialRef::part_mut) without naming the field type.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn reset_part<FieldPartSpec, FieldPart, PartIndex, FieldType>(&'a mut self, part: FieldPartSpec)
+    where
+        FieldType: Default + 'a,
+        FieldPart: Part<PartType = Field<FieldType>>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Self: PluckMut<'a, FieldPart, PartIndex>,
+        Self::Target: HasPart<FieldPart> + 'a,
+    {
+        *self.part_mut(part) = FieldType::default();
+    }
+
+    /// Reset all mutable parts to their default values.
+    ///
+    /// The mutable parts have to be [`Field`] parts of a type implementing [`Default`]. Constant
+    /// parts are left unchanged.
+    #[inline(always)]
+    fn reset_parts(&mut self)
+    where
+        Self: ResetParts,
+    {
+        unsafe { Self::reset_parts_raw(self.get_raw()) }
+    }
+
+    /// Copy the values of all parts to another partial reference.
+    ///
+    /// This is the reverse of [`copy_parts_from`](PartialRef::copy_parts_from). The destination
+    /// reference can have a different target, but has to contain every part of this reference as
+    /// mutable part. The parts have to be [`Field`] parts of a type implementing [`Clone`] and are
+    /// updated using [`clone_from`](Clone::clone_from). Other parts of the destination are left
+    /// unchanged.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn clone_parts_into<'b, Dest, CloneIndex>(&self, dest: &mut Dest)
+    where
+        Dest: PartialRef<'b>,
+        Self: ClonePartsInto<'b, Dest, CloneIndex>,
+    {
+        unsafe { Self::clone_parts_into_raw(self.get_raw(), dest.get_raw()) }
+    }
+
+    /// Compare the values of all parts with the same parts of another partial reference.
+    ///
+    /// The other reference can have a different target, but has to contain every part of this
+    /// reference. The parts have to be [`Field`] parts of a type implementing [`PartialEq`]. Returns
+    /// whether all parts are equal, comparing them in the order they are listed in [`partial!`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    fn parts_eq<'b, Other, EqIndex>(&self, other: &Other) -> bool
+    where
+        Other: PartialRef<'b>,
+        Self: PartsEq<'b, Other, EqIndex>,
+    {
+        unsafe { Self::parts_eq_raw(self.get_raw(), other.get_raw()) }
+    }
+
+    /// Feed the values of all parts into a [`Hasher`](hash::Hasher).
+    ///
+    /// The parts have to be [`Field`] parts of a type implementing [`Hash`](hash::Hash). They are
+    /// hashed in the order they are listed in [`partial!`], so partial references comparing equal
+    /// using [`parts_eq`](PartialRef::parts_eq) have the same hash.
+    #[inline(always)]
+    fn hash_parts<H: hash::Hasher>(&self, state: &mut H)
+    where
+        Self: HashParts,
+    {
+        unsafe { Self::hash_parts_raw(self.get_raw(), state) }
+    }
+
+    /// Call a visitor for the field of each part of this partial reference.
+    ///
+    /// This passes the part's name as listed in [`PartMetadata`] and a shared reference to the
+    /// field to the [`PartVisitor`], in the order in which the parts are listed in [`partial!`].
+    /// Only [`Field`], [`SharedField`] and [`PinnedField`] parts are visited, other parts are
+    /// skipped. See [`VisitRefParts`] for the supported partial references.
+    #[inline(always)]
+    fn for_each_part<Visitor: ?Sized>(&self, visitor: &mut Visitor)
+    where
+        Self: VisitRefParts<Visitor>,
+    {
+        unsafe { Self::visit_ref_parts(self.get_raw(), visitor) }
+    }
+}
+
+/// Construction of partial references.
+///
+/// See also [`IntoPartialRefMut`].
+///
+/// This trait gets an automatic implementation for references (mutable or immutable) to any type
+/// that has a derive statement for [`PartialRefTarget`]. Usually there is no need to implement this
+/// trait manually.
+pub trait IntoPartialRef<'a> {
+    type Ref: PartialRef<'a>;
+
+    /// Convert a mutable or immutable reference into a partial reference.
     fn into_partial_ref(self) -> Self::Ref;
 }
 
-/// Construction of partial references from mutable references.
+/// Construction of partial references from mutable references.
+///
+/// This has an implementation for mutable references that implement [`IntoPartialRefMut`]. It
+/// performs the same operation as [`IntoPartialRefMut`] but is only implemented for mutable
+/// references. This is useful as it allows writing `value.into_partial_ref_mut()` instead of `(&mut
+/// value).into_partial_ref_mut()` using auto referencing of method calls. Using just
+/// [`value.into_partial_ref()`] would result in an immutable reference.
+pub trait IntoPartialRefMut<'a>: IntoPartialRef<'a> {
+    /// Convert a mutable reference into a partial reference.
+    fn into_partial_ref_mut(self) -> Self::Ref;
+}
+
+impl<'a, T> IntoPartialRefMut<'a> for &'a mut T
+where
+    T: ?Sized,
+    Self: IntoPartialRef<'a>,
+{
+    fn into_partial_ref_mut(self) -> Self::Ref {
+        self.into_partial_ref()
+    }
+}
+
+/// A reference to a boxed target is converted like a reference to the target.
+impl<'a, T> IntoPartialRef<'a> for &'a Box<T>
+where
+    T: ?Sized,
+    &'a T: IntoPartialRef<'a>,
+{
+    type Ref = <&'a T as IntoPartialRef<'a>>::Ref;
+
+    #[inline(always)]
+    fn into_partial_ref(self) -> Self::Ref {
+        (&**self).into_partial_ref()
+    }
+}
+
+/// A mutable reference to a boxed target is converted like a mutable reference to the target.
+impl<'a, T> IntoPartialRef<'a> for &'a mut Box<T>
+where
+    T: ?Sized,
+    &'a mut T: IntoPartialRef<'a>,
+{
+    type Ref = <&'a mut T as IntoPartialRef<'a>>::Ref;
+
+    #[inline(always)]
+    fn into_partial_ref(self) -> Self::Ref {
+        (&mut **self).into_partial_ref()
+    }
+}
+
+/// A reference to a shared target is converted like a reference to the target.
+impl<'a, T> IntoPartialRef<'a> for &'a Rc<T>
+where
+    T: ?Sized,
+    &'a T: IntoPartialRef<'a>,
+{
+    type Ref = <&'a T as IntoPartialRef<'a>>::Ref;
+
+    #[inline(always)]
+    fn into_partial_ref(self) -> Self::Ref {
+        (&**self).into_partial_ref()
+    }
+}
+
+/// A reference to a shared target is converted like a reference to the target.
+impl<'a, T> IntoPartialRef<'a> for &'a Arc<T>
+where
+    T: ?Sized,
+    &'a T: IntoPartialRef<'a>,
+{
+    type Ref = <&'a T as IntoPartialRef<'a>>::Ref;
+
+    #[inline(always)]
+    fn into_partial_ref(self) -> Self::Ref {
+        (&**self).into_partial_ref()
+    }
+}
+
+/// *(internal)* Split a part into nested parts.
+///
+/// This is used to implement splitting of nested parts.
+pub unsafe trait SplitIntoParts<'a, ContainingPart, Reference: PartialRef<'a>> {
+    /// A partial reference that has all the parts `Reference` and all parts of `Self` nested in
+    /// `ContainingPart` as constant parts.
+    type Result: PartialRef<'a, Target = Reference::Target>;
+    /// A partial reference that has all the parts `Reference` and all parts of `Self` nested in
+    /// `ContainingPart` as mutable parts.
+    type ResultMut: PartialRef<'a, Target = Reference::Target>;
+}
+
+/// An empty partial reference borrowing no parts.
+///
+/// Partial references with a non-empty set of borrowed parts are built by nesting this type within
+/// the [`Mut`] and [`Const`] types.
+#[repr(transparent)]
+pub struct Ref<'a, Target: PartialRefTarget + ?Sized> {
+    ptr: *mut Target::RawTarget,
+    phantom: PhantomData<&'a mut Target>,
+}
+
+impl<'a, Target: PartialRefTarget + ?Sized> HasTarget for Ref<'a, Target> {
+    type Target = Target;
+}
+
+/// An empty reference to a valid target is a valid reference.
+impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> PartialRef<'a> for Ref<'b, Target> {
+    #[inline(always)]
+    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
+        Ref {
+            ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
+        self.ptr
+    }
+}
+
+/// An empty reference contains no mutable parts and thus is safe to clone.
+impl<'a, Target: PartialRefTarget + ?Sized> Copy for Ref<'a, Target> {}
+
+/// An empty reference contains no mutable parts and thus is safe to clone.
+impl<'a, Target: PartialRefTarget + ?Sized> Clone for Ref<'a, Target> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A mutable part of a partial reference.
+#[repr(transparent)]
+pub struct Mut<Part, Reference: HasTarget> {
+    ptr: *mut <<Reference as HasTarget>::Target as PartialRefTarget>::RawTarget,
+    phantom: PhantomData<(Reference, Part)>,
+}
+
+impl<'a, SomePart: Part, Reference: PartialRef<'a>> HasTarget for Mut<SomePart, Reference>
+where
+    Reference::Target: HasPart<SomePart>,
+{
+    type Target = Reference::Target;
+}
+
+/// Extending a valid reference by a mutable part is still a valid reference when the reference
+/// target has such a part.
+impl<'a, SomePart: Part, Reference: PartialRef<'a>> PartialRef<'a> for Mut<SomePart, Reference>
+where
+    Reference::Target: HasPart<SomePart>,
+{
+    #[inline(always)]
+    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
+        Mut {
+            ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
+        self.ptr
+    }
+}
+
+/// A constant (immutable) part of a partial reference.
+#[repr(transparent)]
+pub struct Const<Part, Reference: HasTarget> {
+    ptr: *mut <<Reference as HasTarget>::Target as PartialRefTarget>::RawTarget,
+    phantom: PhantomData<(Reference, Part)>,
+}
+
+impl<'a, SomePart: Part, Reference: PartialRef<'a>> HasTarget for Const<SomePart, Reference>
+where
+    Reference::Target: HasPart<SomePart>,
+{
+    type Target = Reference::Target;
+}
+
+/// Extending a valid reference by a constant part is still a valid reference when the reference
+/// target has such a part.
+impl<'a, SomePart: Part, Reference: PartialRef<'a>> PartialRef<'a> for Const<SomePart, Reference>
+where
+    Reference::Target: HasPart<SomePart>,
+{
+    #[inline(always)]
+    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
+        Const {
+            ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
+        self.ptr
+    }
+}
+
+/// Implemented for partial references that contain just constant parts.
+///
+/// Every such partial reference can be copied. This is useful as a bound in generic code, where
+/// `Copy` alone would not say anything about the parts of a reference.
+pub unsafe trait ConstOnly: Copy {}
+
+/// An empty reference contains no mutable parts.
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> ConstOnly for Ref<'a, Target> {}
+
+/// A constant part on top of constant parts.
+unsafe impl<SomePart, Reference: ConstOnly + HasTarget> ConstOnly for Const<SomePart, Reference> {}
+
+/// A reference containing just constant parts is safe to clone.
+impl<SomePart, Reference: Copy + HasTarget> Copy for Const<SomePart, Reference> {}
+
+/// A reference containing just constant parts is safe to clone.
+impl<SomePart, Reference: Copy + HasTarget> Clone for Const<SomePart, Reference> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, SomePart, Target, FieldType> Const<SomePart, Ref<'a, Target>>
+where
+    FieldType: ?Sized,
+    SomePart: Part<PartType = Field<FieldType>>,
+    Target: ?Sized,
+    Target: HasPart<SomePart>,
+{
+    /// Only available on single part references, used to implement the more general
+    /// [`part`](PartialRef::part) method of [`PartialRef`].
+    #[inline(always)]
+    fn get_part(self) -> &'a FieldType {
+        unsafe { &*Target::part_ptr(self.get_raw() as *const _) }
+    }
+}
+
+impl<'a, SomePart, Target, FieldType> Mut<SomePart, Ref<'a, Target>>
+where
+    FieldType: ?Sized,
+    SomePart: Part<PartType = Field<FieldType>>,
+    Target: ?Sized,
+    Target: HasPart<SomePart>,
+{
+    /// Only available on single part references, used to implement the more general
+    /// [`part_mut`](PartialRef::part_mut) method of [`PartialRef`]
+    #[inline(always)]
+    fn get_part_mut(self) -> &'a mut FieldType {
+        unsafe { &mut *Target::part_ptr_mut(self.get_raw()) }
+    }
+}
+
+// Parts
+
+/// Marker types for a part of a type.
+///
+/// Types implementing this trait are usually created using the [`part`] macro.
+///
+/// A type implementing this trait is used to identify a part of a reference target. Multiple
+/// different reference targets can have a part identified by the same [`Part`] (see also
+/// [`HasPart`]). A part has an associated [`PartType`], which determines what can be done with a
+/// part.
+pub trait Part: Default {
+    type PartType: PartType;
+}
+
+/// Helper trait to strip lifetimes from a part.
+///
+/// Every part `SomePart<'a, ...>` should implement `PartSpec<SomePart<'b, ...>>`. This is used by
+/// the [`part`](PartialRef::part), [`part_mut`](PartialRef::part_mut),
+/// [`split_part`](PartialRef::split_part) and [`split_part_mut`](PartialRef::split_part_mut)
+/// functions. This allows the passed parameter to have a different lifetime than the accessed part.
+/// This in turn enables part selection using globals with static lifetimes as declared by the
+/// [`part`] macro.
+pub trait PartSpec<Part> {}
+
+/// Implemented when a reference target has a part.
+///
+/// This trait provides methods for unchecked access to a part of a reference target.
+/// Implementations for this are automatically created when deriving PartialRefTarget. For parts of
+/// type [`VariantField`], the produced pointers are null when the variant is not active.
+pub trait HasPart<SomePart: Part>: PartialRefTarget {
+    /// Given a constant pointer to a target, produce a constant pointer to a part of it.
+    ///
+    /// # Safety
+    /// Implementations may construct a temporary reference to ptr, which thus must be valid.
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> <SomePart::PartType as PartType>::Ptr;
+    /// Given a mutable pointer to a target, produce a mutable pointer to a part of it.
+    ///
+    /// # Safety
+    /// Implementations may construct a temporary reference to ptr, which thus must be valid.
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> <SomePart::PartType as PartType>::PtrMut;
+}
+
+/// A part computed by an accessor instead of being a field of the target.
+///
+/// A virtual part can provide a view of the target that doesn't correspond to a single field, e.g.
+/// a sub-slice of a field or a field of a field. The part type is usually a [`Field`] part, so all
+/// accessors for field parts work for virtual parts, too. Targets get virtual parts using the
+/// derive attribute `#[part(virtual(PartName))]`, which implements [`HasPart`] using this trait.
+///
+/// The accessor works on pointers instead of references, as constructing a reference to the whole
+/// target would conflict with references to its other parts. It may construct temporary references
+/// to the fields the part is computed from.
+///
+/// # Safety
+/// The produced pointers must be valid whenever the pointer to the target is valid. The memory they
+/// point to must not overlap with the memory accessible through any other part of the target,
+/// including other virtual parts, unless that part is nested within this part. Usually a virtual
+/// part is a view of fields that have no part of their own.
+pub unsafe trait VirtualPart<Target: ?Sized>: Part {
+    /// Given a constant pointer to a target, produce a constant pointer to the virtual part.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn virtual_ptr(ptr: *const Target) -> <Self::PartType as PartType>::Ptr;
+
+    /// Given a mutable pointer to a target, produce a mutable pointer to the virtual part.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn virtual_ptr_mut(ptr: *mut Target) -> <Self::PartType as PartType>::PtrMut;
+}
+
+/// Implemented when a part of a reference target is stored at a fixed offset within the target.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget for the
+/// parts of struct and union fields, including pinned fields and the elements of array fields.
+/// Together with [`get_raw`](PartialRef::get_raw) this allows working with parts using raw
+/// pointers. Note that the offset is relative to the target, which only coincides with the raw
+/// target for the default raw target or the raw targets `UnsafeCell<Target>` and
+/// `Cell<Target>`.
+///
+/// # Safety
+/// The part must be stored at `OFFSET` bytes from the start of the target.
+pub unsafe trait PartOffset<SomePart: Part>: HasPart<SomePart> {
+    /// Offset of the part within the target in bytes.
+    const OFFSET: usize;
+}
+
+/// Runtime information about the parts of a reference target.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget. The parts
+/// are listed in the order in which they appear in the partial references created from plain
+/// references, followed by pinned parts.
+pub trait PartMetadata {
+    /// Information about each part of the target.
+    const PARTS: &'static [PartInfo];
+    /// The names of the parts of the target, in the same order as [`PARTS`](PartMetadata::PARTS).
+    const PART_NAMES: &'static [&'static str];
+    /// The number of parts of the target.
+    const PART_COUNT: usize = Self::PARTS.len();
+}
+
+/// Information about a part of a reference target, see [`PartMetadata`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PartInfo {
+    /// The part's type as written in the target's definition, e.g. `Colors` or `Items<T>`.
+    pub name: &'static str,
+    /// What the part refers to.
+    pub kind: PartKind,
+    /// The name or index of the field the part belongs to.
+    pub field: Option<&'static str>,
+}
+
+/// What a part listed in [`PartInfo`] refers to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PartKind {
+    /// A part of a single field or the value it points to.
+    Field,
+    /// An abstract part, including variant parts of enums.
+    Abstract,
+    /// A part covering a group of fields.
+    Group,
+    /// A part of a flattened field's type.
+    Flattened,
+}
+
+/// Runtime information about the parts of a partial reference.
+///
+/// This lists the parts of a partial reference type together with whether they are held mutably.
+/// Parts are identified by their index in the target's [`PartMetadata::PARTS`], so comparing the
+/// held parts of two partial reference types at runtime, e.g. in a scheduler, tells whether they
+/// can be used at the same time. This requires all parts of the partial reference to be parts
+/// declared for the target, as listed by [`CanonicalTarget`], so it is not available for nested
+/// parts.
+pub trait HeldParts: HasTarget {
+    /// *(internal)* Append the held parts in the order in which they are listed in [`partial`].
+    fn push_held_parts(parts: &mut Vec<HeldPart>);
+
+    /// The held parts in the order in which they are listed in [`partial`].
+    fn held_parts() -> Vec<HeldPart> {
+        let mut parts = vec![];
+        Self::push_held_parts(&mut parts);
+        parts
+    }
+
+    /// Whether a partial reference of this type conflicts with one of the type `Other`.
+    ///
+    /// Two partial references conflict when they hold the same part and at least one of them holds
+    /// it mutably. Note that this doesn't detect conflicts between a part covering a group of
+    /// fields and the parts of those fields.
+    fn conflicts_with<Other: HeldParts<Target = Self::Target>>() -> bool {
+        let other_parts = Other::held_parts();
+        Self::held_parts().iter().any(|part| {
+            other_parts
+                .iter()
+                .any(|other| part.index == other.index && (part.mutable || other.mutable))
+        })
+    }
+}
+
+/// A part held by a partial reference, see [`HeldParts`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HeldPart {
+    /// The index of the part in the target's [`PartMetadata::PARTS`].
+    pub index: usize,
+    /// Whether the part is held mutably.
+    pub mutable: bool,
+}
+
+impl<'a, Target: PartialRefTarget + ?Sized> HeldParts for Ref<'a, Target> {
+    fn push_held_parts(_parts: &mut Vec<HeldPart>) {}
+}
+
+impl<'a, SomePart, Reference> HeldParts for Const<SomePart, Reference>
+where
+    SomePart: CanonicalPart<Reference::Target>,
+    SomePart::Position: PositionIndex,
+    Reference: PartialRef<'a> + HeldParts,
+    Reference::Target: HasPart<SomePart>,
+{
+    fn push_held_parts(parts: &mut Vec<HeldPart>) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::push_held_parts(parts);
+        parts.push(HeldPart {
+            index: SomePart::Position::INDEX,
+            mutable: false,
+        });
+    }
+}
+
+impl<'a, SomePart, Reference> HeldParts for Mut<SomePart, Reference>
+where
+    SomePart: CanonicalPart<Reference::Target>,
+    SomePart::Position: PositionIndex,
+    Reference: PartialRef<'a> + HeldParts,
+    Reference::Target: HasPart<SomePart>,
+{
+    fn push_held_parts(parts: &mut Vec<HeldPart>) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::push_held_parts(parts);
+        parts.push(HeldPart {
+            index: SomePart::Position::INDEX,
+            mutable: true,
+        });
+    }
+}
+
+/// Visit the fields of the field parts of a reference target.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget for structs
+/// and enums, except for packed structs. They call the visitor once for each field that has its
+/// own part, passing the part's name as listed in [`PartMetadata`] and a reference to the field.
+/// Element parts visit the corresponding element and parts of dereferenced fields visit the value
+/// the field points to. For enums only the fields of the current variant are visited. Flattened
+/// fields are not visited.
+///
+/// The visitor has to implement [`PartVisitor`] for the type of every visited field.
+pub trait VisitParts<Visitor: ?Sized> {
+    /// Call the visitor for the field of each field part.
+    fn visit_parts(&self, visitor: &mut Visitor);
+}
+
+/// A visitor for the fields of a reference target, see [`VisitParts`].
+///
+/// Visitors are usually implemented for all field types having a common trait, e.g. for all
+/// `FieldType: Debug` to print all fields or for all `FieldType: ?Sized` to sum up their sizes.
+pub trait PartVisitor<FieldType: ?Sized> {
+    /// Visit the field of the part named `name`.
+    fn visit(&mut self, name: &'static str, field: &FieldType);
+}
+
+/// Visit the fields of the parts of a partial reference, see
+/// [`for_each_part`](PartialRef::for_each_part).
+///
+/// This requires all parts of the partial reference to be parts declared for the target, as listed
+/// by [`CanonicalTarget`], so it is not available for nested parts. The visitor has to implement
+/// [`PartVisitor`] for the type of every visited field.
+pub unsafe trait VisitRefParts<Visitor: ?Sized>: HasTarget {
+    /// *(internal)* Visit the parts using a raw pointer.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the parts of `Self`.
+    unsafe fn visit_ref_parts(
+        ptr: *const <Self::Target as PartialRefTarget>::RawTarget,
+        visitor: &mut Visitor,
+    );
+}
+
+/// *(internal)* An empty reference has nothing to visit.
+unsafe impl<'a, Target, Visitor> VisitRefParts<Visitor> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+    Visitor: ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_ref_parts(_ptr: *const Target::RawTarget, _visitor: &mut Visitor) {}
+}
+
+/// *(internal)* Visit the remaining parts followed by a constant part.
+unsafe impl<'a, SomePart, Reference, Visitor> VisitRefParts<Visitor> for Const<SomePart, Reference>
+where
+    SomePart: CanonicalPart<Reference::Target>,
+    SomePart::PartType: VisitPartType<Visitor>,
+    SomePart::Position: PositionIndex,
+    Reference: PartialRef<'a> + VisitRefParts<Visitor>,
+    Reference::Target: HasPart<SomePart> + PartMetadata,
+    Visitor: ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_ref_parts(
+        ptr: *const <Reference::Target as PartialRefTarget>::RawTarget,
+        visitor: &mut Visitor,
+    ) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::visit_ref_parts(ptr, visitor);
+        SomePart::PartType::visit_part(
+            <Reference::Target as PartMetadata>::PART_NAMES[SomePart::Position::INDEX],
+            <Reference::Target as HasPart<SomePart>>::part_ptr(ptr),
+            visitor,
+        )
+    }
+}
+
+/// *(internal)* Visit the remaining parts followed by a mutable part.
+unsafe impl<'a, SomePart, Reference, Visitor> VisitRefParts<Visitor> for Mut<SomePart, Reference>
+where
+    SomePart: CanonicalPart<Reference::Target>,
+    SomePart::PartType: VisitPartType<Visitor>,
+    SomePart::Position: PositionIndex,
+    Reference: PartialRef<'a> + VisitRefParts<Visitor>,
+    Reference::Target: HasPart<SomePart> + PartMetadata,
+    Visitor: ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_ref_parts(
+        ptr: *const <Reference::Target as PartialRefTarget>::RawTarget,
+        visitor: &mut Visitor,
+    ) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::visit_ref_parts(ptr, visitor);
+        SomePart::PartType::visit_part(
+            <Reference::Target as PartMetadata>::PART_NAMES[SomePart::Position::INDEX],
+            <Reference::Target as HasPart<SomePart>>::part_ptr(ptr),
+            visitor,
+        )
+    }
+}
+
+/// *(internal)* Visit the field of a part of this type, if it has a field that can be visited.
+pub unsafe trait VisitPartType<Visitor: ?Sized>: PartType {
+    /// Visit the field using a pointer returned by [`HasPart::part_ptr`].
+    ///
+    /// # Safety
+    /// The pointer must be valid for shared access to the part.
+    unsafe fn visit_part(name: &'static str, ptr: Self::Ptr, visitor: &mut Visitor);
+}
+
+unsafe impl<FieldType, Visitor> VisitPartType<Visitor> for Field<FieldType>
+where
+    FieldType: ?Sized,
+    Visitor: PartVisitor<FieldType> + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_part(name: &'static str, ptr: *const FieldType, visitor: &mut Visitor) {
+        visitor.visit(name, &*ptr)
+    }
+}
+
+unsafe impl<FieldType, Visitor> VisitPartType<Visitor> for SharedField<FieldType>
+where
+    FieldType: ?Sized,
+    Visitor: PartVisitor<FieldType> + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_part(name: &'static str, ptr: *const FieldType, visitor: &mut Visitor) {
+        visitor.visit(name, &*ptr)
+    }
+}
+
+unsafe impl<FieldType, Visitor> VisitPartType<Visitor> for PinnedField<FieldType>
+where
+    FieldType: ?Sized,
+    Visitor: PartVisitor<FieldType> + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_part(name: &'static str, ptr: *const FieldType, visitor: &mut Visitor) {
+        visitor.visit(name, &*ptr)
+    }
+}
+
+unsafe impl<Visitor: ?Sized> VisitPartType<Visitor> for AbstractPart {
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: (), _visitor: &mut Visitor) {}
+}
+
+unsafe impl<State, Visitor: ?Sized> VisitPartType<Visitor> for StatefulAbstractPart<State> {
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: (), _visitor: &mut Visitor) {}
+}
+
+unsafe impl<Target: ?Sized, Visitor: ?Sized> VisitPartType<Visitor> for FieldGroup<Target> {
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: *const Target, _visitor: &mut Visitor) {}
+}
+
+/// *(internal)* Fields of enum variants are only accessible using a [`Witness`].
+unsafe impl<Variant, FieldType, Visitor> VisitPartType<Visitor> for VariantField<Variant, FieldType>
+where
+    FieldType: ?Sized,
+    Visitor: ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: *const FieldType, _visitor: &mut Visitor) {}
+}
+
+/// *(internal)* Fields of unions can't be accessed safely.
+unsafe impl<FieldType, Visitor> VisitPartType<Visitor> for UnionField<FieldType>
+where
+    FieldType: ?Sized,
+    Visitor: ?Sized,
+{
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: *const FieldType, _visitor: &mut Visitor) {}
+}
+
+/// *(internal)* Unaligned fields can't be referenced.
+unsafe impl<FieldType, Visitor: ?Sized> VisitPartType<Visitor> for UnalignedField<FieldType> {
+    #[inline(always)]
+    unsafe fn visit_part(_name: &'static str, _ptr: *const FieldType, _visitor: &mut Visitor) {}
+}
+
+/// Type of a part, determines what can be done with a part.
+///
+/// Common part types are [`Field`] and [`AbstractPart`].
+pub trait PartType {
+    /// Type that can be produced from a constant pointer to a reference target.
+    type Ptr;
+    /// Type that can be produced from a mutable pointer to a reference target.
+    type PtrMut;
+}
+
+/// Type of a part that corresponds to a struct field.
+///
+/// Partial references containing a part of this type provide access to that field.
+pub struct Field<FieldType: ?Sized>(PhantomData<FieldType>);
+
+impl<FieldType: ?Sized> PartType for Field<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// A part type that provides fallible access to a field.
+///
+/// This is used by [`try_part`](PartialRef::try_part) and
+/// [`try_part_mut`](PartialRef::try_part_mut). Custom part types whose field can be absent at
+/// runtime implement this to return `None` in that case.
+pub trait TryPartType: PartType {
+    /// The type of the accessed field.
+    type FieldType: ?Sized;
+
+    /// Given a constant part pointer, produce a pointer to the field if it is present.
+    ///
+    /// # Safety
+    /// Implementations may construct a temporary reference to the part, which thus must be valid.
+    unsafe fn try_field_ptr(ptr: Self::Ptr) -> Option<*const Self::FieldType>;
+
+    /// Given a mutable part pointer, produce a pointer to the field if it is present.
+    ///
+    /// # Safety
+    /// Implementations may construct a temporary reference to the part, which thus must be valid.
+    unsafe fn try_field_ptr_mut(ptr: Self::PtrMut) -> Option<*mut Self::FieldType>;
+}
+
+/// The field of a [`Field`] part is always present.
+impl<FieldType: ?Sized> TryPartType for Field<FieldType> {
+    type FieldType = FieldType;
+
+    #[inline(always)]
+    unsafe fn try_field_ptr(ptr: *const FieldType) -> Option<*const FieldType> {
+        Some(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn try_field_ptr_mut(ptr: *mut FieldType) -> Option<*mut FieldType> {
+        Some(ptr)
+    }
+}
+
+/// Implemented for all parts of type [`Field`], naming the type of the field.
+///
+/// This is used by [`part_of`](PartialRef::part_of) and [`part_mut_of`](PartialRef::part_mut_of)
+/// to avoid an additional type parameter for the field type.
+pub trait FieldPartType: Part<PartType = Field<<Self as FieldPartType>::FieldType>> {
+    /// The type of the field.
+    type FieldType: ?Sized;
+}
+
+impl<FieldPart, FieldType> FieldPartType for FieldPart
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+{
+    type FieldType = FieldType;
+}
+
+/// *(internal)* Pointer projection through a smart pointer field.
+///
+/// This is used by the [`Field`] parts of fields with a `#[part(deref, ...)]` attribute, which
+/// refer to the value pointed to by the field instead of the field itself. Implemented for `Box`
+/// and mutable references, including pointers to trait objects, e.g. `Box<dyn Trait>`. As the
+/// parts provide mutable access, this is not implemented for shared pointers such as [`Rc`] or
+/// [`Arc`], which use [`SharedField`] parts instead (see [`SharedDerefField`]).
+#[diagnostic::on_unimplemented(
+    message = "the value pointed to by `{Self}` can't be accessed mutably",
+    note = "declare the part as a shared part using `part!(shared PartName: FieldType)`"
+)]
+pub unsafe trait DerefField {
+    /// The type of the value the field points to.
+    type Target: ?Sized;
+
+    /// Given a constant pointer to the field, produce a constant pointer to the value.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn deref_ptr(ptr: *const Self) -> *const Self::Target;
+
+    /// Given a mutable pointer to the field, produce a mutable pointer to the value.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn deref_ptr_mut(ptr: *mut Self) -> *mut Self::Target;
+}
+
+unsafe impl<T: ?Sized> DerefField for Box<T> {
+    type Target = T;
+
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Self) -> *const T {
+        &**ptr
+    }
+
+    #[inline(always)]
+    unsafe fn deref_ptr_mut(ptr: *mut Self) -> *mut T {
+        &mut **ptr
+    }
+}
+
+unsafe impl<T: ?Sized> DerefField for &mut T {
+    type Target = T;
+
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Self) -> *const T {
+        &**ptr
+    }
+
+    #[inline(always)]
+    unsafe fn deref_ptr_mut(ptr: *mut Self) -> *mut T {
+        &mut **ptr
+    }
+}
+
+/// *(internal)* Pointer projection through a shared pointer field.
+///
+/// This is used by the [`SharedField`] parts of fields with a `#[part(deref, ...)]` attribute.
+/// Implemented for `Rc`, `Arc` and shared references, including pointers to trait objects.
+pub unsafe trait SharedDerefField {
+    /// The type of the value the field points to.
+    type Target: ?Sized;
+
+    /// Given a constant pointer to the field, produce a constant pointer to the value.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn deref_ptr(ptr: *const Self) -> *const Self::Target;
+}
+
+unsafe impl<T: ?Sized> SharedDerefField for Rc<T> {
+    type Target = T;
+
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Self) -> *const T {
+        Rc::as_ptr(&*ptr)
+    }
+}
+
+unsafe impl<T: ?Sized> SharedDerefField for Arc<T> {
+    type Target = T;
+
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Self) -> *const T {
+        Arc::as_ptr(&*ptr)
+    }
+}
+
+unsafe impl<T: ?Sized> SharedDerefField for &T {
+    type Target = T;
+
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Self) -> *const T {
+        *ptr
+    }
+}
+
+/// *(internal)* Pointer projection of a `#[part(deref, ...)]` part for a field of type `Pointer`.
+///
+/// This selects [`DerefField`] for [`Field`] parts and [`SharedDerefField`] for [`SharedField`]
+/// parts.
+pub unsafe trait DerefPartType<Pointer: ?Sized>: PartType {
+    /// Given a constant pointer to the field, produce a constant pointer to the value.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn deref_ptr(ptr: *const Pointer) -> Self::Ptr;
+
+    /// Given a mutable pointer to the field, produce a pointer for mutable access to the value.
+    ///
+    /// # Safety
+    /// The pointer must be valid.
+    unsafe fn deref_ptr_mut(ptr: *mut Pointer) -> Self::PtrMut;
+}
+
+unsafe impl<Pointer> DerefPartType<Pointer> for Field<Pointer::Target>
+where
+    Pointer: DerefField + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Pointer) -> *const Pointer::Target {
+        Pointer::deref_ptr(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn deref_ptr_mut(ptr: *mut Pointer) -> *mut Pointer::Target {
+        Pointer::deref_ptr_mut(ptr)
+    }
+}
+
+unsafe impl<Pointer> DerefPartType<Pointer> for SharedField<Pointer::Target>
+where
+    Pointer: SharedDerefField + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn deref_ptr(ptr: *const Pointer) -> *const Pointer::Target {
+        Pointer::deref_ptr(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn deref_ptr_mut(ptr: *mut Pointer) -> *const Pointer::Target {
+        Pointer::deref_ptr(ptr)
+    }
+}
+
+/// *(internal)* Implemented when `FieldPart` is a part for a field of type `Self`.
+///
+/// The derive uses this to check that the field type of a part matches the type of the field.
+#[diagnostic::on_unimplemented(
+    message = "field has type `{Self}` but part `{FieldPart}` expects a field of type `{PartType}`",
+    label = "field type does not match the part `{FieldPart}`"
+)]
+pub trait FieldTypeOf<FieldPart, PartType> {}
+
+impl<FieldPart, FieldType: ?Sized> FieldTypeOf<FieldPart, Field<FieldType>> for FieldType {}
+
+impl<FieldPart, FieldType: ?Sized> FieldTypeOf<FieldPart, PinnedField<FieldType>> for FieldType {}
+
+impl<FieldPart, FieldType> FieldTypeOf<FieldPart, UnalignedField<FieldType>> for FieldType {}
+
+impl<FieldPart, FieldType: ?Sized> FieldTypeOf<FieldPart, LockedField<FieldType>> for FieldType {}
+
+impl<FieldPart, FieldType: ?Sized> FieldTypeOf<FieldPart, RefCellField<FieldType>>
+    for RefCell<FieldType>
+{
+}
+
+/// *(internal)* Fails to compile unless `FieldPart` is a part for a field of type `FieldType`.
+#[inline(always)]
+pub fn check_field_type<FieldPart: Part, FieldType>()
+where
+    FieldType: ?Sized + FieldTypeOf<FieldPart, FieldPart::PartType>,
+{
+}
+
+/// *(internal)* Implemented when `FieldPart` is an [`UnalignedField`] part for a field of type
+/// `Self`.
+///
+/// The derive uses this to check that the fields of packed structs only have parts that don't hand
+/// out references.
+#[diagnostic::on_unimplemented(
+    message = "field of a packed struct has type `{Self}` but part `{FieldPart}` expects `{PartType}`",
+    label = "fields of packed structs require a part of type `UnalignedField<{Self}>`"
+)]
+pub trait UnalignedFieldTypeOf<FieldPart, PartType> {}
+
+impl<FieldPart, FieldType> UnalignedFieldTypeOf<FieldPart, UnalignedField<FieldType>>
+    for FieldType
+{
+}
+
+/// *(internal)* Fails to compile unless `FieldPart` is an [`UnalignedField`] part for a field of
+/// type `FieldType`.
+#[inline(always)]
+pub fn check_unaligned_field_type<FieldPart: Part, FieldType>()
+where
+    FieldType: UnalignedFieldTypeOf<FieldPart, FieldPart::PartType>,
+{
+}
+
+/// Type of a part that covers a group of fields of the struct `Target`.
+///
+/// Group parts are declared using the `#[part_group(...)]` attribute of the derive. Partial
+/// references containing a part of this type provide access to all fields of the group at once
+/// (see [`group`](PartialRef::group)).
+///
+/// A field can be a member of only one group, as otherwise two groups could mutably borrow the
+/// same field:
+///
+/// ```compile_fail
+/// use partial_ref::*;
+///
+/// #[derive(PartialRefTarget)]
+/// #[part_group(A: x, y)]
+/// #[part_group(B: x)]
+/// struct Overlapping {
+///     x: u32,
+///     y: u32,
+/// }
+/// ```
+pub struct FieldGroup<Target: ?Sized>(PhantomData<*const Target>);
+
+impl<Target: ?Sized> PartType for FieldGroup<Target> {
+    type Ptr = *const Target;
+    type PtrMut = *mut Target;
+}
+
+/// *(internal)* Access to the fields covered by a [`FieldGroup`] part.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget.
+pub unsafe trait FieldGroupRefs<'a, Target: ?Sized> {
+    /// A struct of references to the fields of the group.
+    type Ref;
+    /// A struct of mutable references to the fields of the group.
+    type Mut;
+
+    /// Create references to the fields of the group.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the lifetime `'a`.
+    unsafe fn group_ref(ptr: *const Target) -> Self::Ref;
+
+    /// Create mutable references to the fields of the group.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the lifetime `'a`.
+    unsafe fn group_mut(ptr: *mut Target) -> Self::Mut;
+}
+
+/// *(internal)* Structs of plain references to the fields of all field parts of a target.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget with the
+/// attribute `#[partial_ref(view)]`. See [`to_view`](PartialRef::to_view).
+pub unsafe trait TargetView<'a>: PartialRefTarget {
+    /// A struct of references to the fields.
+    type View;
+    /// A struct of mutable references to the fields.
+    type ViewMut;
+    /// A partial reference containing the parts of the fields as constant parts.
+    type ViewRef: PartialRef<'a, Target = Self>;
+    /// A partial reference containing the parts of the fields as mutable parts.
+    type ViewMutRef: PartialRef<'a, Target = Self>;
+
+    /// Create references to the fields.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the lifetime `'a`.
+    unsafe fn view(ptr: *const Self::RawTarget) -> Self::View;
+
+    /// Create mutable references to the fields.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the lifetime `'a`.
+    unsafe fn view_mut(ptr: *mut Self::RawTarget) -> Self::ViewMut;
+}
+
+/// Type of an abstract part.
+///
+/// Partial reference keep track of an abstract part, without providing any operations on it.
+pub struct AbstractPart;
+
+impl PartType for AbstractPart {
+    type Ptr = ();
+    type PtrMut = ();
+}
+
+/// Type of a part that corresponds to a field of an enum variant.
+///
+/// The `Variant` is the variant part of the variant containing the field. Partial references
+/// containing a part of this type provide access to that field, given a [`Witness`] for the
+/// variant.
+pub struct VariantField<Variant, FieldType: ?Sized>(PhantomData<(Variant, *const FieldType)>);
+
+impl<Variant, FieldType: ?Sized> PartType for VariantField<Variant, FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// Type of a part that corresponds to a structurally pinned struct field.
+///
+/// Partial references containing a part of this type provide pinned access to that field (see
+/// [`pin_part_mut`](PartialRef::pin_part_mut)). Such parts are only contained in partial
+/// references created from a pinned reference to the target.
+pub struct PinnedField<FieldType: ?Sized>(PhantomData<*const FieldType>);
+
+impl<FieldType: ?Sized> PartType for PinnedField<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// Type of a part that corresponds to the value pointed to by a shared pointer field.
+///
+/// The parts of fields of type `Rc<T>`, `Arc<T>` or `&T` with a `#[part(deref, ...)]` attribute
+/// have this type (see [`SharedDerefField`]). Partial references containing a part of this type
+/// provide shared access to the value (see [`shared_part`](PartialRef::shared_part)), but no
+/// mutable access, as the value may be shared. Thus its mutable pointer type is a constant
+/// pointer:
+///
+/// ```compile_fail
+/// use partial_ref::*;
+/// use std::rc::Rc;
+///
+/// part!(shared Names: Vec<String>);
+///
+/// #[derive(PartialRefTarget)]
+/// struct Registry {
+///     #[part(deref, Names)]
+///     names: Rc<Vec<String>>,
+/// }
+///
+/// let mut registry = Registry { names: Rc::new(vec![]) };
+/// registry.into_partial_ref_mut().part_mut(Names).push("a".to_owned());
+/// ```
+pub struct SharedField<FieldType: ?Sized>(PhantomData<*const FieldType>);
+
+impl<FieldType: ?Sized> PartType for SharedField<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *const FieldType;
+}
+
+/// Type of a part that corresponds to a field of a union.
+///
+/// Partial references containing a part of this type provide unsafe access to that field (see
+/// [`part_unchecked`](PartialRef::part_unchecked)).
+pub struct UnionField<FieldType: ?Sized>(PhantomData<*const FieldType>);
+
+impl<FieldType: ?Sized> PartType for UnionField<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// Type of a part that corresponds to a possibly unaligned field of a packed struct.
+///
+/// As references to unaligned fields are not allowed, partial references containing a part of this
+/// type provide access to that field using the wrappers [`Unaligned`] and [`UnalignedMut`] (see
+/// [`unaligned_part`](PartialRef::unaligned_part)).
+pub struct UnalignedField<FieldType>(PhantomData<*const FieldType>);
+
+impl<FieldType> PartType for UnalignedField<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// Type of a part that corresponds to a lock-guarded field.
+///
+/// The field type is the lock, e.g. a `Mutex` or `RwLock` (see [`LockField`]). Partial references
+/// containing a part of this type provide access to the field by acquiring the lock (see
+/// [`lock_part_mut`](PartialRef::lock_part_mut)). Mutable parts take exclusive locks. Constant
+/// parts take shared locks and are only accessible for locks supporting them, i.e. a `RwLock` but
+/// not a `Mutex` (see [`SharedLockField`]).
+pub struct LockedField<FieldType: ?Sized>(PhantomData<*const FieldType>);
+
+impl<FieldType: ?Sized> PartType for LockedField<FieldType> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// *(internal)* A lock that can be the field of a [`LockedField`] part.
+pub trait LockField<'a> {
+    /// The guard returned when locking a mutable part.
+    type GuardMut;
+
+    /// Acquire the lock for exclusive access.
+    fn lock_mut(&'a self) -> LockResult<Self::GuardMut>;
+}
+
+/// *(internal)* A lock of a [`LockedField`] part that can be locked for shared access.
+///
+/// A `Mutex` only has exclusive locks, so constant parts of a `Mutex` field cannot be locked.
+pub trait SharedLockField<'a>: LockField<'a> {
+    /// The guard returned when locking a constant part.
+    type Guard;
+
+    /// Acquire the lock for shared access.
+    fn lock_const(&'a self) -> LockResult<Self::Guard>;
+}
+
+impl<'a, T: ?Sized + 'a> LockField<'a> for Mutex<T> {
+    type GuardMut = MutexGuard<'a, T>;
+
+    #[inline(always)]
+    fn lock_mut(&'a self) -> LockResult<MutexGuard<'a, T>> {
+        self.lock()
+    }
+}
+
+impl<'a, T: ?Sized + 'a> LockField<'a> for RwLock<T> {
+    type GuardMut = RwLockWriteGuard<'a, T>;
+
+    #[inline(always)]
+    fn lock_mut(&'a self) -> LockResult<RwLockWriteGuard<'a, T>> {
+        self.write()
+    }
+}
+
+impl<'a, T: ?Sized + 'a> SharedLockField<'a> for RwLock<T> {
+    type Guard = RwLockReadGuard<'a, T>;
+
+    #[inline(always)]
+    fn lock_const(&'a self) -> LockResult<RwLockReadGuard<'a, T>> {
+        self.read()
+    }
+}
+
+/// Type of a part that corresponds to a field of type `RefCell<FieldType>`.
+///
+/// Partial references containing a part of this type provide access to the cell's value using the
+/// guards [`Ref`](std::cell::Ref) and [`RefMut`](std::cell::RefMut), with borrows tracked at
+/// runtime (see [`cell_part`](PartialRef::cell_part)).
+pub struct RefCellField<FieldType: ?Sized>(PhantomData<*const FieldType>);
+
+impl<FieldType: ?Sized> PartType for RefCellField<FieldType> {
+    type Ptr = *const RefCell<FieldType>;
+    type PtrMut = *mut RefCell<FieldType>;
+}
+
+/// Type of a part that corresponds to the bits `LOW..HIGH` of an integer field.
+///
+/// Partial references containing a part of this type provide access to those bits using the
+/// proxies [`Bits`] and [`BitsMut`] (see [`bit_part`](PartialRef::bit_part)). Parts of this type
+/// are [`BitPart`]s, which refer to an integer field of type `FieldType`.
+pub struct BitField<FieldType, const LOW: u32, const HIGH: u32>(PhantomData<*const FieldType>);
+
+impl<FieldType, const LOW: u32, const HIGH: u32> PartType for BitField<FieldType, LOW, HIGH> {
+    type Ptr = *const FieldType;
+    type PtrMut = *mut FieldType;
+}
+
+/// Type of a part that corresponds to one field of every row of a slice of rows.
+///
+/// The rows have type `Row` and the field is given by the field part `FieldPart` of `Row`.
+/// Partial references containing a part of this type provide access to the field of all rows
+/// using the views [`Column`] and [`ColumnMut`] (see [`column_part`](PartialRef::column_part)).
+/// Parts of this type are [`ColumnPart`]s.
+pub struct ColumnField<Row, FieldPart>(PhantomData<(*const Row, FieldPart)>);
+
+impl<Row, FieldPart> PartType for ColumnField<Row, FieldPart> {
+    type Ptr = *const [Row];
+    type PtrMut = *mut [Row];
+}
+
+/// Shared access to a possibly unaligned field.
+///
+/// Returned by [`unaligned_part`](PartialRef::unaligned_part).
+pub struct Unaligned<'a, FieldType> {
+    ptr: *const FieldType,
+    phantom: PhantomData<&'a FieldType>,
+}
+
+impl<'a, FieldType> Unaligned<'a, FieldType> {
+    /// Read a copy of the field's value.
+    #[inline(always)]
+    pub fn get(&self) -> FieldType
+    where
+        FieldType: Copy,
+    {
+        unsafe { self.ptr.read_unaligned() }
+    }
+
+    /// Pointer to the field, which might not be aligned.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const FieldType {
+        self.ptr
+    }
+}
+
+impl<'a, FieldType> Copy for Unaligned<'a, FieldType> {}
+
+impl<'a, FieldType> Clone for Unaligned<'a, FieldType> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Mutable access to a possibly unaligned field.
+///
+/// Returned by [`unaligned_part_mut`](PartialRef::unaligned_part_mut).
+pub struct UnalignedMut<'a, FieldType> {
+    ptr: *mut FieldType,
+    phantom: PhantomData<&'a mut FieldType>,
+}
+
+impl<'a, FieldType> UnalignedMut<'a, FieldType> {
+    /// Read a copy of the field's value.
+    #[inline(always)]
+    pub fn get(&self) -> FieldType
+    where
+        FieldType: Copy,
+    {
+        unsafe { self.ptr.read_unaligned() }
+    }
+
+    /// Overwrite the field's value, dropping the previous value.
+    #[inline(always)]
+    pub fn set(&mut self, value: FieldType) {
+        drop(self.replace(value));
+    }
+
+    /// Overwrite the field's value, returning the previous value.
+    #[inline(always)]
+    pub fn replace(&mut self, value: FieldType) -> FieldType {
+        unsafe {
+            let previous = self.ptr.read_unaligned();
+            self.ptr.write_unaligned(value);
+            previous
+        }
+    }
+
+    /// Modify the field's value using an aligned copy.
+    #[inline(always)]
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut FieldType) -> R) -> R
+    where
+        FieldType: Copy,
+    {
+        let mut value = self.get();
+        let result = f(&mut value);
+        self.set(value);
+        result
+    }
+
+    /// Reborrow as shared access to the field.
+    #[inline(always)]
+    pub fn as_unaligned(&self) -> Unaligned<'_, FieldType> {
+        Unaligned {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Pointer to the field, which might not be aligned.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut FieldType {
+        self.ptr
+    }
+}
+
+/// Shared access to a bit range of an integer field.
+///
+/// Returned by [`bit_part`](PartialRef::bit_part).
+pub struct Bits<'a, FieldType> {
+    ptr: *const FieldType,
+    low: u32,
+    high: u32,
+    phantom: PhantomData<&'a FieldType>,
+}
+
+impl<'a, FieldType: BitsField> Bits<'a, FieldType> {
+    /// Read the bits, shifted to the lowest bits of the result.
+    #[inline(always)]
+    pub fn get(&self) -> FieldType {
+        unsafe { self.ptr.read() }.get_bits(self.low, self.high)
+    }
+}
+
+impl<'a, FieldType> Copy for Bits<'a, FieldType> {}
+
+impl<'a, FieldType> Clone for Bits<'a, FieldType> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Mutable access to a bit range of an integer field.
+///
+/// Returned by [`bit_part_mut`](PartialRef::bit_part_mut). Bits of the field outside of the range
+/// may be covered by other parts, so writing only modifies the bits of the range.
+pub struct BitsMut<'a, FieldType> {
+    ptr: *mut FieldType,
+    low: u32,
+    high: u32,
+    phantom: PhantomData<&'a mut FieldType>,
+}
+
+impl<'a, FieldType: BitsField> BitsMut<'a, FieldType> {
+    /// Read the bits, shifted to the lowest bits of the result.
+    #[inline(always)]
+    pub fn get(&self) -> FieldType {
+        unsafe { self.ptr.read() }.get_bits(self.low, self.high)
+    }
+
+    /// Overwrite the bits with the lowest bits of `value`, ignoring its other bits.
+    #[inline(always)]
+    pub fn set(&mut self, value: FieldType) {
+        unsafe {
+            let field = self.ptr.read();
+            self.ptr.write(field.set_bits(self.low, self.high, value));
+        }
+    }
+
+    /// Modify the bits using a copy shifted to the lowest bits.
+    #[inline(always)]
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut FieldType) -> R) -> R {
+        let mut value = self.get();
+        let result = f(&mut value);
+        self.set(value);
+        result
+    }
+
+    /// Reborrow as shared access to the bits.
+    #[inline(always)]
+    pub fn as_bits(&self) -> Bits<'_, FieldType> {
+        Bits {
+            ptr: self.ptr,
+            low: self.low,
+            high: self.high,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Shared access to one field of every row of a slice of rows.
+///
+/// Returned by [`column_part`](PartialRef::column_part).
+pub struct Column<'a, Row, FieldPart> {
+    rows: *const [Row],
+    phantom: PhantomData<(&'a [Row], FieldPart)>,
+}
+
+impl<'a, Row, FieldPart, FieldType> Column<'a, Row, FieldPart>
+where
+    Row: PartialRefTarget<RawTarget = Row> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized + 'a,
+{
+    /// The number of rows.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no rows.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The field of the row at `index`, or `None` if out of bounds.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&'a FieldType> {
+        if index < self.len() {
+            Some(unsafe { &*Row::part_ptr((self.rows as *const Row).add(index)) })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the field of every row.
+    #[inline(always)]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a FieldType> + 'a
+    where
+        Row: 'a,
+        FieldPart: 'a,
+    {
+        let rows = self.rows as *const Row;
+        (0..self.len()).map(move |index| unsafe { &*Row::part_ptr(rows.add(index)) })
+    }
+}
+
+impl<'a, Row, FieldPart> Copy for Column<'a, Row, FieldPart> {}
+
+impl<'a, Row, FieldPart> Clone for Column<'a, Row, FieldPart> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Row, FieldPart, FieldType> Index<usize> for Column<'a, Row, FieldPart>
+where
+    Row: PartialRefTarget<RawTarget = Row> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized + 'a,
+{
+    type Output = FieldType;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &FieldType {
+        self.get(index).expect("column index out of bounds")
+    }
+}
+
+/// Mutable access to one field of every row of a slice of rows.
+///
+/// Returned by [`column_part_mut`](PartialRef::column_part_mut). Other fields of the rows may be
+/// covered by other column parts, so this never creates references to whole rows.
+pub struct ColumnMut<'a, Row, FieldPart> {
+    rows: *mut [Row],
+    phantom: PhantomData<(&'a mut [Row], FieldPart)>,
+}
+
+impl<'a, Row, FieldPart, FieldType> ColumnMut<'a, Row, FieldPart>
+where
+    Row: PartialRefTarget<RawTarget = Row> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized + 'a,
+{
+    /// The number of rows.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no rows.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The field of the row at `index`, or `None` if out of bounds.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&FieldType> {
+        self.as_column().get(index)
+    }
+
+    /// Mutable access to the field of the row at `index`, or `None` if out of bounds.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut FieldType> {
+        if index < self.len() {
+            Some(unsafe { &mut *Row::part_ptr_mut((self.rows as *mut Row).add(index)) })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the field of every row.
+    #[inline(always)]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &FieldType> + '_ {
+        let rows = self.rows as *const Row;
+        (0..self.len()).map(move |index| unsafe { &*Row::part_ptr(rows.add(index)) })
+    }
+
+    /// Iterate mutably over the field of every row.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut FieldType> + '_ {
+        let rows = self.rows as *mut Row;
+        (0..self.len()).map(move |index| unsafe { &mut *Row::part_ptr_mut(rows.add(index)) })
+    }
+
+    /// Reborrow as shared access to the column.
+    #[inline(always)]
+    pub fn as_column(&self) -> Column<'_, Row, FieldPart> {
+        Column {
+            rows: self.rows,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, Row, FieldPart, FieldType> Index<usize> for ColumnMut<'a, Row, FieldPart>
+where
+    Row: PartialRefTarget<RawTarget = Row> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized + 'a,
+{
+    type Output = FieldType;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &FieldType {
+        self.get(index).expect("column index out of bounds")
+    }
+}
+
+impl<'a, Row, FieldPart, FieldType> IndexMut<usize> for ColumnMut<'a, Row, FieldPart>
+where
+    Row: PartialRefTarget<RawTarget = Row> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized + 'a,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut FieldType {
+        self.get_mut(index).expect("column index out of bounds")
+    }
+}
+
+/// Implemented when a reference target is an enum having a variant identified by a variant part.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget.
+pub unsafe trait HasVariant<VariantPart: Part>: HasPart<VariantPart> {
+    /// Check whether the target pointed to is the variant identified by `VariantPart`.
+    ///
+    /// # Safety
+    /// The pointer must be valid. This only reads the discriminant of the target, which must be
+    /// stored separately from the variant fields.
+    unsafe fn is_variant(ptr: *const Self::RawTarget) -> bool;
+}
+
+/// A partial reference to an enum that was checked to be a specific variant.
+///
+/// A witness is returned by a successful [`witness`](PartialRef::witness) check and provides access
+/// to the parts of fields that belong to the variant identified by `VariantPart`. The witness owns
+/// the checked partial reference, so the variant can't change while the witness exists and its
+/// accessors don't need to check the variant again. The remaining parts are available again using
+/// [`into_inner`](Witness::into_inner).
+///
+/// Parts of variant fields can't be accessed without a witness:
+///
+/// ```compile_fail
+/// # use partial_ref::*;
+/// part!(Running);
+/// part!(Progress in Running: u32);
+///
+/// #[derive(PartialRefTarget)]
+/// #[repr(u8)]
+/// enum Job {
+///     #[part(Running)]
+///     Running {
+///         #[part(Progress)]
+///         progress: u32,
+///     },
+///     Done,
+/// }
+///
+/// let job = Job::Done;
+/// job.into_partial_ref().part(Progress);
+/// ```
+pub struct Witness<Reference, VariantPart> {
+    reference: Reference,
+    phantom: PhantomData<VariantPart>,
+}
+
+impl<'a, Reference, VariantPart> Witness<Reference, VariantPart>
+where
+    Reference: PartialRef<'a>,
+{
+    /// Access a part of a field of the variant.
+    ///
+    /// This is equivalent to [`part`](PartialRef::part) for parts of type [`VariantField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn part<'b, FieldPartSpec, FieldPart, PartIndex, FieldType, VariantIndex>(
+        &'b self,
+        _part: FieldPartSpec,
+    ) -> &'b FieldType
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: VariantFieldOf<VariantPart, FieldType, VariantIndex>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Reference: PluckConst<'a, FieldPart, PartIndex>,
+        Reference::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe { &*<Reference::Target as HasPart<FieldPart>>::part_ptr(self.reference.get_raw()) }
+    }
+
+    /// Mutable access to a part of a field of the variant.
+    ///
+    /// This is equivalent to [`part_mut`](PartialRef::part_mut) for parts of type
+    /// [`VariantField`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn part_mut<'b, FieldPartSpec, FieldPart, PartIndex, FieldType, VariantIndex>(
+        &'b mut self,
+        _part: FieldPartSpec,
+    ) -> &'b mut FieldType
+    where
+        'a: 'b,
+        FieldType: ?Sized,
+        FieldPart: VariantFieldOf<VariantPart, FieldType, VariantIndex>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Reference: PluckMut<'a, FieldPart, PartIndex>,
+        Reference::Target: HasPart<FieldPart> + 'a,
+    {
+        unsafe {
+            &mut *<Reference::Target as HasPart<FieldPart>>::part_ptr_mut(self.reference.get_raw())
+        }
+    }
+
+    /// Access a part of a field of the variant, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`split_part`](PartialRef::split_part) for parts of type
+    /// [`VariantField`]. The remaining parts are still checked to be the variant.
+    #[inline(always)]
+    pub fn split_part<FieldPartSpec, FieldPart, PartIndex, FieldType, VariantIndex>(
+        self,
+        _part: FieldPartSpec,
+    ) -> (&'a FieldType, Witness<Reference::Remainder, VariantPart>)
+    where
+        FieldType: ?Sized,
+        FieldPart: VariantFieldOf<VariantPart, FieldType, VariantIndex>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Reference: PluckConst<'a, FieldPart, PartIndex>,
+        Reference::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.reference.get_raw();
+        unsafe {
+            (
+                &*<Reference::Target as HasPart<FieldPart>>::part_ptr(ptr),
+                Witness {
+                    reference: Reference::Remainder::from_raw(ptr),
+                    phantom: PhantomData,
+                },
+            )
+        }
+    }
+
+    /// Mutable access to a part of a field of the variant, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`split_part_mut`](PartialRef::split_part_mut) for parts of type
+    /// [`VariantField`]. The remaining parts are still checked to be the variant.
+    #[inline(always)]
+    pub fn split_part_mut<FieldPartSpec, FieldPart, PartIndex, FieldType, VariantIndex>(
+        self,
+        _part: FieldPartSpec,
+    ) -> (
+        &'a mut FieldType,
+        Witness<Reference::Remainder, VariantPart>,
+    )
+    where
+        FieldType: ?Sized,
+        FieldPart: VariantFieldOf<VariantPart, FieldType, VariantIndex>,
+        FieldPartSpec: PartSpec<FieldPart>,
+        Reference: PluckMut<'a, FieldPart, PartIndex>,
+        Reference::Target: HasPart<FieldPart> + 'a,
+    {
+        let ptr = self.reference.get_raw();
+        unsafe {
+            (
+                &mut *<Reference::Target as HasPart<FieldPart>>::part_ptr_mut(ptr),
+                Witness {
+                    reference: Reference::Remainder::from_raw(ptr),
+                    phantom: PhantomData,
+                },
+            )
+        }
+    }
+
+    /// Return the checked partial reference.
+    #[inline(always)]
+    pub fn into_inner(self) -> Reference {
+        self.reference
+    }
+}
+
+/// *(internal)* Implemented for parts of fields of the variant identified by `VariantPart`.
+///
+/// These are the parts of type [`VariantField`] for that variant. For a variant part nested in an
+/// enum field, e.g. `Outer | VariantPart`, these are the parts of that variant's fields nested in
+/// the same field, e.g. `Outer | FieldPart`. The `Index` distinguishes these cases and is
+/// inferred.
+///
+/// A witness for a variant of one enum field doesn't give access to the variant fields of another
+/// enum field of the same type:
+///
+/// ```compile_fail
+/// # use partial_ref::*;
+/// part!(pub Running);
+/// part!(pub Progress in Running: u32);
+///
+/// #[derive(PartialRefTarget)]
+/// #[repr(u8)]
+/// pub enum Job {
+///     #[part(Running)]
+///     Running {
+///         #[part(Progress)]
+///         progress: u32,
+///     },
+///     Done,
+/// }
+///
+/// #[derive(PartialRefTarget)]
+/// pub struct Jobs {
+///     #[part(auto)]
+///     pub first: Job,
+///     #[part(auto)]
+///     pub second: Job,
+/// }
+///
+/// fn progress(jobs: partial!(Jobs, First | Running, Second | Progress)) {
+///     if let Ok(running) = jobs.witness(First | Running) {
+///         running.part(Second | Progress);
+///     }
+/// }
+/// ```
+pub trait VariantFieldOf<VariantPart, FieldType: ?Sized, Index>:
+    Part<PartType = VariantField<Self::FieldVariant, FieldType>>
+{
+    /// The variant part of the part's [`VariantField`] part type.
+    type FieldVariant;
+}
+
+impl<FieldPart, VariantPart, FieldType> VariantFieldOf<VariantPart, FieldType, IndexHere>
+    for FieldPart
+where
+    FieldType: ?Sized,
+    FieldPart: Part<PartType = VariantField<VariantPart, FieldType>>,
+{
+    type FieldVariant = VariantPart;
+}
+
+impl<Outer, Inner, VariantPart, FieldType, Index>
+    VariantFieldOf<Nested<Outer, VariantPart>, FieldType, IndexNext<Index>> for Nested<Outer, Inner>
+where
+    FieldType: ?Sized,
+    Nested<Outer, Inner>: Part<PartType = VariantField<VariantPart, FieldType>>,
+    Inner: VariantFieldOf<VariantPart, FieldType, Index>,
+{
+    type FieldVariant = VariantPart;
+}
+
+/// A nested part.
+///
+/// A nested part can be constructed from an outer part and an inner part. The outer part must be a
+/// [`Field`] part, and the field's type must be a [`PartialRefTarget`] having the the inner part
+/// ([`HasPart`]).
+///
+/// When nesting multiple times, the nested part should always be the outer part. This isn't
+/// enforced, but some operations are only supported in that case.
+///
+/// The field's type can be a type parameter of the containing target, e.g. `S` for a part
+/// `SolverPart<S>`, as long as it is bounded by [`HasPart`] for the inner parts and by
+/// `PartialRefTarget<RawTarget = S>`. Generic code can then use nested parts like
+/// `SolverPart<S> | InnerPart` that are listed in its partial references. Re-borrowing such a
+/// nested part from a reference containing the whole part `SolverPart<S>` requires splitting the
+/// field into all of its parts using [`SplitIntoParts`], which is only possible when the field's
+/// type is known.
+#[derive(Default)]
+pub struct Nested<Outer, Inner>(Outer, Inner);
+
+/// Create default values for nested parts.
+///
+/// These are useful as parameters to pass to [`PartialRef`]'s part functions.
+impl<NewInnerPart: Part, Outer: Part, Inner: Part> std::ops::BitOr<NewInnerPart>
+    for Nested<Outer, Inner>
+{
+    type Output = Nested<Nested<Outer, Inner>, NewInnerPart>;
+
+    fn bitor(self, _rhs: NewInnerPart) -> Self::Output {
+        std::default::Default::default()
+    }
+}
+
+/// A nested part is itself a part.
+impl<Outer, OuterFieldType, Inner> Part for Nested<Outer, Inner>
+where
+    Outer: Part<PartType = Field<OuterFieldType>>,
+    Inner: Part,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasPart<Inner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+{
+    type PartType = Inner::PartType;
+}
+
+impl<Outer, Inner, OuterS, InnerS> PartSpec<Nested<OuterS, InnerS>> for Nested<Outer, Inner>
+where
+    Outer: PartSpec<OuterS>,
+    Inner: PartSpec<InnerS>,
+{
+}
+
+/// A reference has a nested part if it has the outer part and the nested part is valid.
+impl<Reference, Outer, OuterFieldType, Inner> HasPart<Nested<Outer, Inner>> for Reference
+where
+    Reference: ?Sized,
+    Reference: HasPart<Outer>,
+    Outer: Part<PartType = Field<OuterFieldType>>,
+    Inner: Part,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasPart<Inner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+{
+    #[inline(always)]
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> <Inner::PartType as PartType>::Ptr {
+        <OuterFieldType as HasPart<Inner>>::part_ptr(<Self as HasPart<Outer>>::part_ptr(ptr))
+    }
+
+    #[inline(always)]
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> <Inner::PartType as PartType>::PtrMut {
+        <OuterFieldType as HasPart<Inner>>::part_ptr_mut(<Self as HasPart<Outer>>::part_ptr_mut(
+            ptr,
+        ))
+    }
+}
+
+/// A nested variant part identifies a variant of an enum field.
+unsafe impl<Reference, Outer, OuterFieldType, Inner> HasVariant<Nested<Outer, Inner>> for Reference
+where
+    Reference: ?Sized,
+    Reference: HasPart<Outer>,
+    Outer: Part<PartType = Field<OuterFieldType>>,
+    Inner: Part,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasVariant<Inner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+{
+    #[inline(always)]
+    unsafe fn is_variant(ptr: *const Self::RawTarget) -> bool {
+        <OuterFieldType as HasVariant<Inner>>::is_variant(<Self as HasPart<Outer>>::part_ptr(ptr))
+    }
+}
+
+/// A nested group part covers the fields of the inner group part.
+unsafe impl<'a, Outer, Inner, Target> FieldGroupRefs<'a, Target> for Nested<Outer, Inner>
+where
+    Target: ?Sized,
+    Inner: FieldGroupRefs<'a, Target>,
+{
+    type Ref = Inner::Ref;
+    type Mut = Inner::Mut;
+
+    #[inline(always)]
+    unsafe fn group_ref(ptr: *const Target) -> Self::Ref {
+        Inner::group_ref(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn group_mut(ptr: *mut Target) -> Self::Mut {
+        Inner::group_mut(ptr)
+    }
+}
+
+/// One of several instances of the part `LogicalPart`, distinguished by the tag `TAG`.
+///
+/// A tagged part has the same part type as `LogicalPart`, but `TaggedPart<LogicalPart, 0>` and
+/// `TaggedPart<LogicalPart, 1>` are different parts. This allows declaring the parts of several
+/// fields having the same role with a single part declaration, e.g. `#[part(TaggedPart<ClauseDb,
+/// 0>)]` and `#[part(TaggedPart<ClauseDb, 1>)]` on two fields, and writing code that is generic
+/// over the tag, e.g. taking a `partial!(Solver, mut TaggedPart<ClauseDb, N>)`. Such code needs
+/// the bound `Solver: HasPart<TaggedPart<ClauseDb, N>>`, as the target only has the parts for the
+/// tags used in its declaration.
+pub struct TaggedPart<LogicalPart, const TAG: usize>(PhantomData<LogicalPart>);
+
+impl<LogicalPart, const TAG: usize> Default for TaggedPart<LogicalPart, TAG> {
+    fn default() -> Self {
+        TaggedPart(PhantomData)
+    }
+}
+
+impl<LogicalPart: Part, const TAG: usize> Part for TaggedPart<LogicalPart, TAG> {
+    type PartType = LogicalPart::PartType;
+}
+
+impl<LogicalPart, LogicalPartS, const TAG: usize> PartSpec<TaggedPart<LogicalPartS, TAG>>
+    for TaggedPart<LogicalPart, TAG>
+where
+    LogicalPart: PartSpec<LogicalPartS>,
+{
+}
+
+impl<LogicalPart, const TAG: usize> NormalizeNestedPart for TaggedPart<LogicalPart, TAG> {
+    type Normalized = TaggedPart<LogicalPart, TAG>;
+}
+
+impl<NestedPrefix, LogicalPart, const TAG: usize> AppendNestedPart<NestedPrefix>
+    for TaggedPart<LogicalPart, TAG>
+{
+    type Output = Nested<NestedPrefix, TaggedPart<LogicalPart, TAG>>;
+}
+
+/// Create default values for nested parts of tagged parts.
+impl<LogicalPart: Part, InnerPart: Part, const TAG: usize> std::ops::BitOr<InnerPart>
+    for TaggedPart<LogicalPart, TAG>
+{
+    type Output = Nested<TaggedPart<LogicalPart, TAG>, InnerPart>;
+
+    fn bitor(self, _rhs: InnerPart) -> Self::Output {
+        std::default::Default::default()
+    }
+}
+
+// Type level logic for subsetting
+// This is inspired by the `frunk` crate
+
+/// *(internal)* Select the first part.
+pub struct IndexHere;
+
+/// *(internal)* Skip the first part.
+pub struct IndexNext<Index>(Index);
+
+/// *(internal)* Split the first part.
+pub struct IndexSplit<NestedPartIndex, Index>(NestedPartIndex, Index);
+
+/// *(internal)* Extracts the constant part `PluckedPart` at position `Index` from the partial
+/// reference having this trait, leaving `Self::Remainder`.
+///
+/// Plucking a constant part still leaves the part in the remaining reference, but will change it
+/// from mutable to constant.
+///
+/// The `Index` type can be inferred.
+pub unsafe trait PluckConst<'a, PluckedPart, Index>: PartialRef<'a> {
+    /// The partial reference left after plucking.
+    type Remainder: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* Extracts the mutable part `PluckedPart` at position `Index` from the partial
+/// reference having this trait, leaving `Self::Remainder`.
+///
+/// Plucking a mutable part removes it from the remaining reference.
+///
+/// The `Index` type can be inferred.
+pub unsafe trait PluckMut<'a, PluckedPart, Index>: PartialRef<'a> {
+    /// The partial reference left after plucking.
+    type Remainder: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* Plucks the outermost constant part.
+unsafe impl<'a, PluckedPart, Reference> PluckConst<'a, PluckedPart, IndexHere>
+    for Const<PluckedPart, Reference>
+where
+    PluckedPart: Part,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<PluckedPart>,
+{
+    type Remainder = Const<PluckedPart, Reference>;
+}
+
+/// *(internal)* Plucks the first part, converting it from mutable to constant.
+unsafe impl<'a, PluckedPart, Reference> PluckConst<'a, PluckedPart, IndexHere>
+    for Mut<PluckedPart, Reference>
+where
+    PluckedPart: Part,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<PluckedPart>,
+{
+    type Remainder = Const<PluckedPart, Reference>;
+}
+
+/// *(internal)* Plucks the first mutable part.
+unsafe impl<'a, PluckedPart, Reference> PluckMut<'a, PluckedPart, IndexHere>
+    for Mut<PluckedPart, Reference>
+where
+    PluckedPart: Part,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<PluckedPart>,
+{
+    type Remainder = Reference;
+}
+
+/// *(internal)* Skips the constant first part while plucking a constant part.
+unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
+    PluckConst<'a, PluckedPart, IndexNext<Index>> for Const<SkippedPart, Reference>
+where
+    PluckedPart: Part,
+    SkippedPart: Part,
+    Reference::Target: HasPart<PluckedPart>,
+    Reference::Target: HasPart<SkippedPart>,
+    Reference: PluckConst<'a, PluckedPart, Index>,
+{
+    type Remainder = Const<SkippedPart, Reference::Remainder>;
+}
+
+/// *(internal)* Skips the mutable first part while plucking a constant part.
+unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
+    PluckConst<'a, PluckedPart, IndexNext<Index>> for Mut<SkippedPart, Reference>
+where
+    PluckedPart: Part,
+    SkippedPart: Part,
+    Reference::Target: HasPart<PluckedPart>,
+    Reference::Target: HasPart<SkippedPart>,
+    Reference: PluckConst<'a, PluckedPart, Index>,
+{
+    type Remainder = Mut<SkippedPart, Reference::Remainder>;
+}
+
+/// *(internal)* Skips the constant first part while plucking a mutable part.
+unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
+    PluckMut<'a, PluckedPart, IndexNext<Index>> for Const<SkippedPart, Reference>
+where
+    PluckedPart: Part,
+    SkippedPart: Part,
+    Reference::Target: HasPart<PluckedPart>,
+    Reference::Target: HasPart<SkippedPart>,
+    Reference: PluckMut<'a, PluckedPart, Index>,
+{
+    type Remainder = Const<SkippedPart, Reference::Remainder>;
+}
+
+/// *(internal)* Skips the mutable first part while plucking a mutable part.
+unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
+    PluckMut<'a, PluckedPart, IndexNext<Index>> for Mut<SkippedPart, Reference>
+where
+    PluckedPart: Part,
+    SkippedPart: Part,
+    Reference::Target: HasPart<PluckedPart>,
+    Reference::Target: HasPart<SkippedPart>,
+    Reference: PluckMut<'a, PluckedPart, Index>,
+{
+    type Remainder = Mut<SkippedPart, Reference::Remainder>;
+}
+
+/// *(internal)* Pluck a mutable nested subpart of the mutable first part.
+///
+/// This leaves all other subparts in the remaining reference.
+unsafe impl<
+        'a,
+        ContainingPart,
+        PluckedOuter,
+        PluckedInner,
+        Reference,
+        NestedPartIndex,
+        Index,
+        OuterFieldType,
+        ContainingFieldType,
+    > PluckMut<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
+    for Mut<ContainingPart, Reference>
+where
+    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
+    PluckedInner: Part,
+    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
+    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
+    ContainingFieldType: ?Sized,
+    ContainingFieldType::ResultMut: PluckMut<'a, Nested<PluckedOuter, PluckedInner>, Index>,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasPart<PluckedInner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<ContainingPart>,
+    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+{
+    type Remainder = <ContainingFieldType::ResultMut as PluckMut<
+        'a,
+        Nested<PluckedOuter, PluckedInner>,
+        Index,
+    >>::Remainder;
+}
+
+/// *(internal)* Pluck a constant nested subpart of the mutable first part.
+///
+/// This leaves all other subparts as mutable in the remaining reference.
+unsafe impl<
+        'a,
+        ContainingPart,
+        PluckedOuter,
+        PluckedInner,
+        Reference,
+        NestedPartIndex,
+        Index,
+        OuterFieldType,
+        ContainingFieldType,
+    > PluckConst<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
+    for Mut<ContainingPart, Reference>
+where
+    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
+    PluckedInner: Part,
+    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
+    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
+    ContainingFieldType: ?Sized,
+    ContainingFieldType::ResultMut: PluckConst<'a, Nested<PluckedOuter, PluckedInner>, Index>,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasPart<PluckedInner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<ContainingPart>,
+    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+{
+    type Remainder = <ContainingFieldType::ResultMut as PluckConst<
+        'a,
+        Nested<PluckedOuter, PluckedInner>,
+        Index,
+    >>::Remainder;
+}
+
+/// *(internal)* Pluck a constant nested subpart of the constant first part.
+unsafe impl<
+        'a,
+        ContainingPart,
+        PluckedOuter,
+        PluckedInner,
+        Reference,
+        NestedPartIndex,
+        Index,
+        OuterFieldType,
+        ContainingFieldType,
+    > PluckConst<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
+    for Const<ContainingPart, Reference>
+where
+    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
+    PluckedInner: Part,
+    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
+    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
+    ContainingFieldType: ?Sized,
+    ContainingFieldType::ResultMut: PluckConst<'a, Nested<PluckedOuter, PluckedInner>, Index>,
+    OuterFieldType: ?Sized,
+    OuterFieldType: HasPart<PluckedInner>,
+    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<ContainingPart>,
+    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+{
+    type Remainder = Const<ContainingPart, Reference>;
+}
+
+/// *(internal)* Index for the empty subset.
+pub struct SubsetIndexEnd;
+
+/// *(internal)* Construct a subset index from a part index and another subset index.
+pub struct SubsetIndexCons<PartIndex, TailIndex>(PartIndex, TailIndex);
+
+/// *(internal)* Asserts that the parts of the partial reference `Reference` are a subset of the
+/// parts of the partial reference having this trait.
+///
+/// A list of parts is considered a subset if they can be plucked in sequence.
+pub unsafe trait HasSubset<'a, Reference, SubsetIndex>: PartialRef<'a> {
+    type Remainder: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* Every reference has the empty reference as subset.
+unsafe impl<'a, Reference> HasSubset<'a, Ref<'a, Reference::Target>, SubsetIndexEnd> for Reference
+where
+    Reference: PartialRef<'a>,
+{
+    type Remainder = Reference;
+}
+
+/// *(internal)* To have a reference with a constant first part as subset, pluck that part and make
+/// sure the remaining references are in a subset relation.
+unsafe impl<'a, SubsetPart, Reference, PluckedRef, PartIndex, TailIndex>
+    HasSubset<'a, Const<SubsetPart, Reference>, SubsetIndexCons<PartIndex, TailIndex>>
+    for PluckedRef
+where
+    PluckedRef: PluckConst<'a, SubsetPart, PartIndex>,
+    <PluckedRef as PluckConst<'a, SubsetPart, PartIndex>>::Remainder:
+        HasSubset<'a, Reference, TailIndex>,
+    Reference: HasTarget,
+{
+    type Remainder =
+        <<PluckedRef as PluckConst<'a, SubsetPart, PartIndex>>::Remainder as HasSubset<
+            'a,
+            Reference,
+            TailIndex,
+        >>::Remainder;
+}
+
+/// *(internal)* To have a reference with a mutable first part as subset, pluck that part and make
+/// sure the remaining references are in a subset relation.
+unsafe impl<'a, SubsetPart, Reference, PluckedRef, PartIndex, TailIndex>
+    HasSubset<'a, Mut<SubsetPart, Reference>, SubsetIndexCons<PartIndex, TailIndex>> for PluckedRef
+where
+    PluckedRef: PluckMut<'a, SubsetPart, PartIndex>,
+    <PluckedRef as PluckMut<'a, SubsetPart, PartIndex>>::Remainder:
+        HasSubset<'a, Reference, TailIndex>,
+    Reference: HasTarget,
+{
+    type Remainder =
+        <<PluckedRef as PluckMut<'a, SubsetPart, PartIndex>>::Remainder as HasSubset<
+            'a,
+            Reference,
+            TailIndex,
+        >>::Remainder;
+}
+
+/// Asserts that the partial reference having this trait can be converted into the partial
+/// reference `Reference` having a subset of its parts.
+///
+/// This allows functions to accept any partial reference having the required parts, so callers can
+/// pass larger references without an explicit [`borrow`](PartialRef::borrow), similar to an `Into`
+/// bound. Partial references don't implement `From` for their subsets, as every reference is a
+/// subset of itself, which would conflict with the implementation of `From<T>` for `T`.
+///
+/// The `SubsetIndex` type can be inferred.
+pub unsafe trait IntoSubset<'a, Reference, SubsetIndex>:
+    PartialRef<'a> + HasTarget<Target = <Reference as HasTarget>::Target>
+where
+    Reference: PartialRef<'a>,
+{
+    /// Convert this reference into the reference `Reference` having a subset of its parts.
+    ///
+    /// This is equivalent to [`convert`](PartialRef::convert).
+    #[inline(always)]
+    fn into_subset(self) -> Reference {
+        unsafe { Reference::from_raw(self.get_raw()) }
+    }
+}
+
+/// *(internal)* A reference can be converted into every reference that is a subset.
+unsafe impl<'a, Reference, OtherRef, SubsetIndex> IntoSubset<'a, OtherRef, SubsetIndex>
+    for Reference
+where
+    Reference: HasSubset<'a, OtherRef, SubsetIndex>,
+    OtherRef: PartialRef<'a, Target = Reference::Target>,
+{
+}
+
+/// *(internal)* Index for two references having the same parts, consisting of the subset indices
+/// for both directions.
+pub struct SamePartsIndex<SubsetIndex, SupersetIndex>(SubsetIndex, SupersetIndex);
+
+/// Asserts that the partial reference having this trait has exactly the same parts as the partial
+/// reference `Reference`.
+///
+/// This holds when each reference has the other as subset, i.e. when both references list the
+/// same parts with the same mutability, independent of the order in which they are listed. Unlike
+/// a [`HasSubset`] bound, this rejects references with additional parts.
+///
+/// The `SameIndex` type can be inferred.
+pub unsafe trait SameParts<'a, Reference, SameIndex>:
+    PartialRef<'a> + HasTarget<Target = <Reference as HasTarget>::Target>
+where
+    Reference: PartialRef<'a>,
+{
+    /// Convert this reference into the reference `Reference` having the same parts.
+    ///
+    /// This only reorders the listed parts and doesn't change which parts are accessible.
+    #[inline(always)]
+    fn into_same_parts(self) -> Reference {
+        unsafe { Reference::from_raw(self.get_raw()) }
+    }
+}
+
+/// *(internal)* Two references have the same parts if each is a subset of the other.
+unsafe impl<'a, Reference, OtherRef, SubsetIndex, SupersetIndex>
+    SameParts<'a, OtherRef, SamePartsIndex<SubsetIndex, SupersetIndex>> for Reference
+where
+    Reference: HasSubset<'a, OtherRef, SubsetIndex>,
+    OtherRef: HasSubset<'a, Reference, SupersetIndex, Target = Reference::Target>,
+{
+}
+
+// Constant references
+
+/// *(internal)* Replace all mutable parts of a partial reference with constant parts.
+pub unsafe trait ConstParts<'a>: PartialRef<'a> {
+    /// The partial reference having the same parts as `Self`, but only as constant parts.
+    type ConstRef: PartialRef<'a, Target = Self::Target> + ConstOnly;
+}
+
+/// *(internal)* An empty reference stays empty.
+unsafe impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> ConstParts<'a> for Ref<'b, Target> {
+    type ConstRef = Ref<'a, Target>;
+}
+
+/// *(internal)* Constant parts stay constant.
+unsafe impl<'a, SomePart, Reference> ConstParts<'a> for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: ConstParts<'a>,
+    Reference::Target: HasPart<SomePart>,
+{
+    type ConstRef = Const<SomePart, Reference::ConstRef>;
+}
+
+/// *(internal)* Mutable parts become constant.
+unsafe impl<'a, SomePart, Reference> ConstParts<'a> for Mut<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: ConstParts<'a>,
+    Reference::Target: HasPart<SomePart>,
+{
+    type ConstRef = Const<SomePart, Reference::ConstRef>;
+}
+
+/// *(internal)* Separate the constant parts of a partial reference from the mutable parts.
+pub unsafe trait SplitConst<'a>: PartialRef<'a> {
+    /// The partial reference having the constant parts of `Self`.
+    type ConstRef: PartialRef<'a, Target = Self::Target> + ConstOnly;
+    /// The partial reference having the mutable parts of `Self`.
+    type MutRef: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* An empty reference splits into two empty references.
+unsafe impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> SplitConst<'a> for Ref<'b, Target> {
+    type ConstRef = Ref<'a, Target>;
+    type MutRef = Ref<'a, Target>;
+}
+
+/// *(internal)* Constant parts go to the constant reference.
+unsafe impl<'a, SomePart, Reference> SplitConst<'a> for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: SplitConst<'a>,
+    Reference::Target: HasPart<SomePart>,
+{
+    type ConstRef = Const<SomePart, Reference::ConstRef>;
+    type MutRef = Reference::MutRef;
+}
+
+/// *(internal)* Mutable parts go to the mutable reference.
+unsafe impl<'a, SomePart, Reference> SplitConst<'a> for Mut<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: SplitConst<'a>,
+    Reference::Target: HasPart<SomePart>,
+{
+    type ConstRef = Reference::ConstRef;
+    type MutRef = Mut<SomePart, Reference::MutRef>;
+}
+
+/// A mutably borrowed partial reference with all parts downgraded to constant parts.
+///
+/// Returned by [`downgrade_scope`](PartialRef::downgrade_scope). Dereferences to the constant
+/// partial reference, which can be re-borrowed or copied out of the guard to pass it on.
+pub struct DowngradeGuard<'a, Reference: ConstParts<'a>> {
+    reference: Reference::ConstRef,
+    phantom: PhantomData<&'a mut Reference>,
+}
+
+impl<'a, Reference: ConstParts<'a>> Deref for DowngradeGuard<'a, Reference> {
+    type Target = Reference::ConstRef;
+
+    #[inline(always)]
+    fn deref(&self) -> &Reference::ConstRef {
+        &self.reference
+    }
+}
+
+impl<'a, Reference: ConstParts<'a>> DerefMut for DowngradeGuard<'a, Reference> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Reference::ConstRef {
+        &mut self.reference
+    }
+}
+
+/// A constant partial reference that can be shared between threads.
+///
+/// Returned by [`freeze`](PartialRef::freeze). Dereferences to the constant partial reference. It
+/// implements [`Send`] and [`Sync`] when the fields of all parts can be shared between threads, see
+/// [`SyncParts`].
+#[derive(Clone, Copy)]
+pub struct Frozen<Reference: ConstOnly> {
+    reference: Reference,
+}
+
+impl<Reference: ConstOnly> Frozen<Reference> {
+    /// Return the wrapped constant partial reference.
+    #[inline(always)]
+    pub fn into_inner(self) -> Reference {
+        self.reference
+    }
+}
+
+impl<Reference: ConstOnly> Deref for Frozen<Reference> {
+    type Target = Reference;
+
+    #[inline(always)]
+    fn deref(&self) -> &Reference {
+        &self.reference
+    }
+}
+
+impl<Reference: ConstOnly> DerefMut for Frozen<Reference> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Reference {
+        &mut self.reference
+    }
+}
+
+unsafe impl<Reference: ConstOnly + SyncParts> Send for Frozen<Reference> {}
+
+unsafe impl<Reference: ConstOnly + SyncParts> Sync for Frozen<Reference> {}
+
+/// *(internal)* Asserts that the fields of all parts of a constant partial reference implement
+/// [`Sync`].
+pub unsafe trait SyncParts {}
+
+/// *(internal)* An empty reference doesn't access any fields.
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> SyncParts for Ref<'a, Target> {}
+
+/// *(internal)* A constant field part of a `Sync` type followed by other such parts.
+unsafe impl<SomePart, FieldType, Reference> SyncParts for Const<SomePart, Reference>
+where
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Sync + ?Sized,
+    Reference: HasTarget + SyncParts,
+{
+}
+
+// Formatting helpers
+
+/// *(internal)* Format the parts of a partial reference, used for its `Debug` implementation.
+pub trait DebugParts {
+    /// The name of the target type.
+    fn target_name() -> &'static str;
+
+    /// Format the parts in the order they are listed in [`partial!`], separated by commas.
+    ///
+    /// Returns whether any parts were formatted.
+    fn fmt_parts(f: &mut fmt::Formatter) -> Result<bool, fmt::Error>;
+
+    /// Format the partial reference as `PartialRef<Target> { mut PartA, PartB }`.
+    fn fmt_ref(f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PartialRef<{}> {{", short_type_name(Self::target_name()))?;
+        Self::fmt_parts(f)?;
+        f.write_str(" }")
+    }
+}
+
+/// Remove the module paths from a type name as returned by [`type_name`].
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    for (index, segment) in name.split("::").enumerate() {
+        if index > 0 {
+            // Drop the path segment preceding this separator.
+            let start = short
+                .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .map_or(0, |pos| pos + 1);
+            short.truncate(start);
+        }
+        short.push_str(segment);
+    }
+    short
+}
+
+/// Format the name of a part, preceded by a separator unless it is the first part.
+fn fmt_part<SomePart>(f: &mut fmt::Formatter, first: bool, prefix: &str) -> fmt::Result {
+    if !first {
+        f.write_str(",")?;
+    }
+    write!(f, " {}{}", prefix, short_type_name(type_name::<SomePart>()))
+}
+
+/// *(internal)* An empty reference has no parts to format.
+impl<'a, Target: PartialRefTarget + ?Sized> DebugParts for Ref<'a, Target> {
+    fn target_name() -> &'static str {
+        type_name::<Target>()
+    }
+
+    fn fmt_parts(_f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+        Ok(false)
+    }
+}
+
+/// *(internal)* Format the remaining parts followed by a constant part.
+impl<SomePart, Reference: DebugParts + HasTarget> DebugParts for Const<SomePart, Reference> {
+    fn target_name() -> &'static str {
+        Reference::target_name()
+    }
+
+    fn fmt_parts(f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+        // The outermost part is the last part listed in `partial!`.
+        let first = !Reference::fmt_parts(f)?;
+        fmt_part::<SomePart>(f, first, "")?;
+        Ok(true)
+    }
+}
+
+/// *(internal)* Format the remaining parts followed by a mutable part.
+impl<SomePart, Reference: DebugParts + HasTarget> DebugParts for Mut<SomePart, Reference> {
+    fn target_name() -> &'static str {
+        Reference::target_name()
+    }
+
+    fn fmt_parts(f: &mut fmt::Formatter) -> Result<bool, fmt::Error> {
+        // The outermost part is the last part listed in `partial!`.
+        let first = !Reference::fmt_parts(f)?;
+        fmt_part::<SomePart>(f, first, "mut ")?;
+        Ok(true)
+    }
+}
+
+impl<'a, Target: PartialRefTarget + ?Sized> fmt::Debug for Ref<'a, Target> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Self::fmt_ref(f)
+    }
+}
+
+impl<SomePart, Reference: DebugParts + HasTarget> fmt::Debug for Const<SomePart, Reference> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Self::fmt_ref(f)
+    }
+}
+
+impl<SomePart, Reference: DebugParts + HasTarget> fmt::Debug for Mut<SomePart, Reference> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Self::fmt_ref(f)
+    }
+}
+
+// Indexing helpers
+
+/// *(internal)* Access modes that allow shared access to a part.
+pub trait SharedAccess {}
+
+impl SharedAccess for AccessConst {}
+
+impl SharedAccess for AccessMut {}
+
+/// Shared access to a field part by indexing with the part, e.g. `g_ref[Colors]`, as with
+/// [`part`](PartialRef::part).
+///
+/// This requires all parts of the partial reference to be parts declared for the target, as listed
+/// by [`CanonicalTarget`], so it is not available for nested parts.
+impl<IndexPart, FieldType, SomePart, Reference> Index<IndexPart> for Const<SomePart, Reference>
+where
+    IndexPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+    Reference: HasTarget,
+    Reference::Target: HasPart<IndexPart>,
+    Self: PartAccess<IndexPart, Reference::Target>,
+    <Self as PartAccess<IndexPart, Reference::Target>>::Access: SharedAccess,
+{
+    type Output = FieldType;
+
+    #[inline(always)]
+    fn index(&self, _part: IndexPart) -> &FieldType {
+        unsafe { &*<Reference::Target as HasPart<IndexPart>>::part_ptr(self.ptr) }
+    }
+}
+
+/// Mutable access to a mutable field part by indexing with the part, e.g. `g_ref[Colors]`, as
+/// with [`part_mut`](PartialRef::part_mut).
+impl<IndexPart, FieldType, SomePart, Reference> IndexMut<IndexPart> for Const<SomePart, Reference>
+where
+    IndexPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+    Reference: HasTarget,
+    Reference::Target: HasPart<IndexPart>,
+    Self: PartAccess<IndexPart, Reference::Target, Access = AccessMut>,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, _part: IndexPart) -> &mut FieldType {
+        unsafe { &mut *<Reference::Target as HasPart<IndexPart>>::part_ptr_mut(self.ptr) }
+    }
+}
+
+/// Shared access to a field part by indexing with the part, e.g. `g_ref[Colors]`, as with
+/// [`part`](PartialRef::part).
+///
+/// This requires all parts of the partial reference to be parts declared for the target, as listed
+/// by [`CanonicalTarget`], so it is not available for nested parts.
+impl<IndexPart, FieldType, SomePart, Reference> Index<IndexPart> for Mut<SomePart, Reference>
+where
+    IndexPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+    Reference: HasTarget,
+    Reference::Target: HasPart<IndexPart>,
+    Self: PartAccess<IndexPart, Reference::Target>,
+    <Self as PartAccess<IndexPart, Reference::Target>>::Access: SharedAccess,
+{
+    type Output = FieldType;
+
+    #[inline(always)]
+    fn index(&self, _part: IndexPart) -> &FieldType {
+        unsafe { &*<Reference::Target as HasPart<IndexPart>>::part_ptr(self.ptr) }
+    }
+}
+
+/// Mutable access to a mutable field part by indexing with the part, e.g. `g_ref[Colors]`, as
+/// with [`part_mut`](PartialRef::part_mut).
+impl<IndexPart, FieldType, SomePart, Reference> IndexMut<IndexPart> for Mut<SomePart, Reference>
+where
+    IndexPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+    Reference: HasTarget,
+    Reference::Target: HasPart<IndexPart>,
+    Self: PartAccess<IndexPart, Reference::Target, Access = AccessMut>,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, _part: IndexPart) -> &mut FieldType {
+        unsafe { &mut *<Reference::Target as HasPart<IndexPart>>::part_ptr_mut(self.ptr) }
+    }
+}
+
+// Multiple parts helpers
+
+/// Selects mutable access to a part when passed to [`parts`](PartialRef::parts).
+#[derive(Copy, Clone, Default)]
+pub struct MutPart<FieldPartSpec>(pub FieldPartSpec);
+
+/// *(internal)* Access mode of parts that are not wrapped in [`MutPart`].
+pub struct AccessConst;
+
+/// *(internal)* Access mode of parts that are wrapped in [`MutPart`].
+pub struct AccessMut;
+
+/// *(internal)* A part passed to [`parts`](PartialRef::parts).
+///
+/// The `Mode` type can be inferred.
+pub unsafe trait AccessPart<'a, FieldPart, Mode, Reference: PartialRef<'a>> {
+    /// The partial reference `Reference` extended by the accessed part.
+    type Extended: PartialRef<'a, Target = Reference::Target>;
+    /// The reference to the part's field.
+    type Output;
+
+    /// Access the part's field using a raw pointer.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the extended reference.
+    unsafe fn access_part(
+        ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+    ) -> Self::Output;
+}
+
+unsafe impl<'a, FieldPartSpec, FieldPart, FieldType, Reference>
+    AccessPart<'a, FieldPart, AccessConst, Reference> for FieldPartSpec
+where
+    FieldType: ?Sized + 'a,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldPartSpec: PartSpec<FieldPart>,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<FieldPart> + 'a,
+{
+    type Extended = Const<FieldPart, Reference>;
+    type Output = &'a FieldType;
+
+    #[inline(always)]
+    unsafe fn access_part(
+        ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+    ) -> Self::Output {
+        <Const<FieldPart, Ref<'a, Reference::Target>> as PartialRef<'a>>::from_raw(ptr).get_part()
+    }
+}
+
+unsafe impl<'a, FieldPartSpec, FieldPart, FieldType, Reference>
+    AccessPart<'a, FieldPart, AccessMut, Reference> for MutPart<FieldPartSpec>
+where
+    FieldType: ?Sized + 'a,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldPartSpec: PartSpec<FieldPart>,
+    Reference: PartialRef<'a>,
+    Reference::Target: HasPart<FieldPart> + 'a,
+{
+    type Extended = Mut<FieldPart, Reference>;
+    type Output = &'a mut FieldType;
+
+    #[inline(always)]
+    unsafe fn access_part(
+        ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+    ) -> Self::Output {
+        <Mut<FieldPart, Ref<'a, Reference::Target>> as PartialRef<'a>>::from_raw(ptr).get_part_mut()
+    }
+}
+
+/// *(internal)* A tuple of parts passed to [`parts`](PartialRef::parts).
+///
+/// The `FieldParts` type can be inferred.
+pub unsafe trait PartsTuple<'a, Target: PartialRefTarget + ?Sized, FieldParts> {
+    /// The partial reference containing all accessed parts.
+    type Ref: PartialRef<'a, Target = Target>;
+    /// The tuple of references to the parts' fields.
+    type Output;
+
+    /// Access the parts' fields using a raw pointer.
+    ///
+    /// # Safety
+    /// The pointer must be valid for `Self::Ref`.
+    unsafe fn access_parts(ptr: *mut Target::RawTarget) -> Self::Output;
+}
+
+macro_rules! impl_parts_tuple {
+    ($(($Spec:ident, $Part:ident, $Mode:ident)),*) => {
+        impl_parts_tuple!(@impl [] Ref<'a, Target>; $(($Spec, $Part, $Mode))*);
+    };
+    (
+        @impl [$(($Spec:ident, $Part:ident, $Mode:ident, $Reference:ty))*] $Extended:ty;
+    ) => {
+        unsafe impl<'a, Target, $($Spec, $Part, $Mode),*>
+            PartsTuple<'a, Target, ($(($Part, $Mode),)*)> for ($($Spec,)*)
+        where
+            Target: PartialRefTarget + ?Sized + 'a,
+            $($Spec: AccessPart<'a, $Part, $Mode, $Reference>,)*
+        {
+            type Ref = $Extended;
+            type Output = ($(<$Spec as AccessPart<'a, $Part, $Mode, $Reference>>::Output,)*);
+
+            #[inline(always)]
+            unsafe fn access_parts(ptr: *mut Target::RawTarget) -> Self::Output {
+                ($(<$Spec as AccessPart<'a, $Part, $Mode, $Reference>>::access_part(ptr),)*)
+            }
+        }
+    };
+    (
+        @impl [$($done:tt)*] $Reference:ty;
+        ($Spec:ident, $Part:ident, $Mode:ident) $($rest:tt)*
+    ) => {
+        impl_parts_tuple!(
+            @impl [$($done)* ($Spec, $Part, $Mode, $Reference)]
+            <$Spec as AccessPart<'a, $Part, $Mode, $Reference>>::Extended;
+            $($rest)*
+        );
+    };
+}
+
+impl_parts_tuple!((S1, P1, M1));
+impl_parts_tuple!((S1, P1, M1), (S2, P2, M2));
+impl_parts_tuple!((S1, P1, M1), (S2, P2, M2), (S3, P3, M3));
+impl_parts_tuple!((S1, P1, M1), (S2, P2, M2), (S3, P3, M3), (S4, P4, M4));
+impl_parts_tuple!(
+    (S1, P1, M1),
+    (S2, P2, M2),
+    (S3, P3, M3),
+    (S4, P4, M4),
+    (S5, P5, M5)
+);
+impl_parts_tuple!(
+    (S1, P1, M1),
+    (S2, P2, M2),
+    (S3, P3, M3),
+    (S4, P4, M4),
+    (S5, P5, M5),
+    (S6, P6, M6)
+);
+impl_parts_tuple!(
+    (S1, P1, M1),
+    (S2, P2, M2),
+    (S3, P3, M3),
+    (S4, P4, M4),
+    (S5, P5, M5),
+    (S6, P6, M6),
+    (S7, P7, M7)
+);
+impl_parts_tuple!(
+    (S1, P1, M1),
+    (S2, P2, M2),
+    (S3, P3, M3),
+    (S4, P4, M4),
+    (S5, P5, M5),
+    (S6, P6, M6),
+    (S7, P7, M7),
+    (S8, P8, M8)
+);
+
+// Splitting helpers
+
+/// *(internal)* A field type that can be accessed as a mutable slice, used by
+/// [`split_slice_part_at`](PartialRef::split_slice_part_at),
+/// [`get_disjoint_part_mut`](PartialRef::get_disjoint_part_mut), [`RangePart`] and
+/// [`ColumnPart`].
+pub trait SliceField {
+    /// The type of the slice's elements.
+    type Element;
+
+    /// Access the field as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [Self::Element];
+
+    /// Given a pointer to the field, produce a pointer to the elements `start..end`.
+    ///
+    /// # Safety
+    /// The pointer must be valid and the range must be within the field's length. This doesn't
+    /// create a reference to the field's elements.
+    unsafe fn range_ptr(ptr: *const Self, start: usize, end: usize) -> *const [Self::Element];
+
+    /// Given a mutable pointer to the field, produce a mutable pointer to the elements
+    /// `start..end`.
+    ///
+    /// # Safety
+    /// The pointer must be valid and the range must be within the field's length. This doesn't
+    /// create a reference to the field's elements.
+    unsafe fn range_ptr_mut(ptr: *mut Self, start: usize, end: usize) -> *mut [Self::Element];
+
+    /// Given a pointer to the field, return its length.
+    ///
+    /// # Safety
+    /// The pointer must be valid. This doesn't create a reference to the field's elements.
+    unsafe fn len_ptr(ptr: *const Self) -> usize;
+}
+
+impl<T> SliceField for [T] {
+    type Element = T;
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr(ptr: *const [T], start: usize, end: usize) -> *const [T] {
+        ptr::slice_from_raw_parts((ptr as *const T).add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr_mut(ptr: *mut [T], start: usize, end: usize) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut((ptr as *mut T).add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn len_ptr(ptr: *const [T]) -> usize {
+        ptr.len()
+    }
+}
+
+impl<T, const N: usize> SliceField for [T; N] {
+    type Element = T;
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr(ptr: *const [T; N], start: usize, end: usize) -> *const [T] {
+        ptr::slice_from_raw_parts((ptr as *const T).add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr_mut(ptr: *mut [T; N], start: usize, end: usize) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut((ptr as *mut T).add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn len_ptr(_ptr: *const [T; N]) -> usize {
+        N
+    }
+}
+
+impl<T> SliceField for Vec<T> {
+    type Element = T;
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr(ptr: *const Vec<T>, start: usize, end: usize) -> *const [T] {
+        ptr::slice_from_raw_parts((*ptr).as_ptr().add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn range_ptr_mut(ptr: *mut Vec<T>, start: usize, end: usize) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut((*ptr).as_mut_ptr().add(start), end - start)
+    }
+
+    #[inline(always)]
+    unsafe fn len_ptr(ptr: *const Vec<T>) -> usize {
+        (*ptr).len()
+    }
+}
+
+/// Error returned when mutably accessing several elements of a part fails.
 ///
-/// This has an implementation for mutable references that implement [`IntoPartialRefMut`]. It
-/// performs the same operation as [`IntoPartialRefMut`] but is only implemented for mutable
-/// references. This is useful as it allows writing `value.into_partial_ref_mut()` instead of `(&mut
-/// value).into_partial_ref_mut()` using auto referencing of method calls. Using just
-/// [`value.into_partial_ref()`] would result in an immutable reference.
-pub trait IntoPartialRefMut<'a>: IntoPartialRef<'a> {
-    /// Convert a mutable reference into a partial reference.
-    fn into_partial_ref_mut(self) -> Self::Ref;
+/// This is returned by [`get_disjoint_part_mut`](PartialRef::get_disjoint_part_mut),
+/// [`split_chunks_part_mut`](PartialRef::split_chunks_part_mut) and
+/// [`get_disjoint_keys_part_mut`](PartialRef::get_disjoint_keys_part_mut).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisjointMutError {
+    /// An index or range is out of bounds, or a key is missing.
+    IndexOutOfBounds,
+    /// Two indices, ranges or keys overlap.
+    OverlappingIndices,
 }
 
-impl<'a, T> IntoPartialRefMut<'a> for &'a mut T
+impl fmt::Display for DisjointMutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DisjointMutError::IndexOutOfBounds => "an index is out of bounds",
+            DisjointMutError::OverlappingIndices => "there were overlapping indices",
+        })
+    }
+}
+
+impl std::error::Error for DisjointMutError {}
+
+/// Mutable access to several distinct elements of a slice, used by
+/// [`get_disjoint_part_mut`](PartialRef::get_disjoint_part_mut).
+fn get_disjoint_mut<T, const N: usize>(
+    slice: &mut [T],
+    indices: [usize; N],
+) -> Result<[&mut T; N], DisjointMutError> {
+    for (position, &index) in indices.iter().enumerate() {
+        if index >= slice.len() {
+            return Err(DisjointMutError::IndexOutOfBounds);
+        }
+        if indices[..position].contains(&index) {
+            return Err(DisjointMutError::OverlappingIndices);
+        }
+    }
+
+    let ptr = slice.as_mut_ptr();
+    Ok(indices.map(|index| unsafe { &mut *ptr.add(index) }))
+}
+
+/// Split a slice into disjoint chunks, used by
+/// [`split_chunks_part_mut`](PartialRef::split_chunks_part_mut).
+fn split_chunks<'a, T>(
+    slice: &'a mut [T],
+    ranges: &[Range<usize>],
+) -> Result<Vec<&'a mut [T]>, DisjointMutError> {
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_unstable_by_key(|&index| ranges[index].start);
+
+    let mut end = 0;
+    for &index in &order {
+        let range = &ranges[index];
+        if range.start > range.end || range.end > slice.len() {
+            return Err(DisjointMutError::IndexOutOfBounds);
+        }
+        if range.start < end {
+            return Err(DisjointMutError::OverlappingIndices);
+        }
+        end = range.end;
+    }
+
+    let ptr = slice.as_mut_ptr();
+    Ok(ranges
+        .iter()
+        .map(|range| unsafe {
+            std::slice::from_raw_parts_mut(ptr.add(range.start), range.end - range.start)
+        })
+        .collect())
+}
+
+/// The part of a chunk of a slice part, selected at runtime.
+///
+/// Chunks are returned by [`split_chunks_part_mut`](PartialRef::split_chunks_part_mut). No target
+/// has this part, instead a chunk is passed along with a partial reference using [`Extended`], e.g.
+/// `rest.extend_with::<Chunk<Jobs>>(chunk)`, and accessed using
+/// [`extra_part_mut`](Extended::extra_part_mut).
+pub struct Chunk<FieldPart>(PhantomData<FieldPart>);
+
+impl<FieldPart> Default for Chunk<FieldPart> {
+    fn default() -> Self {
+        Chunk(PhantomData)
+    }
+}
+
+impl<FieldPart, FieldType> Part for Chunk<FieldPart>
 where
-    Self: IntoPartialRef<'a>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: SliceField + ?Sized,
 {
-    fn into_partial_ref_mut(self) -> Self::Ref {
-        self.into_partial_ref()
+    type PartType = Field<[FieldType::Element]>;
+}
+
+impl<FieldPart, FieldPartS> PartSpec<Chunk<FieldPartS>> for Chunk<FieldPart> where
+    FieldPart: PartSpec<FieldPartS>
+{
+}
+
+impl<FieldPart> NormalizeNestedPart for Chunk<FieldPart> {
+    type Normalized = Chunk<FieldPart>;
+}
+
+impl<NestedPrefix, FieldPart> AppendNestedPart<NestedPrefix> for Chunk<FieldPart> {
+    type Output = Nested<NestedPrefix, Chunk<FieldPart>>;
+}
+
+/// *(internal)* A map field, used by
+/// [`get_disjoint_keys_part_mut`](PartialRef::get_disjoint_keys_part_mut) and [`KeyPart`].
+pub trait MapField {
+    /// The type of the map's keys.
+    type Key: Eq;
+    /// The type of the map's values.
+    type Value;
+
+    /// Mutable access to the values for several keys, `None` for missing keys.
+    ///
+    /// The keys have to be distinct, implementations may panic otherwise.
+    fn get_disjoint_values_mut<const N: usize>(
+        &mut self,
+        keys: [&Self::Key; N],
+    ) -> [Option<&mut Self::Value>; N];
+}
+
+impl<K, V, S> MapField for HashMap<K, V, S>
+where
+    K: Eq + hash::Hash,
+    S: hash::BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get_disjoint_values_mut<const N: usize>(&mut self, keys: [&K; N]) -> [Option<&mut V>; N] {
+        // Looking up the keys one by one would invalidate the references to the values found
+        // earlier, so this iterates over the map once.
+        let mut values = std::array::from_fn(|_| None);
+        let mut missing = N;
+        for (key, value) in self.iter_mut() {
+            if missing == 0 {
+                break;
+            }
+            if let Some(index) = keys.iter().position(|&wanted| wanted == key) {
+                values[index] = Some(value);
+                missing -= 1;
+            }
+        }
+        values
     }
 }
 
-/// *(internal)* Split a part into nested parts.
+impl<K: Ord, V> MapField for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get_disjoint_values_mut<const N: usize>(&mut self, keys: [&K; N]) -> [Option<&mut V>; N] {
+        let mut values = std::array::from_fn(|_| None);
+        let mut order: [usize; N] = std::array::from_fn(|index| index);
+        order.sort_unstable_by_key(|&index| keys[index]);
+
+        let (Some(&first), Some(&last)) = (order.first(), order.last()) else {
+            return values;
+        };
+        let mut pending = order.iter().peekable();
+        for (key, value) in self.range_mut(keys[first]..=keys[last]) {
+            while pending.next_if(|&&index| keys[index] < key).is_some() {}
+            if let Some(&index) = pending.next_if(|&&index| keys[index] == key) {
+                values[index] = Some(value);
+            }
+        }
+        values
+    }
+}
+
+/// The part of the value for a single key of a map part, selected at runtime.
 ///
-/// This is used to implement splitting of nested parts.
-pub unsafe trait SplitIntoParts<'a, ContainingPart, Reference: PartialRef<'a>> {
-    /// A partial reference that has all the parts `Reference` and all parts of `Self` nested in
-    /// `ContainingPart` as constant parts.
-    type Result: PartialRef<'a, Target = Reference::Target>;
-    /// A partial reference that has all the parts `Reference` and all parts of `Self` nested in
-    /// `ContainingPart` as mutable parts.
-    type ResultMut: PartialRef<'a, Target = Reference::Target>;
+/// Values are returned by
+/// [`get_disjoint_keys_part_mut`](PartialRef::get_disjoint_keys_part_mut). No target has this part,
+/// instead a value is passed along with a partial reference using [`Extended`], e.g.
+/// `rest.extend_with::<KeyPart<Entities>>(entity)`, and accessed using
+/// [`extra_part_mut`](Extended::extra_part_mut).
+pub struct KeyPart<FieldPart>(PhantomData<FieldPart>);
+
+impl<FieldPart> Default for KeyPart<FieldPart> {
+    fn default() -> Self {
+        KeyPart(PhantomData)
+    }
 }
 
-/// An empty partial reference borrowing no parts.
+impl<FieldPart, FieldType> Part for KeyPart<FieldPart>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: MapField + ?Sized,
+{
+    type PartType = Field<FieldType::Value>;
+}
+
+impl<FieldPart, FieldPartS> PartSpec<KeyPart<FieldPartS>> for KeyPart<FieldPart> where
+    FieldPart: PartSpec<FieldPartS>
+{
+}
+
+impl<FieldPart> NormalizeNestedPart for KeyPart<FieldPart> {
+    type Normalized = KeyPart<FieldPart>;
+}
+
+impl<NestedPrefix, FieldPart> AppendNestedPart<NestedPrefix> for KeyPart<FieldPart> {
+    type Output = Nested<NestedPrefix, KeyPart<FieldPart>>;
+}
+
+/// A part for the elements `START..END` of a slice, `Vec` or array field.
 ///
-/// Partial references with a non-empty set of borrowed parts are built by nesting this type within
-/// the [`Mut`] and [`Const`] types.
-#[repr(transparent)]
-pub struct Ref<'a, Target: PartialRefTarget + ?Sized> {
-    ptr: *mut Target::RawTarget,
-    phantom: PhantomData<&'a mut Target>,
+/// The field is given by the part `FieldPart`, see [`SliceField`] for the supported field types.
+/// Range parts for different ranges are different parts, so a partial reference can have several
+/// mutable range parts of the same field, e.g. `partial!(Mixer, mut RangePart<Samples, 0, 512>, mut
+/// RangePart<Samples, 512, 1024>)`. Such a partial reference is created from the field part using
+/// [`split_range_parts_mut`](PartialRef::split_range_parts_mut), which checks that the ranges are
+/// disjoint and within the field's length.
+pub struct RangePart<FieldPart, const START: usize, const END: usize>(PhantomData<FieldPart>);
+
+impl<FieldPart, const START: usize, const END: usize> Default for RangePart<FieldPart, START, END> {
+    fn default() -> Self {
+        RangePart(PhantomData)
+    }
 }
 
-impl<'a, Target: PartialRefTarget + ?Sized> HasTarget for Ref<'a, Target> {
-    type Target = Target;
+impl<FieldPart, FieldType, const START: usize, const END: usize> Part
+    for RangePart<FieldPart, START, END>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: SliceField + ?Sized,
+{
+    type PartType = Field<[FieldType::Element]>;
 }
 
-/// An empty reference to a valid target is a valid reference.
-impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> PartialRef<'a> for Ref<'b, Target> {
+impl<FieldPart, FieldPartS, const START: usize, const END: usize>
+    PartSpec<RangePart<FieldPartS, START, END>> for RangePart<FieldPart, START, END>
+where
+    FieldPart: PartSpec<FieldPartS>,
+{
+}
+
+impl<FieldPart, const START: usize, const END: usize> NormalizeNestedPart
+    for RangePart<FieldPart, START, END>
+{
+    type Normalized = RangePart<FieldPart, START, END>;
+}
+
+impl<NestedPrefix, FieldPart, const START: usize, const END: usize> AppendNestedPart<NestedPrefix>
+    for RangePart<FieldPart, START, END>
+{
+    type Output = Nested<NestedPrefix, RangePart<FieldPart, START, END>>;
+}
+
+/// A target has the range parts of its slice fields.
+impl<Target, FieldPart, FieldType, const START: usize, const END: usize>
+    HasPart<RangePart<FieldPart, START, END>> for Target
+where
+    Target: HasPart<FieldPart> + ?Sized,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: SliceField + ?Sized,
+{
     #[inline(always)]
-    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
-        Ref {
-            ptr,
-            phantom: PhantomData,
-        }
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> *const [FieldType::Element] {
+        FieldType::range_ptr(<Self as HasPart<FieldPart>>::part_ptr(ptr), START, END)
     }
 
     #[inline(always)]
-    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
-        self.ptr
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> *mut [FieldType::Element] {
+        FieldType::range_ptr_mut(<Self as HasPart<FieldPart>>::part_ptr_mut(ptr), START, END)
     }
 }
 
-/// An empty reference contains no mutable parts and thus is safe to clone.
-impl<'a, Target: PartialRefTarget> Copy for Ref<'a, Target> {}
+/// A part for the element at `INDEX` of a slice, `Vec` or array field.
+///
+/// This is like a [`RangePart`] for the range `INDEX..INDEX + 1`, but refers to the element instead
+/// of a slice containing it. Element parts for different indices are different parts, so a partial
+/// reference can have several mutable element parts of the same field, e.g. `partial!(Ledger, mut
+/// ElemPart<Banks, 0>, mut ElemPart<Banks, 3>)`. Such a partial reference is created from the field
+/// part using [`split_range_parts_mut`](PartialRef::split_range_parts_mut), which accepts element
+/// parts as well as range parts. As `FieldPart` can be a nested part, this also works for the
+/// fields of nested targets, e.g. `ElemPart<Nested<Vault, Banks>, 0>`.
+///
+/// For array fields of a derived target, `#[part(elements(PartName))]` declares element parts
+/// without needing a split.
+pub struct ElemPart<FieldPart, const INDEX: usize>(PhantomData<FieldPart>);
 
-/// An empty reference contains no mutable parts and thus is safe to clone.
-impl<'a, Target: PartialRefTarget> Clone for Ref<'a, Target> {
+impl<FieldPart, const INDEX: usize> Default for ElemPart<FieldPart, INDEX> {
+    fn default() -> Self {
+        ElemPart(PhantomData)
+    }
+}
+
+impl<FieldPart, FieldType, const INDEX: usize> Part for ElemPart<FieldPart, INDEX>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: SliceField + ?Sized,
+{
+    type PartType = Field<FieldType::Element>;
+}
+
+impl<FieldPart, FieldPartS, const INDEX: usize> PartSpec<ElemPart<FieldPartS, INDEX>>
+    for ElemPart<FieldPart, INDEX>
+where
+    FieldPart: PartSpec<FieldPartS>,
+{
+}
+
+impl<FieldPart, const INDEX: usize> NormalizeNestedPart for ElemPart<FieldPart, INDEX> {
+    type Normalized = ElemPart<FieldPart, INDEX>;
+}
+
+impl<NestedPrefix, FieldPart, const INDEX: usize> AppendNestedPart<NestedPrefix>
+    for ElemPart<FieldPart, INDEX>
+{
+    type Output = Nested<NestedPrefix, ElemPart<FieldPart, INDEX>>;
+}
+
+/// A target has the element parts of its slice fields.
+impl<Target, FieldPart, FieldType, const INDEX: usize> HasPart<ElemPart<FieldPart, INDEX>>
+    for Target
+where
+    Target: HasPart<FieldPart> + ?Sized,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: SliceField + ?Sized,
+{
     #[inline(always)]
-    fn clone(&self) -> Self {
-        *self
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> *const FieldType::Element {
+        let field = <Self as HasPart<FieldPart>>::part_ptr(ptr);
+        FieldType::range_ptr(field, INDEX, INDEX + 1) as *const FieldType::Element
+    }
+
+    #[inline(always)]
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> *mut FieldType::Element {
+        let field = <Self as HasPart<FieldPart>>::part_ptr_mut(ptr);
+        FieldType::range_ptr_mut(field, INDEX, INDEX + 1) as *mut FieldType::Element
     }
 }
 
-/// A mutable part of a partial reference.
-#[repr(transparent)]
-pub struct Mut<Part, Reference: HasTarget> {
-    ptr: *mut <<Reference as HasTarget>::Target as PartialRefTarget>::RawTarget,
-    phantom: PhantomData<(Reference, Part)>,
+/// *(internal)* A partial reference having only range parts and element parts of the field part
+/// `FieldPart`, listed in increasing order of their ranges, used by
+/// [`split_range_parts_mut`](PartialRef::split_range_parts_mut).
+pub unsafe trait RangePartList<FieldPart>: HasTarget {
+    /// The end of the last range.
+    ///
+    /// Evaluating this fails to compile if the ranges overlap or are not listed in increasing
+    /// order.
+    const END: usize;
 }
 
-impl<'a, SomePart: Part, Reference: PartialRef<'a>> HasTarget for Mut<SomePart, Reference>
+/// *(internal)* An empty reference has no ranges.
+unsafe impl<'a, Target, FieldPart> RangePartList<FieldPart> for Ref<'a, Target>
 where
-    Reference::Target: HasPart<SomePart>,
+    Target: PartialRefTarget + ?Sized,
 {
-    type Target = Reference::Target;
+    const END: usize = 0;
 }
 
-/// Extending a valid reference by a mutable part is still a valid reference when the reference
-/// target has such a part.
-impl<'a, SomePart: Part, Reference: PartialRef<'a>> PartialRef<'a> for Mut<SomePart, Reference>
+/// *(internal)* A constant range part has to follow the remaining ranges.
+unsafe impl<'a, FieldPart, Reference, const START: usize, const END: usize> RangePartList<FieldPart>
+    for Const<RangePart<FieldPart, START, END>, Reference>
 where
-    Reference::Target: HasPart<SomePart>,
+    RangePart<FieldPart, START, END>: Part,
+    Reference: PartialRef<'a> + RangePartList<FieldPart>,
+    Reference::Target: HasPart<RangePart<FieldPart, START, END>>,
+{
+    const END: usize = {
+        assert!(
+            Reference::END <= START && START <= END,
+            "range parts have to be disjoint and listed in increasing order"
+        );
+        END
+    };
+}
+
+/// *(internal)* A mutable range part has to follow the remaining ranges.
+unsafe impl<'a, FieldPart, Reference, const START: usize, const END: usize> RangePartList<FieldPart>
+    for Mut<RangePart<FieldPart, START, END>, Reference>
+where
+    RangePart<FieldPart, START, END>: Part,
+    Reference: PartialRef<'a> + RangePartList<FieldPart>,
+    Reference::Target: HasPart<RangePart<FieldPart, START, END>>,
+{
+    const END: usize = {
+        assert!(
+            Reference::END <= START && START <= END,
+            "range parts have to be disjoint and listed in increasing order"
+        );
+        END
+    };
+}
+
+/// *(internal)* A constant element part has to follow the remaining ranges.
+unsafe impl<'a, FieldPart, Reference, const INDEX: usize> RangePartList<FieldPart>
+    for Const<ElemPart<FieldPart, INDEX>, Reference>
+where
+    ElemPart<FieldPart, INDEX>: Part,
+    Reference: PartialRef<'a> + RangePartList<FieldPart>,
+    Reference::Target: HasPart<ElemPart<FieldPart, INDEX>>,
+{
+    const END: usize = {
+        assert!(
+            Reference::END <= INDEX,
+            "range parts have to be disjoint and listed in increasing order"
+        );
+        INDEX + 1
+    };
+}
+
+/// *(internal)* A mutable element part has to follow the remaining ranges.
+unsafe impl<'a, FieldPart, Reference, const INDEX: usize> RangePartList<FieldPart>
+    for Mut<ElemPart<FieldPart, INDEX>, Reference>
+where
+    ElemPart<FieldPart, INDEX>: Part,
+    Reference: PartialRef<'a> + RangePartList<FieldPart>,
+    Reference::Target: HasPart<ElemPart<FieldPart, INDEX>>,
+{
+    const END: usize = {
+        assert!(
+            Reference::END <= INDEX,
+            "range parts have to be disjoint and listed in increasing order"
+        );
+        INDEX + 1
+    };
+}
+
+/// An unsigned integer field type that can be split into [`BitPart`]s.
+pub trait BitsField: Copy {
+    /// The width of the integer in bits.
+    const BITS: u32;
+
+    /// Extract the bits `low..high`, shifted to the lowest bits.
+    ///
+    /// This requires `low < high <= Self::BITS`.
+    fn get_bits(self, low: u32, high: u32) -> Self;
+
+    /// Replace the bits `low..high` with the lowest bits of `value`.
+    ///
+    /// This requires `low < high <= Self::BITS`.
+    fn set_bits(self, low: u32, high: u32, value: Self) -> Self;
+}
+
+macro_rules! impl_bits_field {
+    ($($int:ty),*) => {
+        $(
+            impl BitsField for $int {
+                const BITS: u32 = <$int>::BITS;
+
+                #[inline(always)]
+                fn get_bits(self, low: u32, high: u32) -> $int {
+                    (self >> low) & (<$int>::MAX >> (Self::BITS - (high - low)))
+                }
+
+                #[inline(always)]
+                fn set_bits(self, low: u32, high: u32, value: $int) -> $int {
+                    let mask = (<$int>::MAX >> (Self::BITS - (high - low))) << low;
+                    (self & !mask) | ((value << low) & mask)
+                }
+            }
+        )*
+    };
+}
+
+impl_bits_field!(u8, u16, u32, u64, u128, usize);
+
+/// A part for the bits `LOW..HIGH` of an unsigned integer field.
+///
+/// The field is given by the part `FieldPart`, see [`BitsField`] for the supported field types.
+/// Bit parts for different bit ranges are different parts, so a partial reference can have several
+/// mutable bit parts of the same field, e.g. `partial!(Device, mut BitPart<Control, 0, 1>, mut
+/// BitPart<Control, 4, 8>)`. Such a partial reference is created from the field part using
+/// [`split_bit_parts_mut`](PartialRef::split_bit_parts_mut), which checks that the bit ranges are
+/// disjoint and within the field's width.
+///
+/// As the bits of a field can't be referenced individually, bit parts are accessed using
+/// [`bit_part`](PartialRef::bit_part) and [`bit_part_mut`](PartialRef::bit_part_mut).
+pub struct BitPart<FieldPart, const LOW: u32, const HIGH: u32>(PhantomData<FieldPart>);
+
+impl<FieldPart, const LOW: u32, const HIGH: u32> Default for BitPart<FieldPart, LOW, HIGH> {
+    fn default() -> Self {
+        BitPart(PhantomData)
+    }
+}
+
+impl<FieldPart, FieldType, const LOW: u32, const HIGH: u32> Part for BitPart<FieldPart, LOW, HIGH>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: BitsField,
+{
+    type PartType = BitField<FieldType, LOW, HIGH>;
+}
+
+impl<FieldPart, FieldPartS, const LOW: u32, const HIGH: u32>
+    PartSpec<BitPart<FieldPartS, LOW, HIGH>> for BitPart<FieldPart, LOW, HIGH>
+where
+    FieldPart: PartSpec<FieldPartS>,
+{
+}
+
+impl<FieldPart, const LOW: u32, const HIGH: u32> NormalizeNestedPart
+    for BitPart<FieldPart, LOW, HIGH>
+{
+    type Normalized = BitPart<FieldPart, LOW, HIGH>;
+}
+
+impl<NestedPrefix, FieldPart, const LOW: u32, const HIGH: u32> AppendNestedPart<NestedPrefix>
+    for BitPart<FieldPart, LOW, HIGH>
+{
+    type Output = Nested<NestedPrefix, BitPart<FieldPart, LOW, HIGH>>;
+}
+
+/// A target has the bit parts of its integer fields.
+impl<Target, FieldPart, FieldType, const LOW: u32, const HIGH: u32>
+    HasPart<BitPart<FieldPart, LOW, HIGH>> for Target
+where
+    Target: HasPart<FieldPart> + ?Sized,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: BitsField,
 {
     #[inline(always)]
-    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
-        Mut {
-            ptr,
-            phantom: PhantomData,
-        }
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> *const FieldType {
+        <Self as HasPart<FieldPart>>::part_ptr(ptr)
     }
 
     #[inline(always)]
-    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
-        self.ptr
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> *mut FieldType {
+        <Self as HasPart<FieldPart>>::part_ptr_mut(ptr)
     }
 }
 
-/// A constant (immutable) part of a partial reference.
-#[repr(transparent)]
-pub struct Const<Part, Reference: HasTarget> {
-    ptr: *mut <<Reference as HasTarget>::Target as PartialRefTarget>::RawTarget,
-    phantom: PhantomData<(Reference, Part)>,
+/// *(internal)* A partial reference having only bit parts of the field part `FieldPart`, listed in
+/// increasing order of their bit ranges, used by
+/// [`split_bit_parts_mut`](PartialRef::split_bit_parts_mut).
+pub unsafe trait BitPartList<FieldPart>: HasTarget {
+    /// The end of the last bit range.
+    ///
+    /// Evaluating this fails to compile if the bit ranges are empty, overlap, are not listed in
+    /// increasing order or exceed the width of the field.
+    const END: u32;
 }
 
-impl<'a, SomePart: Part, Reference: PartialRef<'a>> HasTarget for Const<SomePart, Reference>
+/// *(internal)* An empty reference has no bit ranges.
+unsafe impl<'a, Target, FieldPart> BitPartList<FieldPart> for Ref<'a, Target>
 where
-    Reference::Target: HasPart<SomePart>,
+    Target: PartialRefTarget + ?Sized,
+{
+    const END: u32 = 0;
+}
+
+/// *(internal)* A constant bit part has to follow the remaining bit ranges.
+unsafe impl<'a, FieldPart, FieldType, Reference, const LOW: u32, const HIGH: u32>
+    BitPartList<FieldPart> for Const<BitPart<FieldPart, LOW, HIGH>, Reference>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: BitsField,
+    Reference: PartialRef<'a> + BitPartList<FieldPart>,
+    Reference::Target: HasPart<BitPart<FieldPart, LOW, HIGH>>,
+{
+    const END: u32 = {
+        assert!(
+            Reference::END <= LOW && LOW < HIGH && HIGH <= FieldType::BITS,
+            "bit parts have to be non-empty, disjoint, within the field and listed in increasing order"
+        );
+        HIGH
+    };
+}
+
+/// *(internal)* A mutable bit part has to follow the remaining bit ranges.
+unsafe impl<'a, FieldPart, FieldType, Reference, const LOW: u32, const HIGH: u32>
+    BitPartList<FieldPart> for Mut<BitPart<FieldPart, LOW, HIGH>, Reference>
+where
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: BitsField,
+    Reference: PartialRef<'a> + BitPartList<FieldPart>,
+    Reference::Target: HasPart<BitPart<FieldPart, LOW, HIGH>>,
+{
+    const END: u32 = {
+        assert!(
+            Reference::END <= LOW && LOW < HIGH && HIGH <= FieldType::BITS,
+            "bit parts have to be non-empty, disjoint, within the field and listed in increasing order"
+        );
+        HIGH
+    };
+}
+
+/// A part for one field of every row of a slice, `Vec` or array field of rows.
+///
+/// The rows are given by the part `RowsPart`, see [`SliceField`] for the supported field types.
+/// The rows have to be a [`PartialRefTarget`] and the column is given by the field part
+/// `FieldPart` of the rows, which can be a nested part. Column parts for different fields are
+/// different parts, so a partial reference can have several mutable columns of the same rows,
+/// e.g. `partial!(Particles, mut ColumnPart<Rows, Position>, mut ColumnPart<Rows, Velocity>)`.
+/// Such a partial reference is created from the rows' part using
+/// [`split_column_parts_mut`](PartialRef::split_column_parts_mut), which checks that the columns
+/// are disjoint.
+pub struct ColumnPart<RowsPart, FieldPart>(PhantomData<(RowsPart, FieldPart)>);
+
+impl<RowsPart, FieldPart> Default for ColumnPart<RowsPart, FieldPart> {
+    fn default() -> Self {
+        ColumnPart(PhantomData)
+    }
+}
+
+impl<RowsPart, RowsType, FieldPart, FieldType> Part for ColumnPart<RowsPart, FieldPart>
+where
+    RowsPart: Part<PartType = Field<RowsType>>,
+    RowsType: SliceField + ?Sized,
+    RowsType::Element: PartialRefTarget<RawTarget = RowsType::Element> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
+{
+    type PartType = ColumnField<RowsType::Element, FieldPart>;
+}
+
+impl<RowsPart, FieldPart, RowsPartS, FieldPartS> PartSpec<ColumnPart<RowsPartS, FieldPartS>>
+    for ColumnPart<RowsPart, FieldPart>
+where
+    RowsPart: PartSpec<RowsPartS>,
+    FieldPart: PartSpec<FieldPartS>,
+{
+}
+
+impl<RowsPart, FieldPart> NormalizeNestedPart for ColumnPart<RowsPart, FieldPart> {
+    type Normalized = ColumnPart<RowsPart, FieldPart>;
+}
+
+impl<NestedPrefix, RowsPart, FieldPart> AppendNestedPart<NestedPrefix>
+    for ColumnPart<RowsPart, FieldPart>
 {
-    type Target = Reference::Target;
+    type Output = Nested<NestedPrefix, ColumnPart<RowsPart, FieldPart>>;
 }
 
-/// Extending a valid reference by a constant part is still a valid reference when the reference
-/// target has such a part.
-impl<'a, SomePart: Part, Reference: PartialRef<'a>> PartialRef<'a> for Const<SomePart, Reference>
+/// A target has the column parts of its fields of rows.
+impl<Target, RowsPart, RowsType, FieldPart, FieldType> HasPart<ColumnPart<RowsPart, FieldPart>>
+    for Target
 where
-    Reference::Target: HasPart<SomePart>,
+    Target: HasPart<RowsPart> + ?Sized,
+    RowsPart: Part<PartType = Field<RowsType>>,
+    RowsType: SliceField + ?Sized,
+    RowsType::Element: PartialRefTarget<RawTarget = RowsType::Element> + HasPart<FieldPart>,
+    FieldPart: Part<PartType = Field<FieldType>>,
+    FieldType: ?Sized,
 {
     #[inline(always)]
-    unsafe fn from_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) -> Self {
-        Const {
-            ptr,
-            phantom: PhantomData,
-        }
+    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> *const [RowsType::Element] {
+        let field = <Self as HasPart<RowsPart>>::part_ptr(ptr);
+        RowsType::range_ptr(field, 0, RowsType::len_ptr(field))
     }
 
     #[inline(always)]
-    fn get_raw(&self) -> *mut <Self::Target as PartialRefTarget>::RawTarget {
-        self.ptr
+    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> *mut [RowsType::Element] {
+        let field = <Self as HasPart<RowsPart>>::part_ptr_mut(ptr);
+        RowsType::range_ptr_mut(field, 0, RowsType::len_ptr(field))
     }
 }
 
-/// A reference containing just constant parts is safe to clone.
-impl<SomePart, Reference: Copy + HasTarget> Copy for Const<SomePart, Reference> {}
-
-/// A reference containing just constant parts is safe to clone.
-impl<SomePart, Reference: Copy + HasTarget> Clone for Const<SomePart, Reference> {
-    #[inline(always)]
-    fn clone(&self) -> Self {
-        *self
-    }
+/// *(internal)* A partial reference having only column parts of the rows part `RowsPart`, used by
+/// [`split_column_parts_mut`](PartialRef::split_column_parts_mut).
+pub unsafe trait ColumnPartList<'a, RowsPart, Row: PartialRefTarget>: HasTarget {
+    /// The partial reference to a row having the parts of the columns.
+    type RowRef: PartialRef<'a, Target = Row>;
 }
 
-impl<'a, SomePart, Target, FieldType> Const<SomePart, Ref<'a, Target>>
+/// *(internal)* An empty reference has no columns.
+unsafe impl<'a, Target, RowsPart, Row> ColumnPartList<'a, RowsPart, Row> for Ref<'a, Target>
 where
-    FieldType: ?Sized,
-    SomePart: Part<PartType = Field<FieldType>>,
-    Target: ?Sized,
-    Target: HasPart<SomePart>,
+    Target: PartialRefTarget + ?Sized,
+    Row: PartialRefTarget + 'a,
 {
-    /// Only available on single part references, used to implement the more general
-    /// [`part`](PartialRef::part) method of [`PartialRef`].
-    #[inline(always)]
-    fn get_part(self) -> &'a FieldType {
-        unsafe { &*Target::part_ptr(self.get_raw() as *const _) }
-    }
+    type RowRef = Ref<'a, Row>;
 }
 
-impl<'a, SomePart, Target, FieldType> Mut<SomePart, Ref<'a, Target>>
+/// *(internal)* A constant column part is a constant part of the rows.
+unsafe impl<'a, RowsPart, FieldPart, Row, Reference> ColumnPartList<'a, RowsPart, Row>
+    for Const<ColumnPart<RowsPart, FieldPart>, Reference>
 where
-    FieldType: ?Sized,
-    SomePart: Part<PartType = Field<FieldType>>,
-    Target: ?Sized,
-    Target: HasPart<SomePart>,
+    ColumnPart<RowsPart, FieldPart>: Part,
+    FieldPart: Part,
+    Row: PartialRefTarget + HasPart<FieldPart>,
+    Reference: PartialRef<'a> + ColumnPartList<'a, RowsPart, Row>,
+    Reference::Target: HasPart<ColumnPart<RowsPart, FieldPart>>,
 {
-    /// Only available on single part references, used to implement the more general
-    /// [`part_mut`](PartialRef::part_mut) method of [`PartialRef`]
-    #[inline(always)]
-    fn get_part_mut(self) -> &'a mut FieldType {
-        unsafe { &mut *Target::part_ptr_mut(self.get_raw()) }
-    }
+    type RowRef = Const<FieldPart, Reference::RowRef>;
 }
 
-// Parts
-
-/// Marker types for a part of a type.
-///
-/// Types implementing this trait are usually created using the [`part`] macro.
-///
-/// A type implementing this trait is used to identify a part of a reference target. Multiple
-/// different reference targets can have a part identified by the same [`Part`] (see also
-/// [`HasPart`]). A part has an associated [`PartType`], which determines what can be done with a
-/// part.
-pub trait Part: Default {
-    type PartType: PartType;
+/// *(internal)* A mutable column part is a mutable part of the rows.
+unsafe impl<'a, RowsPart, FieldPart, Row, Reference> ColumnPartList<'a, RowsPart, Row>
+    for Mut<ColumnPart<RowsPart, FieldPart>, Reference>
+where
+    ColumnPart<RowsPart, FieldPart>: Part,
+    FieldPart: Part,
+    Row: PartialRefTarget + HasPart<FieldPart>,
+    Reference: PartialRef<'a> + ColumnPartList<'a, RowsPart, Row>,
+    Reference::Target: HasPart<ColumnPart<RowsPart, FieldPart>>,
+{
+    type RowRef = Mut<FieldPart, Reference::RowRef>;
 }
 
-/// Helper trait to strip lifetimes from a part.
-///
-/// Every part `SomePart<'a, ...>` should implement `PartSpec<SomePart<'b, ...>>`. This is used by
-/// the [`part`](PartialRef::part), [`part_mut`](PartialRef::part_mut),
-/// [`split_part`](PartialRef::split_part) and [`split_part_mut`](PartialRef::split_part_mut)
-/// functions. This allows the passed parameter to have a different lifetime than the accessed part.
-/// This in turn enables part selection using globals with static lifetimes as declared by the
-/// [`part`] macro.
-pub trait PartSpec<Part> {}
+/// *(internal)* A tuple of partial references that `Reference` can be split into, used by
+/// [`split_n`](PartialRef::split_n).
+pub unsafe trait SplitTuple<'a, Reference: PartialRef<'a>, SplitIndex> {
+    /// The tuple of the re-borrowed references and the remaining reference.
+    type Output;
 
-/// Implemented when a reference target has a part.
-///
-/// This trait provides methods for unchecked access to a part of a reference target.
-/// Implementations for this are automatically created when deriving PartialRefTarget.
-pub trait HasPart<SomePart: Part>: PartialRefTarget {
-    /// Given a constant pointer to a target, produce a constant pointer to a part of it.
-    ///
-    /// # Safety
-    /// Implementations may construct a temporary reference to ptr, which thus must be valid.
-    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> <SomePart::PartType as PartType>::Ptr;
-    /// Given a mutable pointer to a target, produce a mutable pointer to a part of it.
+    /// Create the split references from a raw pointer.
     ///
     /// # Safety
-    /// Implementations may construct a temporary reference to ptr, which thus must be valid.
-    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> <SomePart::PartType as PartType>::PtrMut;
+    /// The pointer must be valid for the parts of `Reference` and the lifetime `'a`.
+    unsafe fn split_raw(
+        ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+    ) -> Self::Output;
 }
 
-/// Type of a part, determines what can be done with a part.
-///
-/// Common part types are [`Field`] and [`AbstractPart`].
-pub trait PartType {
-    /// Type that can be produced from a constant pointer to a reference target.
-    type Ptr;
-    /// Type that can be produced from a mutable pointer to a reference target.
-    type PtrMut;
+macro_rules! impl_split_tuple {
+    ($(($Borrowed:ident, $Index:ident)),*) => {
+        impl_split_tuple!(@impl [] Reference; $(($Borrowed, $Index))*);
+    };
+    (
+        @impl [$(($Borrowed:ident, $Index:ident, $Reference:ty))*] $Remainder:ty;
+    ) => {
+        unsafe impl<'a, Reference, $($Borrowed, $Index),*>
+            SplitTuple<'a, Reference, ($($Index,)*)> for ($($Borrowed,)*)
+        where
+            Reference: PartialRef<'a>,
+            $(
+                $Borrowed: PartialRef<'a, Target = Reference::Target>,
+                $Reference: HasSubset<'a, $Borrowed, $Index>,
+            )*
+        {
+            type Output = ($($Borrowed,)* $Remainder);
+
+            #[inline(always)]
+            unsafe fn split_raw(
+                ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+            ) -> Self::Output {
+                ($($Borrowed::from_raw(ptr),)* <$Remainder as PartialRef<'a>>::from_raw(ptr))
+            }
+        }
+    };
+    (
+        @impl [$($done:tt)*] $Reference:ty;
+        ($Borrowed:ident, $Index:ident) $($rest:tt)*
+    ) => {
+        impl_split_tuple!(
+            @impl [$($done)* ($Borrowed, $Index, $Reference)]
+            <$Reference as HasSubset<'a, $Borrowed, $Index>>::Remainder;
+            $($rest)*
+        );
+    };
 }
 
-/// Type of a part that corresponds to a struct field.
+impl_split_tuple!((B1, I1), (B2, I2));
+impl_split_tuple!((B1, I1), (B2, I2), (B3, I3));
+impl_split_tuple!((B1, I1), (B2, I2), (B3, I3), (B4, I4));
+impl_split_tuple!((B1, I1), (B2, I2), (B3, I3), (B4, I4), (B5, I5));
+impl_split_tuple!((B1, I1), (B2, I2), (B3, I3), (B4, I4), (B5, I5), (B6, I6));
+
+// Bundled part bounds
+
+/// *(internal)* Placeholder for the unused positions of a [`PartTuple`].
 ///
-/// Partial references containing a part of this type provide access to that field.
-pub struct Field<FieldType: ?Sized>(PhantomData<FieldType>);
+/// Every target has this part.
+#[derive(Default)]
+pub struct NoPart;
 
-impl<FieldType: ?Sized> PartType for Field<FieldType> {
-    type Ptr = *const FieldType;
-    type PtrMut = *mut FieldType;
+impl Part for NoPart {
+    type PartType = AbstractPart;
 }
 
-/// Type of an abstract part.
-///
-/// Partial reference keep track of an abstract part, without providing any operations on it.
-pub struct AbstractPart;
+impl NormalizeNestedPart for NoPart {
+    type Normalized = NoPart;
+}
 
-impl PartType for AbstractPart {
-    type Ptr = ();
-    type PtrMut = ();
+impl<NestedPrefix> AppendNestedPart<NestedPrefix> for NoPart {
+    type Output = Nested<NestedPrefix, NoPart>;
 }
 
-/// A nested part.
-///
-/// A nested part can be constructed from an outer part and an inner part. The outer part must be a
-/// [`Field`] part, and the field's type must be a [`PartialRefTarget`] having the the inner part
-/// ([`HasPart`]).
+impl<Target: PartialRefTarget + ?Sized> HasPart<NoPart> for Target {
+    #[inline(always)]
+    unsafe fn part_ptr(_ptr: *const Self::RawTarget) {}
+
+    #[inline(always)]
+    unsafe fn part_ptr_mut(_ptr: *mut Self::RawTarget) {}
+}
+
+/// *(internal)* A tuple of up to eight parts, used by [`HasParts`].
 ///
-/// When nesting multiple times, the nested part should always be the outer part. This isn't
-/// enforced, but some operations are only supported in that case.
-#[derive(Default)]
-pub struct Nested<Outer, Inner>(Outer, Inner);
+/// Unused positions are filled with [`NoPart`].
+pub trait PartTuple {
+    type P1: Part;
+    type P2: Part;
+    type P3: Part;
+    type P4: Part;
+    type P5: Part;
+    type P6: Part;
+    type P7: Part;
+    type P8: Part;
+}
 
-/// Create default values for nested parts.
+macro_rules! impl_part_tuple {
+    ($($Part:ident),*; $($Unused:ident),*) => {
+        impl<$($Part: Part),*> PartTuple for ($($Part,)*) {
+            $(type $Part = $Part;)*
+            $(type $Unused = NoPart;)*
+        }
+    };
+}
+
+impl_part_tuple!(P1; P2, P3, P4, P5, P6, P7, P8);
+impl_part_tuple!(P1, P2; P3, P4, P5, P6, P7, P8);
+impl_part_tuple!(P1, P2, P3; P4, P5, P6, P7, P8);
+impl_part_tuple!(P1, P2, P3, P4; P5, P6, P7, P8);
+impl_part_tuple!(P1, P2, P3, P4, P5; P6, P7, P8);
+impl_part_tuple!(P1, P2, P3, P4, P5, P6; P7, P8);
+impl_part_tuple!(P1, P2, P3, P4, P5, P6, P7; P8);
+
+impl_part_tuple!(P1, P2, P3, P4, P5, P6, P7, P8;);
+
+/// A target having all parts of a tuple of up to eight parts.
 ///
-/// These are useful as parameters to pass to [`PartialRef`]'s part functions.
-impl<NewInnerPart: Part, Outer: Part, Inner: Part> std::ops::BitOr<NewInnerPart>
-    for Nested<Outer, Inner>
+/// The bound `T: HasParts<(PartA, PartB)>` is equivalent to `T: HasPart<PartA> + HasPart<PartB>`.
+/// As the parts are supertraits, such a bound is sufficient for using any partial reference to `T`
+/// having these parts, independent of whether they are constant or mutable, e.g.
+/// `partial!(T, mut PartA, PartB)`.
+pub trait HasParts<Parts: PartTuple>:
+    HasPart<Parts::P1>
+    + HasPart<Parts::P2>
+    + HasPart<Parts::P3>
+    + HasPart<Parts::P4>
+    + HasPart<Parts::P5>
+    + HasPart<Parts::P6>
+    + HasPart<Parts::P7>
+    + HasPart<Parts::P8>
 {
-    type Output = Nested<Nested<Outer, Inner>, NewInnerPart>;
+}
 
-    fn bitor(self, _rhs: NewInnerPart) -> Self::Output {
-        std::default::Default::default()
-    }
+impl<Target, Parts> HasParts<Parts> for Target
+where
+    Target: PartialRefTarget + ?Sized,
+    Parts: PartTuple,
+    Target: HasPart<Parts::P1>
+        + HasPart<Parts::P2>
+        + HasPart<Parts::P3>
+        + HasPart<Parts::P4>
+        + HasPart<Parts::P5>
+        + HasPart<Parts::P6>
+        + HasPart<Parts::P7>
+        + HasPart<Parts::P8>,
+{
 }
 
-/// A nested part is itself a part.
-impl<Outer, OuterFieldType, Inner> Part for Nested<Outer, Inner>
+// Joining helpers
+
+/// *(internal)* Shorten the lifetime of a partial reference without changing its parts.
+pub unsafe trait ShortenParts<'a>: PartialRef<'a> {
+    /// The partial reference having the same parts as `Self`, but the lifetime `'a`.
+    type ShortRef: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* An empty reference gets the shorter lifetime.
+unsafe impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> ShortenParts<'a> for Ref<'b, Target> {
+    type ShortRef = Ref<'a, Target>;
+}
+
+/// *(internal)* Constant parts stay constant.
+unsafe impl<'a, SomePart, Reference> ShortenParts<'a> for Const<SomePart, Reference>
 where
-    Outer: Part<PartType = Field<OuterFieldType>>,
-    Inner: Part,
-    OuterFieldType: ?Sized,
-    OuterFieldType: HasPart<Inner>,
-    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+    SomePart: Part,
+    Reference: ShortenParts<'a>,
+    Reference::Target: HasPart<SomePart>,
 {
-    type PartType = Inner::PartType;
+    type ShortRef = Const<SomePart, Reference::ShortRef>;
 }
 
-impl<Outer, Inner, OuterS, InnerS> PartSpec<Nested<OuterS, InnerS>> for Nested<Outer, Inner>
+/// *(internal)* Mutable parts stay mutable.
+unsafe impl<'a, SomePart, Reference> ShortenParts<'a> for Mut<SomePart, Reference>
 where
-    Outer: PartSpec<OuterS>,
-    Inner: PartSpec<InnerS>,
+    SomePart: Part,
+    Reference: ShortenParts<'a>,
+    Reference::Target: HasPart<SomePart>,
 {
+    type ShortRef = Mut<SomePart, Reference::ShortRef>;
 }
 
-/// A reference has a nested part if it has the outer part and the nested part is valid.
-impl<Reference, Outer, OuterFieldType, Inner> HasPart<Nested<Outer, Inner>> for Reference
+/// *(internal)* Implemented when `SomePart` is a constant part of the partial reference.
+///
+/// The `Index` type can be inferred.
+pub unsafe trait ContainsConstPart<SomePart, Index> {}
+
+unsafe impl<SomePart, Reference: HasTarget> ContainsConstPart<SomePart, IndexHere>
+    for Const<SomePart, Reference>
+{
+}
+
+unsafe impl<SomePart, OtherPart, Reference: HasTarget, Index>
+    ContainsConstPart<SomePart, IndexNext<Index>> for Const<OtherPart, Reference>
 where
-    Reference: ?Sized,
-    Reference: HasPart<Outer>,
-    Outer: Part<PartType = Field<OuterFieldType>>,
-    Inner: Part,
-    OuterFieldType: ?Sized,
-    OuterFieldType: HasPart<Inner>,
-    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
+    Reference: ContainsConstPart<SomePart, Index>,
 {
-    #[inline(always)]
-    unsafe fn part_ptr(ptr: *const Self::RawTarget) -> <Inner::PartType as PartType>::Ptr {
-        <OuterFieldType as HasPart<Inner>>::part_ptr(<Self as HasPart<Outer>>::part_ptr(ptr))
-    }
+}
 
-    #[inline(always)]
-    unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> <Inner::PartType as PartType>::PtrMut {
-        <OuterFieldType as HasPart<Inner>>::part_ptr_mut(<Self as HasPart<Outer>>::part_ptr_mut(
-            ptr,
-        ))
-    }
+unsafe impl<SomePart, OtherPart, Reference: HasTarget, Index>
+    ContainsConstPart<SomePart, IndexNext<Index>> for Mut<OtherPart, Reference>
+where
+    Reference: ContainsConstPart<SomePart, Index>,
+{
 }
 
-// Type level logic for subsetting
-// This is inspired by the `frunk` crate
+/// *(internal)* Implemented when every constant part of the partial reference is a constant part
+/// of `Joined`.
+///
+/// The index is a list of the part indices of the constant parts within `Joined`.
+pub unsafe trait ConstPartsIn<Joined, ConstIndex> {}
 
-/// *(internal)* Select the first part.
-pub struct IndexHere;
+unsafe impl<'a, Target, Joined> ConstPartsIn<Joined, SubsetIndexEnd> for Ref<'a, Target> where
+    Target: PartialRefTarget + ?Sized
+{
+}
 
-/// *(internal)* Skip the first part.
-pub struct IndexNext<Index>(Index);
+unsafe impl<SomePart, Reference: HasTarget, Joined, ConstIndex> ConstPartsIn<Joined, ConstIndex>
+    for Mut<SomePart, Reference>
+where
+    Reference: ConstPartsIn<Joined, ConstIndex>,
+{
+}
 
-/// *(internal)* Split the first part.
-pub struct IndexSplit<NestedPartIndex, Index>(NestedPartIndex, Index);
+unsafe impl<SomePart, Reference: HasTarget, Joined, PartIndex, TailIndex>
+    ConstPartsIn<Joined, SubsetIndexCons<PartIndex, TailIndex>> for Const<SomePart, Reference>
+where
+    Reference: ConstPartsIn<Joined, TailIndex>,
+    Joined: ContainsConstPart<SomePart, PartIndex>,
+{
+}
 
-/// *(internal)* Extracts the constant part `PluckedPart` at position `Index` from the partial
-/// reference having this trait, leaving `Self::Remainder`.
-///
-/// Plucking a constant part still leaves the part in the remaining reference, but will change it
-/// from mutable to constant.
+// Copying helpers
+
+/// *(internal)* Copy the values of the mutable parts of a partial reference from the same parts of
+/// the partial reference `Source`.
 ///
-/// The `Index` type can be inferred.
-pub unsafe trait PluckConst<'a, PluckedPart, Index>: PartialRef<'a> {
-    /// The partial reference left after plucking.
-    type Remainder: PartialRef<'a, Target = Self::Target>;
+/// The index is a list of the part indices of the mutable parts within `Source`.
+pub unsafe trait CopyPartsFrom<'b, Source: PartialRef<'b>, CopyIndex>: HasTarget {
+    /// Copy the parts using raw pointers.
+    ///
+    /// # Safety
+    /// The pointers must be valid for the parts of `Self` and `Source` respectively and the mutable
+    /// parts of `Self` must not overlap with the parts of `Source`.
+    unsafe fn copy_parts_from_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        source_ptr: *mut <Source::Target as PartialRefTarget>::RawTarget,
+    );
 }
 
-/// *(internal)* Extracts the mutable part `PluckedPart` at position `Index` from the partial
-/// reference having this trait, leaving `Self::Remainder`.
-///
-/// Plucking a mutable part removes it from the remaining reference.
-///
-/// The `Index` type can be inferred.
-pub unsafe trait PluckMut<'a, PluckedPart, Index>: PartialRef<'a> {
-    /// The partial reference left after plucking.
-    type Remainder: PartialRef<'a, Target = Self::Target>;
+/// *(internal)* An empty reference has nothing to copy.
+unsafe impl<'a, 'b, Target, Source> CopyPartsFrom<'b, Source, SubsetIndexEnd> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+    Source: PartialRef<'b>,
+{
+    #[inline(always)]
+    unsafe fn copy_parts_from_raw(
+        _ptr: *mut Target::RawTarget,
+        _source_ptr: *mut <Source::Target as PartialRefTarget>::RawTarget,
+    ) {
+    }
 }
 
-/// *(internal)* Plucks the outermost constant part.
-unsafe impl<'a, PluckedPart, Reference> PluckConst<'a, PluckedPart, IndexHere>
-    for Const<PluckedPart, Reference>
+/// *(internal)* Constant parts are not copied.
+unsafe impl<'a, 'b, SomePart, Reference, Source, CopyIndex> CopyPartsFrom<'b, Source, CopyIndex>
+    for Const<SomePart, Reference>
 where
-    PluckedPart: Part,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<PluckedPart>,
+    SomePart: Part,
+    Reference: PartialRef<'a> + CopyPartsFrom<'b, Source, CopyIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Source: PartialRef<'b>,
 {
-    type Remainder = Const<PluckedPart, Reference>;
+    #[inline(always)]
+    unsafe fn copy_parts_from_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        source_ptr: *mut <Source::Target as PartialRefTarget>::RawTarget,
+    ) {
+        Reference::copy_parts_from_raw(ptr, source_ptr)
+    }
 }
 
-/// *(internal)* Plucks the first part, converting it from mutable to constant.
-unsafe impl<'a, PluckedPart, Reference> PluckConst<'a, PluckedPart, IndexHere>
-    for Mut<PluckedPart, Reference>
+/// *(internal)* To copy a mutable field part, pluck it from the source and copy the remaining
+/// parts.
+unsafe impl<'a, 'b, SomePart, FieldType, Reference, Source, PartIndex, TailIndex>
+    CopyPartsFrom<'b, Source, SubsetIndexCons<PartIndex, TailIndex>> for Mut<SomePart, Reference>
 where
-    PluckedPart: Part,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<PluckedPart>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Clone,
+    Reference: PartialRef<'a> + CopyPartsFrom<'b, Source, TailIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Source: PluckConst<'b, SomePart, PartIndex>,
+    Source::Target: HasPart<SomePart>,
 {
-    type Remainder = Const<PluckedPart, Reference>;
+    #[inline(always)]
+    unsafe fn copy_parts_from_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        source_ptr: *mut <Source::Target as PartialRefTarget>::RawTarget,
+    ) {
+        let part = &mut *<Self::Target as HasPart<SomePart>>::part_ptr_mut(ptr);
+        part.clone_from(&*<Source::Target as HasPart<SomePart>>::part_ptr(
+            source_ptr,
+        ));
+        Reference::copy_parts_from_raw(ptr, source_ptr)
+    }
 }
 
-/// *(internal)* Plucks the first mutable part.
-unsafe impl<'a, PluckedPart, Reference> PluckMut<'a, PluckedPart, IndexHere>
-    for Mut<PluckedPart, Reference>
+/// *(internal)* Copy the values of the parts of a partial reference to the same parts of the
+/// partial reference `Dest`.
+///
+/// The index is a list of the part indices of the parts within `Dest`.
+pub unsafe trait ClonePartsInto<'b, Dest: PartialRef<'b>, CloneIndex>: HasTarget {
+    /// Copy the parts using raw pointers.
+    ///
+    /// # Safety
+    /// The pointers must be valid for the parts of `Self` and `Dest` respectively and the parts of
+    /// `Self` must not overlap with the mutable parts of `Dest`.
+    unsafe fn clone_parts_into_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        dest_ptr: *mut <Dest::Target as PartialRefTarget>::RawTarget,
+    );
+}
+
+/// *(internal)* An empty reference has nothing to copy.
+unsafe impl<'a, 'b, Target, Dest> ClonePartsInto<'b, Dest, SubsetIndexEnd> for Ref<'a, Target>
 where
-    PluckedPart: Part,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<PluckedPart>,
+    Target: PartialRefTarget + ?Sized,
+    Dest: PartialRef<'b>,
 {
-    type Remainder = Reference;
+    #[inline(always)]
+    unsafe fn clone_parts_into_raw(
+        _ptr: *mut Target::RawTarget,
+        _dest_ptr: *mut <Dest::Target as PartialRefTarget>::RawTarget,
+    ) {
+    }
 }
 
-/// *(internal)* Skips the constant first part while plucking a constant part.
-unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
-    PluckConst<'a, PluckedPart, IndexNext<Index>> for Const<SkippedPart, Reference>
+/// *(internal)* To copy a constant field part, pluck it from the destination and copy the remaining
+/// parts.
+unsafe impl<'a, 'b, SomePart, FieldType, Reference, Dest, PartIndex, TailIndex>
+    ClonePartsInto<'b, Dest, SubsetIndexCons<PartIndex, TailIndex>> for Const<SomePart, Reference>
 where
-    PluckedPart: Part,
-    SkippedPart: Part,
-    Reference::Target: HasPart<PluckedPart>,
-    Reference::Target: HasPart<SkippedPart>,
-    Reference: PluckConst<'a, PluckedPart, Index>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Clone,
+    Reference: PartialRef<'a> + ClonePartsInto<'b, Dest, TailIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Dest: PluckMut<'b, SomePart, PartIndex>,
+    Dest::Target: HasPart<SomePart>,
 {
-    type Remainder = Const<SkippedPart, Reference::Remainder>;
+    #[inline(always)]
+    unsafe fn clone_parts_into_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        dest_ptr: *mut <Dest::Target as PartialRefTarget>::RawTarget,
+    ) {
+        let part = &mut *<Dest::Target as HasPart<SomePart>>::part_ptr_mut(dest_ptr);
+        part.clone_from(&*<Self::Target as HasPart<SomePart>>::part_ptr(ptr));
+        Reference::clone_parts_into_raw(ptr, dest_ptr)
+    }
 }
 
-/// *(internal)* Skips the mutable first part while plucking a constant part.
-unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
-    PluckConst<'a, PluckedPart, IndexNext<Index>> for Mut<SkippedPart, Reference>
+/// *(internal)* To copy a mutable field part, pluck it from the destination and copy the remaining
+/// parts.
+unsafe impl<'a, 'b, SomePart, FieldType, Reference, Dest, PartIndex, TailIndex>
+    ClonePartsInto<'b, Dest, SubsetIndexCons<PartIndex, TailIndex>> for Mut<SomePart, Reference>
 where
-    PluckedPart: Part,
-    SkippedPart: Part,
-    Reference::Target: HasPart<PluckedPart>,
-    Reference::Target: HasPart<SkippedPart>,
-    Reference: PluckConst<'a, PluckedPart, Index>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Clone,
+    Reference: PartialRef<'a> + ClonePartsInto<'b, Dest, TailIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Dest: PluckMut<'b, SomePart, PartIndex>,
+    Dest::Target: HasPart<SomePart>,
 {
-    type Remainder = Mut<SkippedPart, Reference::Remainder>;
+    #[inline(always)]
+    unsafe fn clone_parts_into_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        dest_ptr: *mut <Dest::Target as PartialRefTarget>::RawTarget,
+    ) {
+        let part = &mut *<Dest::Target as HasPart<SomePart>>::part_ptr_mut(dest_ptr);
+        part.clone_from(&*<Self::Target as HasPart<SomePart>>::part_ptr(ptr));
+        Reference::clone_parts_into_raw(ptr, dest_ptr)
+    }
 }
 
-/// *(internal)* Skips the constant first part while plucking a mutable part.
-unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
-    PluckMut<'a, PluckedPart, IndexNext<Index>> for Const<SkippedPart, Reference>
+// Resetting helpers
+
+/// *(internal)* Reset the values of the mutable parts of a partial reference to their defaults.
+pub unsafe trait ResetParts: HasTarget {
+    /// Reset the parts using a raw pointer.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the parts of `Self`.
+    unsafe fn reset_parts_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget);
+}
+
+/// *(internal)* An empty reference has nothing to reset.
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> ResetParts for Ref<'a, Target> {
+    #[inline(always)]
+    unsafe fn reset_parts_raw(_ptr: *mut Target::RawTarget) {}
+}
+
+/// *(internal)* Constant parts are not reset.
+unsafe impl<'a, SomePart, Reference> ResetParts for Const<SomePart, Reference>
 where
-    PluckedPart: Part,
-    SkippedPart: Part,
-    Reference::Target: HasPart<PluckedPart>,
-    Reference::Target: HasPart<SkippedPart>,
-    Reference: PluckMut<'a, PluckedPart, Index>,
+    SomePart: Part,
+    Reference: PartialRef<'a> + ResetParts,
+    Reference::Target: HasPart<SomePart>,
 {
-    type Remainder = Const<SkippedPart, Reference::Remainder>;
+    #[inline(always)]
+    unsafe fn reset_parts_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) {
+        Reference::reset_parts_raw(ptr)
+    }
 }
 
-/// *(internal)* Skips the mutable first part while plucking a mutable part.
-unsafe impl<'a, PluckedPart, SkippedPart, Reference, Index>
-    PluckMut<'a, PluckedPart, IndexNext<Index>> for Mut<SkippedPart, Reference>
+/// *(internal)* Reset a mutable field part and the remaining parts.
+unsafe impl<'a, SomePart, FieldType, Reference> ResetParts for Mut<SomePart, Reference>
 where
-    PluckedPart: Part,
-    SkippedPart: Part,
-    Reference::Target: HasPart<PluckedPart>,
-    Reference::Target: HasPart<SkippedPart>,
-    Reference: PluckMut<'a, PluckedPart, Index>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Default,
+    Reference: PartialRef<'a> + ResetParts,
+    Reference::Target: HasPart<SomePart>,
 {
-    type Remainder = Mut<SkippedPart, Reference::Remainder>;
+    #[inline(always)]
+    unsafe fn reset_parts_raw(ptr: *mut <Self::Target as PartialRefTarget>::RawTarget) {
+        *<Self::Target as HasPart<SomePart>>::part_ptr_mut(ptr) = FieldType::default();
+        Reference::reset_parts_raw(ptr)
+    }
 }
 
-/// *(internal)* Pluck a mutable nested subpart of the mutable first part.
+// Comparing helpers
+
+/// *(internal)* Compare the values of the parts of a partial reference with the same parts of the
+/// partial reference `Other`.
 ///
-/// This leaves all other subparts in the remaining reference.
-unsafe impl<
-        'a,
-        ContainingPart,
-        PluckedOuter,
-        PluckedInner,
-        Reference,
-        NestedPartIndex,
-        Index,
-        OuterFieldType,
-        ContainingFieldType,
-    > PluckMut<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
-    for Mut<ContainingPart, Reference>
+/// The index is a list of the part indices of the parts within `Other`.
+pub unsafe trait PartsEq<'b, Other: PartialRef<'b>, EqIndex>: HasTarget {
+    /// Compare the parts using raw pointers.
+    ///
+    /// # Safety
+    /// The pointers must be valid for the parts of `Self` and `Other` respectively.
+    unsafe fn parts_eq_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        other_ptr: *mut <Other::Target as PartialRefTarget>::RawTarget,
+    ) -> bool;
+}
+
+/// *(internal)* Empty references are always equal.
+unsafe impl<'a, 'b, Target, Other> PartsEq<'b, Other, SubsetIndexEnd> for Ref<'a, Target>
 where
-    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
-    PluckedInner: Part,
-    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
-    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
-    ContainingFieldType: ?Sized,
-    ContainingFieldType::ResultMut: PluckMut<'a, Nested<PluckedOuter, PluckedInner>, Index>,
-    OuterFieldType: ?Sized,
-    OuterFieldType: HasPart<PluckedInner>,
-    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<ContainingPart>,
-    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+    Target: PartialRefTarget + ?Sized,
+    Other: PartialRef<'b>,
 {
-    type Remainder = <ContainingFieldType::ResultMut as PluckMut<
-        'a,
-        Nested<PluckedOuter, PluckedInner>,
-        Index,
-    >>::Remainder;
+    #[inline(always)]
+    unsafe fn parts_eq_raw(
+        _ptr: *mut Target::RawTarget,
+        _other_ptr: *mut <Other::Target as PartialRefTarget>::RawTarget,
+    ) -> bool {
+        true
+    }
 }
 
-/// *(internal)* Pluck a constant nested subpart of the mutable first part.
-///
-/// This leaves all other subparts as mutable in the remaining reference.
-unsafe impl<
-        'a,
-        ContainingPart,
-        PluckedOuter,
-        PluckedInner,
-        Reference,
-        NestedPartIndex,
-        Index,
-        OuterFieldType,
-        ContainingFieldType,
-    > PluckConst<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
-    for Mut<ContainingPart, Reference>
+/// *(internal)* To compare a constant field part, pluck it from the other reference and compare
+/// the remaining parts.
+unsafe impl<'a, 'b, SomePart, FieldType, Reference, Other, PartIndex, TailIndex>
+    PartsEq<'b, Other, SubsetIndexCons<PartIndex, TailIndex>> for Const<SomePart, Reference>
 where
-    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
-    PluckedInner: Part,
-    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
-    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
-    ContainingFieldType: ?Sized,
-    ContainingFieldType::ResultMut: PluckConst<'a, Nested<PluckedOuter, PluckedInner>, Index>,
-    OuterFieldType: ?Sized,
-    OuterFieldType: HasPart<PluckedInner>,
-    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<ContainingPart>,
-    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: PartialEq + ?Sized,
+    Reference: PartialRef<'a> + PartsEq<'b, Other, TailIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Other: PluckConst<'b, SomePart, PartIndex>,
+    Other::Target: HasPart<SomePart>,
 {
-    type Remainder = <ContainingFieldType::ResultMut as PluckConst<
-        'a,
-        Nested<PluckedOuter, PluckedInner>,
-        Index,
-    >>::Remainder;
+    #[inline(always)]
+    unsafe fn parts_eq_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        other_ptr: *mut <Other::Target as PartialRefTarget>::RawTarget,
+    ) -> bool {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::parts_eq_raw(ptr, other_ptr)
+            && *<Self::Target as HasPart<SomePart>>::part_ptr(ptr)
+                == *<Other::Target as HasPart<SomePart>>::part_ptr(other_ptr)
+    }
 }
 
-/// *(internal)* Pluck a constant nested subpart of the constant first part.
-unsafe impl<
-        'a,
-        ContainingPart,
-        PluckedOuter,
-        PluckedInner,
-        Reference,
-        NestedPartIndex,
-        Index,
-        OuterFieldType,
-        ContainingFieldType,
-    > PluckConst<'a, Nested<PluckedOuter, PluckedInner>, IndexSplit<NestedPartIndex, Index>>
-    for Const<ContainingPart, Reference>
+/// *(internal)* To compare a mutable field part, pluck it from the other reference and compare
+/// the remaining parts.
+unsafe impl<'a, 'b, SomePart, FieldType, Reference, Other, PartIndex, TailIndex>
+    PartsEq<'b, Other, SubsetIndexCons<PartIndex, TailIndex>> for Mut<SomePart, Reference>
 where
-    PluckedOuter: Part<PartType = Field<OuterFieldType>>,
-    PluckedInner: Part,
-    ContainingPart: Part<PartType = Field<ContainingFieldType>>,
-    ContainingFieldType: SplitIntoParts<'a, ContainingPart, Reference>,
-    ContainingFieldType: ?Sized,
-    ContainingFieldType::ResultMut: PluckConst<'a, Nested<PluckedOuter, PluckedInner>, Index>,
-    OuterFieldType: ?Sized,
-    OuterFieldType: HasPart<PluckedInner>,
-    OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
-    Reference: PartialRef<'a>,
-    Reference::Target: HasPart<ContainingPart>,
-    ContainingPart: ContainsNestedPart<PluckedOuter, NestedPartIndex>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: PartialEq + ?Sized,
+    Reference: PartialRef<'a> + PartsEq<'b, Other, TailIndex>,
+    Reference::Target: HasPart<SomePart>,
+    Other: PluckConst<'b, SomePart, PartIndex>,
+    Other::Target: HasPart<SomePart>,
 {
-    type Remainder = Const<ContainingPart, Reference>;
+    #[inline(always)]
+    unsafe fn parts_eq_raw(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        other_ptr: *mut <Other::Target as PartialRefTarget>::RawTarget,
+    ) -> bool {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::parts_eq_raw(ptr, other_ptr)
+            && *<Self::Target as HasPart<SomePart>>::part_ptr(ptr)
+                == *<Other::Target as HasPart<SomePart>>::part_ptr(other_ptr)
+    }
 }
 
-/// *(internal)* Index for the empty subset.
-pub struct SubsetIndexEnd;
+// Hashing helpers
 
-/// *(internal)* Construct a subset index from a part index and another subset index.
-pub struct SubsetIndexCons<PartIndex, TailIndex>(PartIndex, TailIndex);
+/// *(internal)* Hash the values of the parts of a partial reference.
+pub unsafe trait HashParts: HasTarget {
+    /// Hash the parts using a raw pointer.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the parts of `Self`.
+    unsafe fn hash_parts_raw<H: hash::Hasher>(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        state: &mut H,
+    );
+}
 
-/// *(internal)* Asserts that the parts of the partial reference `Reference` are a subset of the
-/// parts of the partial reference having this trait.
-///
-/// A list of parts is considered a subset if they can be plucked in sequence.
-pub unsafe trait HasSubset<'a, Reference, SubsetIndex>: PartialRef<'a> {
-    type Remainder: PartialRef<'a, Target = Self::Target>;
+/// *(internal)* An empty reference has nothing to hash.
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> HashParts for Ref<'a, Target> {
+    #[inline(always)]
+    unsafe fn hash_parts_raw<H: hash::Hasher>(_ptr: *mut Target::RawTarget, _state: &mut H) {}
 }
 
-/// *(internal)* Every reference has the empty reference as subset.
-unsafe impl<'a, Reference> HasSubset<'a, Ref<'a, Reference::Target>, SubsetIndexEnd> for Reference
+/// *(internal)* Hash the remaining parts followed by a constant field part.
+unsafe impl<'a, SomePart, FieldType, Reference> HashParts for Const<SomePart, Reference>
 where
-    Reference: PartialRef<'a>,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: hash::Hash + ?Sized,
+    Reference: PartialRef<'a> + HashParts,
+    Reference::Target: HasPart<SomePart>,
 {
-    type Remainder = Reference;
+    #[inline(always)]
+    unsafe fn hash_parts_raw<H: hash::Hasher>(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        state: &mut H,
+    ) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::hash_parts_raw(ptr, state);
+        hash::Hash::hash(&*<Self::Target as HasPart<SomePart>>::part_ptr(ptr), state)
+    }
 }
 
-/// *(internal)* To have a reference with a constant first part as subset, pluck that part and make
-/// sure the remaining references are in a subset relation.
-unsafe impl<'a, SubsetPart, Reference, PluckedRef, PartIndex, TailIndex>
-    HasSubset<'a, Const<SubsetPart, Reference>, SubsetIndexCons<PartIndex, TailIndex>>
-    for PluckedRef
+/// *(internal)* Hash the remaining parts followed by a mutable field part.
+unsafe impl<'a, SomePart, FieldType, Reference> HashParts for Mut<SomePart, Reference>
 where
-    PluckedRef: PluckConst<'a, SubsetPart, PartIndex>,
-    <PluckedRef as PluckConst<'a, SubsetPart, PartIndex>>::Remainder:
-        HasSubset<'a, Reference, TailIndex>,
-    Reference: HasTarget,
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: hash::Hash + ?Sized,
+    Reference: PartialRef<'a> + HashParts,
+    Reference::Target: HasPart<SomePart>,
 {
-    type Remainder =
-        <<PluckedRef as PluckConst<'a, SubsetPart, PartIndex>>::Remainder as HasSubset<
-            'a,
-            Reference,
-            TailIndex,
-        >>::Remainder;
+    #[inline(always)]
+    unsafe fn hash_parts_raw<H: hash::Hasher>(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        state: &mut H,
+    ) {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::hash_parts_raw(ptr, state);
+        hash::Hash::hash(&*<Self::Target as HasPart<SomePart>>::part_ptr(ptr), state)
+    }
 }
 
-/// *(internal)* To have a reference with a mutable first part as subset, pluck that part and make
-/// sure the remaining references are in a subset relation.
-unsafe impl<'a, SubsetPart, Reference, PluckedRef, PartIndex, TailIndex>
-    HasSubset<'a, Mut<SubsetPart, Reference>, SubsetIndexCons<PartIndex, TailIndex>> for PluckedRef
-where
-    PluckedRef: PluckMut<'a, SubsetPart, PartIndex>,
-    <PluckedRef as PluckMut<'a, SubsetPart, PartIndex>>::Remainder:
-        HasSubset<'a, Reference, TailIndex>,
-    Reference: HasTarget,
+// Swapping helpers
+
+/// Swap the fields of mutable parts of two partial references.
+///
+/// The partial references can have different targets, as long as the fields of both parts have the
+/// same type. This is equivalent to [`std::mem::swap`] on the results of
+/// [`part_mut`](PartialRef::part_mut).
+///
+/// Usually the type parameters can be inferred.
+#[inline(always)]
+pub fn swap_parts<
+    'a,
+    'b,
+    ReferenceA,
+    FieldPartSpecA,
+    FieldPartA,
+    PartIndexA,
+    ReferenceB,
+    FieldPartSpecB,
+    FieldPartB,
+    PartIndexB,
+    FieldType,
+>(
+    reference_a: &'a mut ReferenceA,
+    part_a: FieldPartSpecA,
+    reference_b: &'b mut ReferenceB,
+    part_b: FieldPartSpecB,
+) where
+    ReferenceA: PluckMut<'a, FieldPartA, PartIndexA>,
+    ReferenceA::Target: HasPart<FieldPartA> + 'a,
+    FieldPartA: Part<PartType = Field<FieldType>>,
+    FieldPartSpecA: PartSpec<FieldPartA>,
+    ReferenceB: PluckMut<'b, FieldPartB, PartIndexB>,
+    ReferenceB::Target: HasPart<FieldPartB> + 'b,
+    FieldPartB: Part<PartType = Field<FieldType>>,
+    FieldPartSpecB: PartSpec<FieldPartB>,
+    FieldType: 'a + 'b,
 {
-    type Remainder =
-        <<PluckedRef as PluckMut<'a, SubsetPart, PartIndex>>::Remainder as HasSubset<
-            'a,
-            Reference,
-            TailIndex,
-        >>::Remainder;
+    std::mem::swap(reference_a.part_mut(part_a), reference_b.part_mut(part_b))
 }
 
 // Nesting helpers
@@ -962,3 +6769,77 @@ where
     OuterFieldType: PartialRefTarget<RawTarget = OuterFieldType>,
 {
 }
+
+/// *(internal)* The form of a part in which every nested part is the outer part.
+///
+/// The parts `Nested<Nested<A, B>, C>` and `Nested<A, Nested<B, C>>` refer to the same field, but
+/// only the first form, as produced by [`nested_part`], is supported by all operations. This maps
+/// both to the first form. Implementations for this are automatically created by the [`part`]
+/// macro.
+pub trait NormalizeNestedPart {
+    /// The normalized part.
+    type Normalized;
+}
+
+/// *(internal)* The normalized form of `Nested<NestedPrefix, Self>` for a normalized
+/// `NestedPrefix`.
+///
+/// Implementations for this are automatically created by the [`part`] macro.
+pub trait AppendNestedPart<NestedPrefix> {
+    /// The normalized nested part.
+    type Output;
+}
+
+/// *(internal)* A nested part is normalized by appending the inner part to the normalized outer
+/// part.
+impl<Outer, Inner> NormalizeNestedPart for Nested<Outer, Inner>
+where
+    Outer: NormalizeNestedPart,
+    Inner: AppendNestedPart<Outer::Normalized>,
+{
+    type Normalized = Inner::Output;
+}
+
+/// *(internal)* Appending a nested part appends its outer part followed by its inner part.
+impl<NestedPrefix, Outer, Inner> AppendNestedPart<NestedPrefix> for Nested<Outer, Inner>
+where
+    Outer: AppendNestedPart<NestedPrefix>,
+    Inner: AppendNestedPart<Outer::Output>,
+{
+    type Output = Inner::Output;
+}
+
+/// *(internal)* The form of a partial reference in which all parts are normalized.
+///
+/// See [`reassociate`](PartialRef::reassociate).
+pub trait NormalizeNestedParts<'a>: PartialRef<'a> {
+    /// The partial reference having the normalized parts of `Self`.
+    type Normalized: PartialRef<'a, Target = Self::Target>;
+}
+
+/// *(internal)* An empty reference is normalized.
+impl<'a, 'b: 'a, Target: PartialRefTarget + ?Sized> NormalizeNestedParts<'a> for Ref<'b, Target> {
+    type Normalized = Self;
+}
+
+/// *(internal)* Normalize a constant part and the remaining parts.
+impl<'a, SomePart, Reference> NormalizeNestedParts<'a> for Const<SomePart, Reference>
+where
+    SomePart: Part + NormalizeNestedPart,
+    SomePart::Normalized: Part,
+    Reference: NormalizeNestedParts<'a>,
+    Reference::Target: HasPart<SomePart> + HasPart<SomePart::Normalized>,
+{
+    type Normalized = Const<SomePart::Normalized, Reference::Normalized>;
+}
+
+/// *(internal)* Normalize a mutable part and the remaining parts.
+impl<'a, SomePart, Reference> NormalizeNestedParts<'a> for Mut<SomePart, Reference>
+where
+    SomePart: Part + NormalizeNestedPart,
+    SomePart::Normalized: Part,
+    Reference: NormalizeNestedParts<'a>,
+    Reference::Target: HasPart<SomePart> + HasPart<SomePart::Normalized>,
+{
+    type Normalized = Mut<SomePart::Normalized, Reference::Normalized>;
+}
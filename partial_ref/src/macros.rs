@@ -5,20 +5,128 @@
 ///
 /// This macro can define [`AbstractPart`]s using `part!(PartName);` or `part!(pub PartName);` and
 /// [`Field`] parts using `part!(PartName: FieldType);` or `part!(pub PartName: FieldType);`.
+///
+/// Parts for fields of enum variants ([`VariantField`] parts) are defined using
+/// `part!(PartName in VariantPart: FieldType);` or `part!(pub PartName in VariantPart: FieldType);`
+/// where `VariantPart` is the abstract part identifying the variant.
+///
+/// Parts for structurally pinned fields ([`PinnedField`] parts) are defined using
+/// `part!(pin PartName: FieldType);` or `part!(pub pin PartName: FieldType);`.
+///
+/// Parts for the values pointed to by shared pointer fields
+/// ([`SharedField`](crate::SharedField) parts) are defined using
+/// `part!(shared PartName: FieldType);` or `part!(pub shared PartName: FieldType);`.
+///
+/// Parts for fields of unions ([`UnionField`] parts) are defined using
+/// `part!(union PartName: FieldType);` or `part!(pub union PartName: FieldType);`.
+///
+/// Parts for possibly unaligned fields of packed structs ([`UnalignedField`] parts) are defined
+/// using `part!(unaligned PartName: FieldType);` or `part!(pub unaligned PartName: FieldType);`.
+///
+/// Parts for lock-guarded fields ([`LockedField`](crate::LockedField) parts) are defined using
+/// `part!(lock PartName: FieldType);` or `part!(pub lock PartName: FieldType);`.
+///
+/// Parts for fields of type `RefCell<FieldType>` ([`RefCellField`](crate::RefCellField) parts) are
+/// defined using `part!(ref_cell PartName: FieldType);` or
+/// `part!(pub ref_cell PartName: FieldType);`.
+///
+/// Parts stored in thread-local storage ([`ThreadLocalPart`](crate::ThreadLocalPart)s) are defined
+/// using `part!(thread_local PartName: FieldType);` or
+/// `part!(pub thread_local PartName: FieldType);`. This also declares the storage, which is
+/// initialized using `FieldType::default()`.
+///
+/// Field parts can have lifetime, type and const parameters, as in
+/// `part!(pub PartName<'a, T, const N: usize>: &'a [T; N]);`. Parameters have to be listed in that
+/// order. A part having only lifetime parameters is also declared as a constant of the same name, so
+/// it can be passed to [`PartialRef`]'s part functions. Other parts have no such constant, instead a
+/// value can be created using `PartName::default()`.
 #[macro_export]
 macro_rules! part {
-    ($part:ident) => { part!(@template $part () () ($crate::AbstractPart)); };
-    (pub $part:ident) => { part!(@template $part () (pub) ($crate::AbstractPart)); };
-    ($part:ident<> : $field:ty) => { part!($part : $field); };
-    (pub $part:ident<> : $field:ty) => { part!(pub $part : $field); };
+    ($part:ident) => { $crate::part!(@template $part () () ($crate::AbstractPart)); };
+    (pub $part:ident) => { $crate::part!(@template $part () (pub) ($crate::AbstractPart)); };
+    ($part:ident in $variant:ty : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::VariantField<$variant, $field>));
+    };
+    (pub $part:ident in $variant:ty : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::VariantField<$variant, $field>));
+    };
+    (pin $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::PinnedField<$field>));
+    };
+    (pub pin $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::PinnedField<$field>));
+    };
+    (shared $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::SharedField<$field>));
+    };
+    (pub shared $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::SharedField<$field>));
+    };
+    (union $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::UnionField<$field>));
+    };
+    (pub union $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::UnionField<$field>));
+    };
+    (unaligned $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::UnalignedField<$field>));
+    };
+    (pub unaligned $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::UnalignedField<$field>));
+    };
+    (lock $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::LockedField<$field>));
+    };
+    (pub lock $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::LockedField<$field>));
+    };
+    (ref_cell $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::RefCellField<$field>));
+    };
+    (pub ref_cell $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::RefCellField<$field>));
+    };
+    (thread_local $part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::ThreadLocalField<$field>));
+        $crate::part!(@thread_local $part ($field));
+    };
+    (pub thread_local $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::ThreadLocalField<$field>));
+        $crate::part!(@thread_local $part ($field));
+    };
+    ($part:ident<> : $field:ty) => { $crate::part!($part : $field); };
+    (pub $part:ident<> : $field:ty) => { $crate::part!(pub $part : $field); };
     ($part:ident<$($lt:lifetime),*> : $field:ty) => {
-        part!(@template $part ($($lt),*) () ($crate::Field<$field>) ($field));
+        $crate::part!(@template $part ($($lt),*) () ($crate::Field<$field>) ($field));
     };
     (pub $part:ident<$($lt:lifetime),*> : $field:ty) => {
-        part!(@template $part ($($lt),*) (pub) ($crate::Field<$field>) ($field));
+        $crate::part!(@template $part ($($lt),*) (pub) ($crate::Field<$field>) ($field));
+    };
+    ($part:ident<$($generics:tt)*) => {
+        $crate::part!(@generics () $part [] [] [] $($generics)*);
+    };
+    (pub $part:ident<$($generics:tt)*) => {
+        $crate::part!(@generics (pub) $part [] [] [] $($generics)*);
+    };
+    ($part:ident : $field:ty) => {
+        $crate::part!(@template $part () () ($crate::Field<$field>));
+    };
+    (pub $part:ident : $field:ty) => {
+        $crate::part!(@template $part () (pub) ($crate::Field<$field>));
+    };
+    (@thread_local $part:ident ($field:ty)) => {
+        impl $crate::ThreadLocalPart for $part {
+            type FieldType = $field;
+
+            fn local_key() -> &'static ::std::thread::LocalKey<::std::cell::RefCell<$field>> {
+                ::std::thread_local! {
+                    static STORAGE: ::std::cell::RefCell<$field> =
+                        ::std::cell::RefCell::new(::std::default::Default::default());
+                }
+                &STORAGE
+            }
+        }
     };
-    ($part:ident : $field:ty) => { part!(@template $part () () ($crate::Field<$field>)); };
-    (pub $part:ident : $field:ty) => { part!(@template $part () (pub) ($crate::Field<$field>)); };
     (@template $part:ident () ($($vis:tt)*) ($($part_type:tt)*)) => {
         #[derive(Default)]
         $($vis)* struct $part;
@@ -28,6 +136,14 @@ macro_rules! part {
 
         impl $crate::PartSpec<$part> for $part {}
 
+        impl $crate::NormalizeNestedPart for $part {
+            type Normalized = $part;
+        }
+
+        impl<NestedPrefix> $crate::AppendNestedPart<NestedPrefix> for $part {
+            type Output = $crate::Nested<NestedPrefix, $part>;
+        }
+
         // TODO maybe constrain InnerPart
         impl<InnerPart: $crate::Part> ::std::ops::BitOr<InnerPart> for $part {
             type Output = $crate::Nested<$part, InnerPart>;
@@ -37,7 +153,10 @@ macro_rules! part {
             }
         }
     };
-    (@template $part:ident ($($lt:lifetime),*) ($($vis:tt)*) ($($part_type:tt)*) ($field:ty)) => {
+    (@template
+        $part:ident ($($lt:lifetime),*) ($($vis:tt)*) ($($part_type:tt)*) ($field:ty)
+        $($spec_lts:tt)*
+    ) => {
         #[derive(Default)]
         $($vis)* struct $part<$($lt),*> {
             phantom: ::std::marker::PhantomData<$field>,
@@ -50,7 +169,15 @@ macro_rules! part {
             type PartType = $($part_type)*;
         }
 
-        part!(@spec_template $part ($($lt),*));
+        $crate::part!(@spec_template $part ($($lt),*) [] [] $($spec_lts)*);
+
+        impl<$($lt),*> $crate::NormalizeNestedPart for $part<$($lt),*> {
+            type Normalized = $part<$($lt),*>;
+        }
+
+        impl<$($lt,)* NestedPrefix> $crate::AppendNestedPart<NestedPrefix> for $part<$($lt),*> {
+            type Output = $crate::Nested<NestedPrefix, $part<$($lt),*>>;
+        }
 
         // TODO maybe constrain InnerPart
         impl<$($lt),*, InnerPart: $crate::Part> ::std::ops::BitOr<InnerPart> for $part<$($lt),*> {
@@ -61,23 +188,158 @@ macro_rules! part {
             }
         }
     };
-    (@spec_template $part:ident ($l:lifetime)) => {
-        impl<'a, 'b>  $crate::PartSpec<$part<'a>> for $part<'b> {}
+    (@generics
+        ($($vis:tt)*) $part:ident
+        [$($lt:lifetime,)*] [$($param:ident,)*] [$($const_param:ident : $const_ty:ty,)*]
+        > : $field:ty
+    ) => {
+        $crate::part!(
+            @generic_template $part ($($lt),*) ($($param),*) ($($const_param: $const_ty),*)
+            ($($vis)*) ($crate::Field<$field>) ($field)
+        );
+    };
+    (@generics $vis:tt $part:ident $lts:tt $params:tt $consts:tt , $($rest:tt)*) => {
+        $crate::part!(@generics $vis $part $lts $params $consts $($rest)*);
+    };
+    (@generics
+        $vis:tt $part:ident $lts:tt $params:tt [$($consts:tt)*]
+        const $const_param:ident : $const_ty:ident $($rest:tt)*
+    ) => {
+        $crate::part!(
+            @generics $vis $part $lts $params [$($consts)* $const_param: $const_ty,] $($rest)*
+        );
     };
-    (@spec_template $part:ident ($l1:lifetime, $l2:lifetime)) => {
-        impl<'a1, 'a2, 'b1, 'b2>  $crate::PartSpec<$part<'a1, 'a2>> for $part<'b1, 'b2> {}
+    (@generics
+        $vis:tt $part:ident [$($lts:tt)*] $params:tt $consts:tt $lt:lifetime $($rest:tt)*
+    ) => {
+        $crate::part!(@generics $vis $part [$($lts)* $lt,] $params $consts $($rest)*);
     };
-    (@spec_template $part:ident ($l1:lifetime, $l2:lifetime, $l3:lifetime)) => {
-        impl<'a1, 'a2, 'a3, 'b1, 'b2, 'b3>
-            $crate::PartSpec<$part<'a1, 'a2, 'a3>>
-        for $part<'b1, 'b2, 'b3> {}
+    (@generics
+        $vis:tt $part:ident $lts:tt [$($params:tt)*] $consts:tt $param:ident $($rest:tt)*
+    ) => {
+        $crate::part!(@generics $vis $part $lts [$($params)* $param,] $consts $($rest)*);
     };
-    (@spec_template $part:ident ($($tt:tt)*)) => {
+    (@generic_template
+        $part:ident ($($lt:lifetime),*) ($($param:ident),*) ($($const_param:ident : $const_ty:ty),*)
+        ($($vis:tt)*) ($($part_type:tt)*) ($field:ty) $($spec_lts:tt)*
+    ) => {
+        $($vis)* struct $part<$($lt,)* $($param,)* $(const $const_param: $const_ty),*> {
+            phantom: ::std::marker::PhantomData<$field>,
+        }
+
+        impl<$($lt,)* $($param,)* $(const $const_param: $const_ty),*> ::std::default::Default
+            for $part<$($lt,)* $($param,)* $($const_param),*>
+        {
+            fn default() -> Self {
+                $part { phantom: ::std::marker::PhantomData }
+            }
+        }
+
+        impl<$($lt,)* $($param,)* $(const $const_param: $const_ty),*> $crate::Part
+            for $part<$($lt,)* $($param,)* $($const_param),*>
+        {
+            type PartType = $($part_type)*;
+        }
+
+        $crate::part!(
+            @spec_template $part ($($lt),*)
+            [$($param,)* $(const $const_param: $const_ty,)*] [$($param,)* $($const_param,)*]
+            $($spec_lts)*
+        );
+
+        impl<$($lt,)* $($param,)* $(const $const_param: $const_ty),*> $crate::NormalizeNestedPart
+            for $part<$($lt,)* $($param,)* $($const_param),*>
+        {
+            type Normalized = $part<$($lt,)* $($param,)* $($const_param),*>;
+        }
+
+        impl<$($lt,)* $($param,)* $(const $const_param: $const_ty,)* NestedPrefix>
+            $crate::AppendNestedPart<NestedPrefix>
+            for $part<$($lt,)* $($param,)* $($const_param),*>
+        {
+            type Output =
+                $crate::Nested<NestedPrefix, $part<$($lt,)* $($param,)* $($const_param),*>>;
+        }
+
+        // TODO maybe constrain InnerPart
+        impl<$($lt,)* $($param,)* $(const $const_param: $const_ty,)* InnerPart: $crate::Part>
+            ::std::ops::BitOr<InnerPart> for $part<$($lt,)* $($param,)* $($const_param),*>
+        {
+            type Output = $crate::Nested<$part<$($lt,)* $($param,)* $($const_param),*>, InnerPart>;
+
+            fn bitor(self, _rhs: InnerPart) -> Self::Output {
+                std::default::Default::default()
+            }
+        }
+    };
+    // Used by the derive, which passes two lists of distinct lifetimes to support any number of
+    // lifetimes.
+    (@spec_template
+        $part:ident ($($lt:lifetime),*) [$($params:tt)*] [$($args:tt)*]
+        ($($a:lifetime),*) ($($b:lifetime),*)
+    ) => {
+        impl<$($a,)* $($b,)* $($params)*>
+            $crate::PartSpec<$part<$($a,)* $($args)*>>
+        for $part<$($b,)* $($args)*> {}
+    };
+    (@spec_template $part:ident () [$($params:tt)*] [$($args:tt)*]) => {
+        impl<$($params)*> $crate::PartSpec<$part<$($args)*>> for $part<$($args)*> {}
+    };
+    (@spec_template $part:ident ($l:lifetime) [$($params:tt)*] [$($args:tt)*]) => {
+        impl<'a, 'b, $($params)*> $crate::PartSpec<$part<'a, $($args)*>> for $part<'b, $($args)*> {}
+    };
+    (@spec_template $part:ident ($l1:lifetime, $l2:lifetime) [$($params:tt)*] [$($args:tt)*]) => {
+        impl<'a1, 'a2, 'b1, 'b2, $($params)*>
+            $crate::PartSpec<$part<'a1, 'a2, $($args)*>>
+        for $part<'b1, 'b2, $($args)*> {}
+    };
+    (@spec_template
+        $part:ident ($l1:lifetime, $l2:lifetime, $l3:lifetime) [$($params:tt)*] [$($args:tt)*]
+    ) => {
+        impl<'a1, 'a2, 'a3, 'b1, 'b2, 'b3, $($params)*>
+            $crate::PartSpec<$part<'a1, 'a2, 'a3, $($args)*>>
+        for $part<'b1, 'b2, 'b3, $($args)*> {}
+    };
+    (@spec_template $part:ident ($($tt:tt)*) [$($params:tt)*] [$($args:tt)*]) => {
         // TODO avoid this limitation
         compile_error!("parts with more than 3 lifetimes are not supported yet");
     };
 }
 
+/// Declares a [`PartToken`](crate::PartToken).
+///
+/// `part_token!(TokenName);` or `part_token!(pub TokenName);` defines a zero-sized token type with a
+/// function `TokenName::take()`, which returns the only value of the token type the first time it is
+/// called and `None` afterwards. The token's abstract part is `TokenPart<TokenName>`.
+#[macro_export]
+macro_rules! part_token {
+    ($token:ident) => { $crate::part_token!(@template $token ()); };
+    (pub $token:ident) => { $crate::part_token!(@template $token (pub)); };
+    (@template $token:ident ($($vis:tt)*)) => {
+        $($vis)* struct $token {
+            _seal: $crate::TokenSeal,
+        }
+
+        impl $token {
+            /// Take the token, returning `None` if it was taken before.
+            #[allow(dead_code)]
+            $($vis)* fn take() -> ::std::option::Option<Self> {
+                static TAKEN: ::std::sync::atomic::AtomicBool =
+                    ::std::sync::atomic::AtomicBool::new(false);
+                if TAKEN.swap(true, ::std::sync::atomic::Ordering::SeqCst) {
+                    ::std::option::Option::None
+                } else {
+                    ::std::option::Option::Some($token {
+                        _seal: unsafe { $crate::TokenSeal::new_unchecked() },
+                    })
+                }
+            }
+        }
+
+        unsafe impl $crate::PartToken for $token {}
+    };
+}
+
 /// Concise syntax for partial reference types.
 ///
 /// The first parameter is the reference target type, optionally preceded by a lifetime. The
@@ -95,26 +357,63 @@ macro_rules! part {
 macro_rules! partial {
     ($target_lt:lifetime $target:ty) => { $crate::Ref<$target_lt, $target> };
     ($target_lt:lifetime $target:ty, $($parts:tt)*) => {
-        partial!(@extend $crate::Ref<$target_lt, $target>, $($parts)*)
+        $crate::partial!(@extend $crate::Ref<$target_lt, $target>, $($parts)*)
     };
     ($target:ty) => { $crate::Ref<$target> };
     ($target:ty, $($parts:tt)* ) => {
-        partial!(@extend $crate::Ref<$target>, $($parts)*)
+        $crate::partial!(@extend $crate::Ref<$target>, $($parts)*)
     };
     (@extend $target:ty) => {$target};
     (@extend $target:ty, ) => {$target};
     (@extend $target:ty, mut $($part:ty)|* , $($rest:tt)*) => {
-        partial!(@extend $crate::Mut<$crate::nested_part!($($part)|*), $target>, $($rest)*)
+        $crate::partial!(
+            @extend $crate::Mut<$crate::nested_part!($($part)|*), $target>, $($rest)*
+        )
     };
     (@extend $target:ty, mut $($part:ty)|*) => {
-        partial!(@extend $target, mut $($part)|*, )
+        $crate::partial!(@extend $target, mut $($part)|*, )
     };
     (@extend $target:ty, $($part:ty)|* , $($rest:tt)*) => {
-        partial!(@extend $crate::Const<$crate::nested_part!($($part)|*), $target>, $($rest)*)
+        $crate::partial!(
+            @extend $crate::Const<$crate::nested_part!($($part)|*), $target>, $($rest)*
+        )
     };
     (@extend $target:ty, $($part:ty)|*) => {
-        partial!(@extend $target, $($part)|*, )
+        $crate::partial!(@extend $target, $($part)|*, )
+    };
+}
+
+/// Concise syntax for [`PartSet`](crate::PartSet) types.
+///
+/// The parameters are the parts of the set, using the same syntax as the parts passed to
+/// [`partial`]. A partial reference having the parts of a set is given by
+/// [`PartialRefFor`](crate::PartialRefFor).
+///
+/// Examples:
+///   * `part_set!()` is `EmptyParts`
+///   * `part_set!(PartA, mut PartB)` is `WithMut<PartB, WithConst<PartA, EmptyParts>>`
+///   * `part_set!(mut PartA | PartB)` is `WithMut<Nested<PartA, PartB>, EmptyParts>`
+#[macro_export]
+macro_rules! part_set {
+    (@extend $set:ty) => {$set};
+    (@extend $set:ty, ) => {$set};
+    (@extend $set:ty, mut $($part:ty)|* , $($rest:tt)*) => {
+        $crate::part_set!(
+            @extend $crate::WithMut<$crate::nested_part!($($part)|*), $set>, $($rest)*
+        )
+    };
+    (@extend $set:ty, mut $($part:ty)|*) => {
+        $crate::part_set!(@extend $set, mut $($part)|*, )
+    };
+    (@extend $set:ty, $($part:ty)|* , $($rest:tt)*) => {
+        $crate::part_set!(
+            @extend $crate::WithConst<$crate::nested_part!($($part)|*), $set>, $($rest)*
+        )
+    };
+    (@extend $set:ty, $($part:ty)|*) => {
+        $crate::part_set!(@extend $set, $($part)|*, )
     };
+    ($($parts:tt)*) => { $crate::part_set!(@extend $crate::EmptyParts, $($parts)*) };
 }
 
 /// Expands `A | B | ... | Z` to `Nested<...Nested<A, B>, ..., Z>`
@@ -128,7 +427,7 @@ macro_rules! nested_part {
         $crate::Nested<$target , $part>
     };
     ($target:ty | $part:ty | $($subparts:ty)|*) => {
-        nested_part!($crate::Nested<$target , $part> | $($subparts)|*)
+        $crate::nested_part!($crate::Nested<$target , $part> | $($subparts)|*)
     };
 }
 
@@ -145,10 +444,10 @@ macro_rules! nested_part {
 #[macro_export]
 macro_rules! split_borrow {
     ($target:ident = &($($parts:tt)*) $from:ident) => {
-        split_borrow!($target, $from = &($($parts)*) $from)
+        $crate::split_borrow!($target, $from = &($($parts)*) $from)
     };
     ($target:ident, $rest:ident = &($($parts:tt)*) $from:expr) => {
         #[allow(unused_variables, unused_mut)]
-        let (mut $target, mut $rest) = ($from).split_borrow::<partial!(_, $($parts)*), _>();
+        let (mut $target, mut $rest) = ($from).split_borrow::<$crate::partial!(_, $($parts)*), _>();
     };
 }
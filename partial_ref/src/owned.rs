@@ -0,0 +1,77 @@
+//! Partial references owning their target.
+
+use std::marker::PhantomData;
+
+use crate::{
+    FullRefTarget, HasSubset, PartSet, PartSetRef, PartialRef, PartialRefFor, PartialRefTarget,
+    RawTargetOf,
+};
+
+/// An owned target that can only be accessed through the parts of a part set.
+///
+/// This owns a boxed target `Owner = Box<Target>` and hands out partial references having the
+/// parts of the [`PartSet`] `Parts`, as in `OwnedPartialRef<Box<Graph>, part_set!(mut Colors,
+/// Weights)>`. As it doesn't borrow the target, it can be moved into `'static` tasks, e.g. closures
+/// run by a thread pool, while still restricting the task to the declared parts.
+pub struct OwnedPartialRef<Owner, Parts: PartSet> {
+    owner: Owner,
+    phantom: PhantomData<Parts>,
+}
+
+impl<Target, Parts> OwnedPartialRef<Box<Target>, Parts>
+where
+    Target: PartialRefTarget,
+    Target::RawTarget: RawTargetOf<Target>,
+    Parts: PartSet,
+{
+    /// Take ownership of a boxed target.
+    ///
+    /// The parts of `Parts` have to be parts of `Target` that can be borrowed together from a
+    /// partial reference having all parts (see [`FullRefTarget`]). The `SubsetIndex` type can be
+    /// inferred.
+    #[inline(always)]
+    pub fn new<SubsetIndex>(owner: Box<Target>) -> Self
+    where
+        Target: FullRefTarget<'static> + 'static,
+        Parts: PartSetRef<'static, Target>,
+        Target::FullRef: HasSubset<'static, PartialRefFor<'static, Target, Parts>, SubsetIndex>,
+    {
+        OwnedPartialRef {
+            owner,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Access the target with all parts of `Parts` as constant parts.
+    #[inline(always)]
+    pub fn as_partial_ref<'a>(&'a self) -> PartialRefFor<'a, Target, Parts::ConstSet>
+    where
+        Parts::ConstSet: PartSetRef<'a, Target>,
+    {
+        let ptr = &*self.owner as *const Target as *mut Target;
+        unsafe {
+            <PartialRefFor<'a, Target, Parts::ConstSet> as PartialRef<'a>>::from_raw(
+                Target::RawTarget::from_target_ptr(ptr),
+            )
+        }
+    }
+
+    /// Access the target with the parts of `Parts`.
+    #[inline(always)]
+    pub fn as_partial_ref_mut<'a>(&'a mut self) -> PartialRefFor<'a, Target, Parts>
+    where
+        Parts: PartSetRef<'a, Target>,
+    {
+        unsafe {
+            <PartialRefFor<'a, Target, Parts> as PartialRef<'a>>::from_raw(
+                Target::RawTarget::from_target_ptr(&mut *self.owner),
+            )
+        }
+    }
+
+    /// Give up the restriction to the parts of `Parts` and return the boxed target.
+    #[inline(always)]
+    pub fn into_inner(self) -> Box<Target> {
+        self.owner
+    }
+}
@@ -0,0 +1,226 @@
+//! Pairs of partial references to two different targets.
+
+use crate::{
+    FieldPartType, HasPart, HasSubset, HasTarget, PartSpec, PartialRef, PluckConst, PluckMut,
+};
+
+/// A pair of partial references, usually to two different targets.
+///
+/// This allows passing parts of two targets as a single value. The parts of the left and right
+/// reference are selected by wrapping them in [`Left`] and [`Right`] respectively, e.g.
+/// `pair.part(Left(Colors))`. Like a partial reference, a pair can be re-borrowed and split, which
+/// is done for both sides at once.
+pub struct PartialPair<LeftRef, RightRef> {
+    left: LeftRef,
+    right: RightRef,
+}
+
+/// Selects a part of the left reference of a [`PartialPair`].
+#[derive(Copy, Clone, Default)]
+pub struct Left<FieldPartSpec>(pub FieldPartSpec);
+
+/// Selects a part of the right reference of a [`PartialPair`].
+#[derive(Copy, Clone, Default)]
+pub struct Right<FieldPartSpec>(pub FieldPartSpec);
+
+/// *(internal)* A part of a [`PartialPair`], selected by [`Left`] or [`Right`].
+///
+/// The `FieldPart` and `PartIndex` types can be inferred.
+pub trait PairPart<'a, LeftRef, RightRef, FieldPart, PartIndex> {
+    /// The type of the selected field.
+    type FieldType: ?Sized + 'a;
+
+    /// Access the selected field.
+    fn pair_part(pair: &'a PartialPair<LeftRef, RightRef>) -> &'a Self::FieldType;
+}
+
+/// *(internal)* A part of a [`PartialPair`] that can be accessed mutably.
+///
+/// The `FieldPart` and `PartIndex` types can be inferred.
+pub trait PairPartMut<'a, LeftRef, RightRef, FieldPart, PartIndex> {
+    /// The type of the selected field.
+    type FieldType: ?Sized + 'a;
+
+    /// Mutable access to the selected field.
+    fn pair_part_mut(pair: &'a mut PartialPair<LeftRef, RightRef>) -> &'a mut Self::FieldType;
+}
+
+impl<'a, FieldPartSpec, FieldPart, PartIndex, LeftRef, RightRef>
+    PairPart<'a, LeftRef, RightRef, FieldPart, PartIndex> for Left<FieldPartSpec>
+where
+    FieldPartSpec: PartSpec<FieldPart>,
+    FieldPart: FieldPartType,
+    FieldPart::FieldType: 'a,
+    LeftRef: PluckConst<'a, FieldPart, PartIndex>,
+    LeftRef::Target: HasPart<FieldPart> + 'a,
+{
+    type FieldType = FieldPart::FieldType;
+
+    #[inline(always)]
+    fn pair_part(pair: &'a PartialPair<LeftRef, RightRef>) -> &'a Self::FieldType {
+        pair.left.part_of::<FieldPart, PartIndex>()
+    }
+}
+
+impl<'a, FieldPartSpec, FieldPart, PartIndex, LeftRef, RightRef>
+    PairPart<'a, LeftRef, RightRef, FieldPart, PartIndex> for Right<FieldPartSpec>
+where
+    FieldPartSpec: PartSpec<FieldPart>,
+    FieldPart: FieldPartType,
+    FieldPart::FieldType: 'a,
+    RightRef: PluckConst<'a, FieldPart, PartIndex>,
+    RightRef::Target: HasPart<FieldPart> + 'a,
+{
+    type FieldType = FieldPart::FieldType;
+
+    #[inline(always)]
+    fn pair_part(pair: &'a PartialPair<LeftRef, RightRef>) -> &'a Self::FieldType {
+        pair.right.part_of::<FieldPart, PartIndex>()
+    }
+}
+
+impl<'a, FieldPartSpec, FieldPart, PartIndex, LeftRef, RightRef>
+    PairPartMut<'a, LeftRef, RightRef, FieldPart, PartIndex> for Left<FieldPartSpec>
+where
+    FieldPartSpec: PartSpec<FieldPart>,
+    FieldPart: FieldPartType,
+    FieldPart::FieldType: 'a,
+    LeftRef: PluckMut<'a, FieldPart, PartIndex>,
+    LeftRef::Target: HasPart<FieldPart> + 'a,
+{
+    type FieldType = FieldPart::FieldType;
+
+    #[inline(always)]
+    fn pair_part_mut(pair: &'a mut PartialPair<LeftRef, RightRef>) -> &'a mut Self::FieldType {
+        pair.left.part_mut_of::<FieldPart, PartIndex>()
+    }
+}
+
+impl<'a, FieldPartSpec, FieldPart, PartIndex, LeftRef, RightRef>
+    PairPartMut<'a, LeftRef, RightRef, FieldPart, PartIndex> for Right<FieldPartSpec>
+where
+    FieldPartSpec: PartSpec<FieldPart>,
+    FieldPart: FieldPartType,
+    FieldPart::FieldType: 'a,
+    RightRef: PluckMut<'a, FieldPart, PartIndex>,
+    RightRef::Target: HasPart<FieldPart> + 'a,
+{
+    type FieldType = FieldPart::FieldType;
+
+    #[inline(always)]
+    fn pair_part_mut(pair: &'a mut PartialPair<LeftRef, RightRef>) -> &'a mut Self::FieldType {
+        pair.right.part_mut_of::<FieldPart, PartIndex>()
+    }
+}
+
+impl<LeftRef: HasTarget, RightRef: HasTarget> PartialPair<LeftRef, RightRef> {
+    /// Pair two partial references.
+    #[inline(always)]
+    pub fn new(left: LeftRef, right: RightRef) -> Self {
+        PartialPair { left, right }
+    }
+
+    /// Split into the two paired partial references.
+    #[inline(always)]
+    pub fn into_inner(self) -> (LeftRef, RightRef) {
+        (self.left, self.right)
+    }
+
+    /// Mutable access to the left partial reference.
+    #[inline(always)]
+    pub fn left(&mut self) -> &mut LeftRef {
+        &mut self.left
+    }
+
+    /// Mutable access to the right partial reference.
+    #[inline(always)]
+    pub fn right(&mut self) -> &mut RightRef {
+        &mut self.right
+    }
+
+    /// Access a part of either reference.
+    ///
+    /// The part has to be wrapped in [`Left`] or [`Right`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn part<'a, SidePart, FieldPart, PartIndex>(
+        &'a self,
+        _part: SidePart,
+    ) -> &'a SidePart::FieldType
+    where
+        SidePart: PairPart<'a, LeftRef, RightRef, FieldPart, PartIndex>,
+    {
+        SidePart::pair_part(self)
+    }
+
+    /// Mutable access to a part of either reference.
+    ///
+    /// The part has to be wrapped in [`Left`] or [`Right`].
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn part_mut<'a, SidePart, FieldPart, PartIndex>(
+        &'a mut self,
+        _part: SidePart,
+    ) -> &'a mut SidePart::FieldType
+    where
+        SidePart: PairPartMut<'a, LeftRef, RightRef, FieldPart, PartIndex>,
+    {
+        SidePart::pair_part_mut(self)
+    }
+
+    /// Partially re-borrows both references.
+    ///
+    /// This is equivalent to [`PartialRef::borrow`] for each reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[inline(always)]
+    pub fn borrow<'a, LeftBorrowed, RightBorrowed, LeftIndex, RightIndex>(
+        &'a mut self,
+    ) -> PartialPair<LeftBorrowed, RightBorrowed>
+    where
+        LeftRef: HasSubset<'a, LeftBorrowed, LeftIndex>,
+        LeftBorrowed: PartialRef<'a, Target = LeftRef::Target>,
+        RightRef: HasSubset<'a, RightBorrowed, RightIndex>,
+        RightBorrowed: PartialRef<'a, Target = RightRef::Target>,
+    {
+        PartialPair {
+            left: self.left.borrow(),
+            right: self.right.borrow(),
+        }
+    }
+
+    /// Partially re-borrows both references, splitting off the remaining parts.
+    ///
+    /// This is equivalent to [`PartialRef::split_borrow`] for each reference.
+    ///
+    /// Usually the type parameters can be inferred.
+    #[allow(clippy::type_complexity)]
+    #[inline(always)]
+    pub fn split_borrow<'a, LeftBorrowed, RightBorrowed, LeftIndex, RightIndex>(
+        &'a mut self,
+    ) -> (
+        PartialPair<LeftBorrowed, RightBorrowed>,
+        PartialPair<
+            <LeftRef as HasSubset<'a, LeftBorrowed, LeftIndex>>::Remainder,
+            <RightRef as HasSubset<'a, RightBorrowed, RightIndex>>::Remainder,
+        >,
+    )
+    where
+        LeftRef: HasSubset<'a, LeftBorrowed, LeftIndex>,
+        LeftBorrowed: PartialRef<'a, Target = LeftRef::Target>,
+        RightRef: HasSubset<'a, RightBorrowed, RightIndex>,
+        RightBorrowed: PartialRef<'a, Target = RightRef::Target>,
+    {
+        let (left, left_rest) = self.left.split_borrow();
+        let (right, right_rest) = self.right.split_borrow();
+        (
+            PartialPair { left, right },
+            PartialPair {
+                left: left_rest,
+                right: right_rest,
+            },
+        )
+    }
+}
@@ -0,0 +1,412 @@
+//! Type-level sets of parts.
+
+use std::marker::PhantomData;
+
+use crate::{
+    AccessConst, AccessMut, Const, HasPart, HasTarget, IndexHere, IndexNext, Mut, Part, PartialRef,
+    PartialRefTarget, Ref,
+};
+
+/// A type-level set of parts, each of which is either constant or mutable.
+///
+/// Part sets are built from [`EmptyParts`], [`WithConst`] and [`WithMut`] using the same nesting
+/// as [`Ref`], [`Const`] and [`Mut`], but without a target or a lifetime. This allows naming the
+/// parts of a partial reference in generic code without matching on the nesting of a partial
+/// reference type. A part set is usually written using the [`part_set`](crate::part_set) macro.
+///
+/// The partial reference having the parts of a set is given by [`PartSetRef`] and the part set of a
+/// partial reference by [`PartsOf`].
+pub unsafe trait PartSet {
+    /// The part set having the same parts as `Self`, but only as constant parts.
+    type ConstSet: PartSet;
+
+    /// The number of parts in this set.
+    const LEN: usize;
+}
+
+/// The part set without any parts.
+pub struct EmptyParts;
+
+/// A part set extended by a constant part.
+pub struct WithConst<SomePart, Tail: PartSet>(PhantomData<(SomePart, Tail)>);
+
+/// A part set extended by a mutable part.
+pub struct WithMut<SomePart, Tail: PartSet>(PhantomData<(SomePart, Tail)>);
+
+unsafe impl PartSet for EmptyParts {
+    type ConstSet = EmptyParts;
+
+    const LEN: usize = 0;
+}
+
+unsafe impl<SomePart: Part, Tail: PartSet> PartSet for WithConst<SomePart, Tail> {
+    type ConstSet = WithConst<SomePart, Tail::ConstSet>;
+
+    const LEN: usize = 1 + Tail::LEN;
+}
+
+unsafe impl<SomePart: Part, Tail: PartSet> PartSet for WithMut<SomePart, Tail> {
+    type ConstSet = WithConst<SomePart, Tail::ConstSet>;
+
+    const LEN: usize = 1 + Tail::LEN;
+}
+
+/// The partial reference to `Target` with lifetime `'a` having the parts of a part set.
+///
+/// Usually this is used through the [`PartialRefFor`] alias.
+pub unsafe trait PartSetRef<'a, Target: PartialRefTarget + ?Sized + 'a>: PartSet {
+    /// The partial reference having the parts of `Self`.
+    type Ref: PartialRef<'a, Target = Target>;
+}
+
+unsafe impl<'a, Target: PartialRefTarget + ?Sized + 'a> PartSetRef<'a, Target> for EmptyParts {
+    type Ref = Ref<'a, Target>;
+}
+
+unsafe impl<'a, Target, SomePart, Tail> PartSetRef<'a, Target> for WithConst<SomePart, Tail>
+where
+    Target: PartialRefTarget + HasPart<SomePart> + ?Sized + 'a,
+    SomePart: Part,
+    Tail: PartSetRef<'a, Target>,
+{
+    type Ref = Const<SomePart, Tail::Ref>;
+}
+
+unsafe impl<'a, Target, SomePart, Tail> PartSetRef<'a, Target> for WithMut<SomePart, Tail>
+where
+    Target: PartialRefTarget + HasPart<SomePart> + ?Sized + 'a,
+    SomePart: Part,
+    Tail: PartSetRef<'a, Target>,
+{
+    type Ref = Mut<SomePart, Tail::Ref>;
+}
+
+/// The partial reference to `Target` with lifetime `'a` having the parts of the part set `Parts`.
+///
+/// `PartialRefFor<'a, Graph, part_set!(mut Colors, Weights)>` is the same type as
+/// `partial!('a Graph, mut Colors, Weights)`.
+pub type PartialRefFor<'a, Target, Parts> = <Parts as PartSetRef<'a, Target>>::Ref;
+
+/// The part set of a partial reference.
+pub unsafe trait PartsOf: HasTarget {
+    /// The part set having the same parts as `Self`.
+    type Parts: PartSet;
+}
+
+unsafe impl<'a, Target: PartialRefTarget + ?Sized> PartsOf for Ref<'a, Target> {
+    type Parts = EmptyParts;
+}
+
+unsafe impl<'a, SomePart, Reference> PartsOf for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: PartialRef<'a> + PartsOf,
+    Reference::Target: HasPart<SomePart>,
+{
+    type Parts = WithConst<SomePart, Reference::Parts>;
+}
+
+unsafe impl<'a, SomePart, Reference> PartsOf for Mut<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: PartialRef<'a> + PartsOf,
+    Reference::Target: HasPart<SomePart>,
+{
+    type Parts = WithMut<SomePart, Reference::Parts>;
+}
+
+/// A target with a canonical order of its parts.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget. The
+/// canonical order is the order in which the parts are listed in
+/// [`PartMetadata`](crate::PartMetadata).
+pub unsafe trait CanonicalTarget: PartialRefTarget {
+    /// *(internal)* A list of the parts of the target, starting with the last part, as nested
+    /// pairs ending in `()`.
+    type CanonicalOrder;
+}
+
+/// *(internal)* The position of a part in the canonical order of the parts of `Target`.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget.
+pub unsafe trait CanonicalPart<Target: ?Sized>: Part {
+    /// The position as [`IndexHere`] or [`IndexNext`], which is the index of the part in the list
+    /// of parts of [`PartMetadata`](crate::PartMetadata).
+    type Position;
+}
+
+/// *(internal)* The index of a position in the list of parts of
+/// [`PartMetadata`](crate::PartMetadata).
+pub trait PositionIndex {
+    const INDEX: usize;
+}
+
+impl PositionIndex for IndexHere {
+    const INDEX: usize = 0;
+}
+
+impl<Index: PositionIndex> PositionIndex for IndexNext<Index> {
+    const INDEX: usize = Index::INDEX + 1;
+}
+
+/// *(internal)* Marks a part that is not contained in a partial reference.
+pub struct AccessNone;
+
+/// *(internal)* Select `Equal` or `Unequal` depending on whether `Other` is the same position.
+pub trait ComparePositions<Other, Equal, Unequal> {
+    /// Either `Equal` or `Unequal`.
+    type Output;
+}
+
+impl<Equal, Unequal> ComparePositions<IndexHere, Equal, Unequal> for IndexHere {
+    type Output = Equal;
+}
+
+impl<Index, Equal, Unequal> ComparePositions<IndexNext<Index>, Equal, Unequal> for IndexHere {
+    type Output = Unequal;
+}
+
+impl<Index, Equal, Unequal> ComparePositions<IndexHere, Equal, Unequal> for IndexNext<Index> {
+    type Output = Unequal;
+}
+
+impl<Index, OtherIndex, Equal, Unequal> ComparePositions<IndexNext<OtherIndex>, Equal, Unequal>
+    for IndexNext<Index>
+where
+    Index: ComparePositions<OtherIndex, Equal, Unequal>,
+{
+    type Output = Index::Output;
+}
+
+/// *(internal)* How the part `SomePart` of `Target` is contained in the partial reference having
+/// this trait.
+///
+/// This is only implemented when all parts of the partial reference have a canonical position.
+pub unsafe trait PartAccess<SomePart, Target: ?Sized> {
+    /// One of [`AccessNone`], [`AccessConst`] or [`AccessMut`].
+    type Access;
+}
+
+unsafe impl<'a, SomePart, Target> PartAccess<SomePart, Target> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+{
+    type Access = AccessNone;
+}
+
+unsafe impl<SomePart, Target, OtherPart, Reference> PartAccess<SomePart, Target>
+    for Const<OtherPart, Reference>
+where
+    Target: ?Sized,
+    SomePart: CanonicalPart<Target>,
+    OtherPart: CanonicalPart<Target>,
+    Reference: HasTarget + PartAccess<SomePart, Target>,
+    OtherPart::Position: ComparePositions<SomePart::Position, AccessConst, Reference::Access>,
+{
+    type Access = <OtherPart::Position as ComparePositions<
+        SomePart::Position,
+        AccessConst,
+        Reference::Access,
+    >>::Output;
+}
+
+unsafe impl<SomePart, Target, OtherPart, Reference> PartAccess<SomePart, Target>
+    for Mut<OtherPart, Reference>
+where
+    Target: ?Sized,
+    SomePart: CanonicalPart<Target>,
+    OtherPart: CanonicalPart<Target>,
+    Reference: HasTarget + PartAccess<SomePart, Target>,
+    OtherPart::Position: ComparePositions<SomePart::Position, AccessMut, Reference::Access>,
+{
+    type Access = <OtherPart::Position as ComparePositions<
+        SomePart::Position,
+        AccessMut,
+        Reference::Access,
+    >>::Output;
+}
+
+/// *(internal)* Add a part with the access `Self` to the part set `Tail`.
+pub trait AddPartAccess<SomePart, Tail: PartSet> {
+    /// The extended part set.
+    type Output: PartSet;
+}
+
+impl<SomePart, Tail: PartSet> AddPartAccess<SomePart, Tail> for AccessNone {
+    type Output = Tail;
+}
+
+impl<SomePart: Part, Tail: PartSet> AddPartAccess<SomePart, Tail> for AccessConst {
+    type Output = WithConst<SomePart, Tail>;
+}
+
+impl<SomePart: Part, Tail: PartSet> AddPartAccess<SomePart, Tail> for AccessMut {
+    type Output = WithMut<SomePart, Tail>;
+}
+
+/// *(internal)* The part set containing the parts of `Reference` in the order of the list of
+/// parts having this trait.
+pub unsafe trait CanonicalParts<Reference> {
+    /// The part set in canonical order.
+    type Parts: PartSet;
+}
+
+unsafe impl<Reference> CanonicalParts<Reference> for () {
+    type Parts = EmptyParts;
+}
+
+unsafe impl<Reference, SomePart, Tail> CanonicalParts<Reference> for (SomePart, Tail)
+where
+    Reference: HasTarget + PartAccess<SomePart, <Reference as HasTarget>::Target>,
+    Tail: CanonicalParts<Reference>,
+    Reference::Access: AddPartAccess<SomePart, Tail::Parts>,
+{
+    type Parts = <Reference::Access as AddPartAccess<SomePart, Tail::Parts>>::Output;
+}
+
+/// A partial reference with the same parts listed in canonical order.
+///
+/// Partial references having the same parts in a different order have the same canonical
+/// reference. This is only implemented when all parts of the partial reference are parts declared
+/// for the target, in particular nested parts are not supported.
+pub unsafe trait Canonicalize<'a>: PartialRef<'a> {
+    /// The partial reference having the parts of `Self` in canonical order.
+    type Canonical: PartialRef<'a, Target = Self::Target>;
+}
+
+unsafe impl<'a, Reference> Canonicalize<'a> for Reference
+where
+    Reference: PartialRef<'a>,
+    <Reference as HasTarget>::Target: CanonicalTarget + 'a,
+    <<Reference as HasTarget>::Target as CanonicalTarget>::CanonicalOrder:
+        CanonicalParts<Reference>,
+    <<<Reference as HasTarget>::Target as CanonicalTarget>::CanonicalOrder as CanonicalParts<
+        Reference,
+    >>::Parts: PartSetRef<'a, <Reference as HasTarget>::Target>,
+{
+    type Canonical = PartialRefFor<
+        'a,
+        <Reference as HasTarget>::Target,
+        <<<Reference as HasTarget>::Target as CanonicalTarget>::CanonicalOrder as CanonicalParts<
+            Reference,
+        >>::Parts,
+    >;
+}
+
+/// The partial reference having the parts of `Reference` in canonical order.
+///
+/// `CanonicalRef<'a, partial!('a Graph, mut Colors, Weights)>` and
+/// `CanonicalRef<'a, partial!('a Graph, Weights, mut Colors)>` are the same type.
+pub type CanonicalRef<'a, Reference> = <Reference as Canonicalize<'a>>::Canonical;
+
+/// *(internal)* The remainder of a part `SomePart` of a partial reference after splitting off a
+/// subset with the access `Self` to that part, followed by the remaining parts `Rest`.
+pub trait RemainderAccess<SomePart, Rest> {
+    /// The part followed by `Rest`, or only `Rest` if the part was split off.
+    type Output;
+}
+
+impl<SomePart, Rest: HasTarget> RemainderAccess<SomePart, Rest> for AccessNone {
+    type Output = Mut<SomePart, Rest>;
+}
+
+impl<SomePart, Rest: HasTarget> RemainderAccess<SomePart, Rest> for AccessConst {
+    type Output = Const<SomePart, Rest>;
+}
+
+impl<SomePart, Rest> RemainderAccess<SomePart, Rest> for AccessMut {
+    type Output = Rest;
+}
+
+/// Computes the partial reference left after splitting off the partial reference `Subset`.
+///
+/// The remainder is the same type that is returned by [`split_borrow`](PartialRef::split_borrow),
+/// but it is determined by `Subset` alone without any index parameters. Like [`Canonicalize`],
+/// this is only implemented when all parts of `Subset` are parts declared for the target, in
+/// particular nested parts are not supported.
+pub unsafe trait RemainderParts<'a, Subset>: PartialRef<'a> {
+    /// The partial reference left after splitting off `Subset`.
+    type Remainder: PartialRef<'a, Target = Self::Target>;
+}
+
+unsafe impl<'a, Target, Subset> RemainderParts<'a, Subset> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+{
+    type Remainder = Self;
+}
+
+unsafe impl<'a, SomePart, Reference, Subset> RemainderParts<'a, Subset>
+    for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: RemainderParts<'a, Subset>,
+    Reference::Target: HasPart<SomePart>,
+{
+    type Remainder = Const<SomePart, Reference::Remainder>;
+}
+
+unsafe impl<'a, SomePart, Reference, Subset> RemainderParts<'a, Subset> for Mut<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: RemainderParts<'a, Subset>,
+    Reference::Target: HasPart<SomePart>,
+    Subset: PartAccess<SomePart, Reference::Target>,
+    Subset::Access: RemainderAccess<SomePart, Reference::Remainder>,
+    <Subset::Access as RemainderAccess<SomePart, Reference::Remainder>>::Output:
+        PartialRef<'a, Target = Reference::Target>,
+{
+    type Remainder = <Subset::Access as RemainderAccess<SomePart, Reference::Remainder>>::Output;
+}
+
+/// The partial reference left after splitting off the partial reference `Subset` from the partial
+/// reference `Reference`.
+///
+/// This allows naming the remainder of [`split_borrow`](PartialRef::split_borrow) in struct fields
+/// and return types, e.g. `RemainderOf<'a, partial!('a Graph, mut Colors, Weights),
+/// partial!('a Graph, mut Colors)>` is `partial!('a Graph, Weights)`.
+pub type RemainderOf<'a, Reference, Subset> = <Reference as RemainderParts<'a, Subset>>::Remainder;
+
+/// A type-level function applied to each part of a partial reference by [`FoldParts`].
+///
+/// `Access` is [`AccessConst`] or [`AccessMut`] depending on whether the part is a constant or a
+/// mutable part and `Acc` is the result for the preceding parts.
+pub trait PartFolder<SomePart, Access, Acc> {
+    /// The result after folding this part.
+    type Output;
+}
+
+/// Fold over the parts of a partial reference at the type level.
+///
+/// Starting with `Init`, the [`PartFolder`] `Folder` is applied to each part in the order in which
+/// the parts are listed in [`partial!`](crate::partial). The result is a type, but a constant can
+/// be computed by implementing a trait with an associated constant for the possible results. This
+/// allows computing properties of partial reference types in generic code, e.g. counting the
+/// mutable parts.
+pub trait FoldParts<Folder, Init> {
+    /// The result of the fold.
+    type Output;
+}
+
+impl<'a, Target, Folder, Init> FoldParts<Folder, Init> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+{
+    type Output = Init;
+}
+
+impl<SomePart, Reference, Folder, Init> FoldParts<Folder, Init> for Const<SomePart, Reference>
+where
+    Reference: HasTarget + FoldParts<Folder, Init>,
+    Folder: PartFolder<SomePart, AccessConst, Reference::Output>,
+{
+    type Output = Folder::Output;
+}
+
+impl<SomePart, Reference, Folder, Init> FoldParts<Folder, Init> for Mut<SomePart, Reference>
+where
+    Reference: HasTarget + FoldParts<Folder, Init>,
+    Folder: PartFolder<SomePart, AccessMut, Reference::Output>,
+{
+    type Output = Folder::Output;
+}
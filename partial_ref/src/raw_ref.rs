@@ -0,0 +1,76 @@
+//! Partial references with an erased lifetime.
+
+use std::marker::PhantomData;
+
+use crate::{ClaimParts, HasTarget, PartClaim, PartialRef, PartialRefTarget, ShortenParts};
+
+/// A partial reference without a lifetime.
+///
+/// This stores the pointer of a partial reference of type `Reference` without borrowing the
+/// referenced value, so it can be kept where lifetimes can't be expressed, e.g. in callback
+/// registries or as user data passed through foreign code. The lifetime of `Reference` is
+/// irrelevant and usually `'static`, as in `RawPartialRef<partial!('static Graph, mut Colors)>`.
+///
+/// A usable partial reference is recovered using the unsafe [`revive`](RawPartialRef::revive).
+pub struct RawPartialRef<Reference: HasTarget> {
+    ptr: *mut <Reference::Target as PartialRefTarget>::RawTarget,
+    phantom: PhantomData<*const Reference>,
+}
+
+impl<Reference: HasTarget> RawPartialRef<Reference> {
+    /// Erase the lifetime of a partial reference.
+    ///
+    /// The partial reference `reference` must have the same parts as `Reference`.
+    #[inline(always)]
+    pub fn new<'a, Source>(reference: Source) -> Self
+    where
+        Source: ShortenParts<'a, Target = <Reference as HasTarget>::Target>,
+        Reference: ShortenParts<'a, ShortRef = <Source as ShortenParts<'a>>::ShortRef>,
+    {
+        RawPartialRef {
+            ptr: reference.get_raw(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Recover a partial reference with the lifetime `'b`.
+    ///
+    /// # Safety
+    /// The referenced value must be valid for the lifetime `'b` and it must be possible to access
+    /// the parts of `Reference` for that lifetime, as with the partial reference this was created
+    /// from. In particular no other reference may access a mutable part during that lifetime.
+    #[inline(always)]
+    pub unsafe fn revive<'b>(self) -> Reference::ShortRef
+    where
+        Reference: ShortenParts<'b>,
+    {
+        <Reference::ShortRef as PartialRef<'b>>::from_raw(self.ptr)
+    }
+
+    /// Claim the parts of `Reference` for the referenced value.
+    ///
+    /// Keeping the returned claim alive while using the result of [`revive`](RawPartialRef::revive)
+    /// checks for conflicting claims in debug builds, see [`PartClaim`].
+    #[inline(always)]
+    pub fn claim(&self) -> PartClaim
+    where
+        Reference: ClaimParts,
+    {
+        PartClaim::new::<Reference>(self.ptr)
+    }
+
+    /// The pointer to the referenced value.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *mut <Reference::Target as PartialRefTarget>::RawTarget {
+        self.ptr
+    }
+}
+
+impl<Reference: HasTarget> Copy for RawPartialRef<Reference> {}
+
+impl<Reference: HasTarget> Clone for RawPartialRef<Reference> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
@@ -0,0 +1,319 @@
+//! Serialization of the parts of partial references, enabled by the `serde` feature.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeOwned, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::{Const, Field, HasPart, HasTarget, Mut, Part, PartialRef, PartialRefTarget, Ref};
+
+/// Implemented when a field part of a reference target can be serialized by name.
+///
+/// Implementations for this are automatically created when deriving PartialRefTarget with the
+/// `serde` feature enabled. They exist for the parts of struct fields, including element parts and
+/// parts of dereferenced fields, but not for the parts of pinned fields, enum variant fields, union
+/// fields or fields of packed structs.
+pub trait SerdePart<FieldPart: Part>: HasPart<FieldPart> {
+    /// The name of the part, as listed in [`PartMetadata`](crate::PartMetadata).
+    ///
+    /// This is used as the key of the part's field when serializing.
+    const NAME: &'static str;
+}
+
+/// Serialize the fields of the parts of a partial reference.
+///
+/// The fields are serialized as a map from the part names given by [`SerdePart`] to the values of
+/// the fields, in the order in which the parts are listed in the [`partial!`](crate::partial)
+/// type. All parts of the partial reference have to be [`Field`] parts of fields that implement
+/// `Serialize`. Partial references implementing this also implement `Serialize`.
+pub unsafe trait SerializeParts<'a>: PartialRef<'a> {
+    /// *(internal)* The number of serialized parts.
+    const SERIALIZED_PARTS: usize;
+
+    /// *(internal)* Serialize the field of each part as an entry of `map`.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the parts of `Self`.
+    unsafe fn serialize_entries<M: SerializeMap>(
+        ptr: *const <Self::Target as PartialRefTarget>::RawTarget,
+        map: &mut M,
+    ) -> Result<(), M::Error>;
+
+    /// Serialize the fields of all parts of this partial reference.
+    fn serialize_parts<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(Self::SERIALIZED_PARTS))?;
+        unsafe { Self::serialize_entries(self.get_raw(), &mut map)? };
+        map.end()
+    }
+}
+
+/// *(internal)* An empty reference has nothing to serialize.
+unsafe impl<'a, Target> SerializeParts<'a> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+{
+    const SERIALIZED_PARTS: usize = 0;
+
+    #[inline(always)]
+    unsafe fn serialize_entries<M: SerializeMap>(
+        _ptr: *const Target::RawTarget,
+        _map: &mut M,
+    ) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+/// *(internal)* Serialize the remaining parts followed by a constant field part.
+unsafe impl<'a, SomePart, FieldType, Reference> SerializeParts<'a> for Const<SomePart, Reference>
+where
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Serialize,
+    Reference: SerializeParts<'a>,
+    Reference::Target: SerdePart<SomePart>,
+{
+    const SERIALIZED_PARTS: usize = 1 + Reference::SERIALIZED_PARTS;
+
+    #[inline(always)]
+    unsafe fn serialize_entries<M: SerializeMap>(
+        ptr: *const <Self::Target as PartialRefTarget>::RawTarget,
+        map: &mut M,
+    ) -> Result<(), M::Error> {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::serialize_entries(ptr, map)?;
+        map.serialize_entry(
+            <Self::Target as SerdePart<SomePart>>::NAME,
+            &*<Self::Target as HasPart<SomePart>>::part_ptr(ptr),
+        )
+    }
+}
+
+/// *(internal)* Serialize the remaining parts followed by a mutable field part.
+unsafe impl<'a, SomePart, FieldType, Reference> SerializeParts<'a> for Mut<SomePart, Reference>
+where
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: Serialize,
+    Reference: SerializeParts<'a>,
+    Reference::Target: SerdePart<SomePart>,
+{
+    const SERIALIZED_PARTS: usize = 1 + Reference::SERIALIZED_PARTS;
+
+    #[inline(always)]
+    unsafe fn serialize_entries<M: SerializeMap>(
+        ptr: *const <Self::Target as PartialRefTarget>::RawTarget,
+        map: &mut M,
+    ) -> Result<(), M::Error> {
+        // The outermost part is the last part listed in `partial!`.
+        Reference::serialize_entries(ptr, map)?;
+        map.serialize_entry(
+            <Self::Target as SerdePart<SomePart>>::NAME,
+            &*<Self::Target as HasPart<SomePart>>::part_ptr(ptr),
+        )
+    }
+}
+
+impl<'a, SomePart, Reference> Serialize for Const<SomePart, Reference>
+where
+    Reference: HasTarget,
+    Self: SerializeParts<'a>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_parts(serializer)
+    }
+}
+
+impl<'a, SomePart, Reference> Serialize for Mut<SomePart, Reference>
+where
+    Reference: HasTarget,
+    Self: SerializeParts<'a>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_parts(serializer)
+    }
+}
+
+/// Deserialize the fields of the mutable parts of a partial reference.
+///
+/// This reads a map as written by [`SerializeParts`] and overwrites the field of every mutable
+/// part with the deserialized value. All mutable parts of the partial reference have to be
+/// [`Field`] parts of fields that implement `DeserializeOwned`. The map must contain exactly one
+/// entry for each mutable part. Entries for constant parts are accepted but ignored, leaving the
+/// fields unchanged, so a map written by a partial reference can be read back using the same type.
+///
+/// The fields are only overwritten after the whole map was read successfully. When an error is
+/// returned, the fields of all parts are unchanged.
+pub unsafe trait DeserializeParts<'a>: PartialRef<'a> {
+    /// *(internal)* The values read for the mutable parts before they are written.
+    type Staged: Default;
+
+    /// *(internal)* Deserialize the value of the map entry with the key `name` into `staged`.
+    ///
+    /// Returns whether there is a part named `name`.
+    fn deserialize_entry<'de, A: MapAccess<'de>>(
+        name: &str,
+        staged: &mut Self::Staged,
+        map: &mut A,
+    ) -> Result<bool, A::Error>;
+
+    /// *(internal)* The name of the first mutable part without a value in `staged`.
+    fn missing_part(staged: &Self::Staged) -> Option<&'static str>;
+
+    /// *(internal)* Write the values in `staged` to the fields of the mutable parts.
+    ///
+    /// # Safety
+    /// The pointer must be valid for the parts of `Self` and `staged` must contain a value for
+    /// every mutable part.
+    unsafe fn write_staged(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        staged: Self::Staged,
+    );
+
+    /// Deserialize the fields of all mutable parts of this partial reference.
+    fn deserialize_parts<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        let staged = deserializer.deserialize_map(PartsVisitor::<'a, Self>(PhantomData))?;
+        unsafe { Self::write_staged(self.get_raw(), staged) };
+        Ok(())
+    }
+}
+
+/// Visitor for the map of parts read by [`deserialize_parts`](DeserializeParts::deserialize_parts).
+struct PartsVisitor<'a, Reference>(PhantomData<(&'a (), Reference)>);
+
+impl<'a, 'de, Reference: DeserializeParts<'a>> Visitor<'de> for PartsVisitor<'a, Reference> {
+    type Value = Reference::Staged;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map from part names to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut staged = Reference::Staged::default();
+
+        while let Some(name) = map.next_key::<String>()? {
+            if !Reference::deserialize_entry(&name, &mut staged, &mut map)? {
+                return Err(de::Error::custom(format_args!("unknown part `{}`", name)));
+            }
+        }
+
+        if let Some(name) = Reference::missing_part(&staged) {
+            return Err(de::Error::missing_field(name));
+        }
+
+        Ok(staged)
+    }
+}
+
+/// *(internal)* An empty reference has nothing to deserialize.
+unsafe impl<'a, Target> DeserializeParts<'a> for Ref<'a, Target>
+where
+    Target: PartialRefTarget + ?Sized,
+{
+    type Staged = ();
+
+    #[inline(always)]
+    fn deserialize_entry<'de, A: MapAccess<'de>>(
+        _name: &str,
+        _staged: &mut (),
+        _map: &mut A,
+    ) -> Result<bool, A::Error> {
+        Ok(false)
+    }
+
+    #[inline(always)]
+    fn missing_part(_staged: &()) -> Option<&'static str> {
+        None
+    }
+
+    #[inline(always)]
+    unsafe fn write_staged(_ptr: *mut Target::RawTarget, _staged: ()) {}
+}
+
+/// *(internal)* The entry of a constant part is skipped without deserializing the value.
+unsafe impl<'a, SomePart, Reference> DeserializeParts<'a> for Const<SomePart, Reference>
+where
+    SomePart: Part,
+    Reference: DeserializeParts<'a>,
+    Reference::Target: SerdePart<SomePart>,
+{
+    type Staged = Reference::Staged;
+
+    #[inline(always)]
+    fn deserialize_entry<'de, A: MapAccess<'de>>(
+        name: &str,
+        staged: &mut Self::Staged,
+        map: &mut A,
+    ) -> Result<bool, A::Error> {
+        if name == <Self::Target as SerdePart<SomePart>>::NAME {
+            map.next_value::<de::IgnoredAny>()?;
+            Ok(true)
+        } else {
+            Reference::deserialize_entry(name, staged, map)
+        }
+    }
+
+    #[inline(always)]
+    fn missing_part(staged: &Self::Staged) -> Option<&'static str> {
+        Reference::missing_part(staged)
+    }
+
+    #[inline(always)]
+    unsafe fn write_staged(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        staged: Self::Staged,
+    ) {
+        Reference::write_staged(ptr, staged)
+    }
+}
+
+/// *(internal)* Deserialize a mutable field part if it has the requested name, otherwise try the
+/// remaining parts.
+unsafe impl<'a, SomePart, FieldType, Reference> DeserializeParts<'a> for Mut<SomePart, Reference>
+where
+    SomePart: Part<PartType = Field<FieldType>>,
+    FieldType: DeserializeOwned,
+    Reference: DeserializeParts<'a>,
+    Reference::Target: SerdePart<SomePart>,
+{
+    type Staged = (Option<FieldType>, Reference::Staged);
+
+    #[inline(always)]
+    fn deserialize_entry<'de, A: MapAccess<'de>>(
+        name: &str,
+        staged: &mut Self::Staged,
+        map: &mut A,
+    ) -> Result<bool, A::Error> {
+        let name_here = <Self::Target as SerdePart<SomePart>>::NAME;
+        if name == name_here {
+            if staged.0.is_some() {
+                return Err(de::Error::duplicate_field(name_here));
+            }
+            staged.0 = Some(map.next_value()?);
+            Ok(true)
+        } else {
+            Reference::deserialize_entry(name, &mut staged.1, map)
+        }
+    }
+
+    #[inline(always)]
+    fn missing_part(staged: &Self::Staged) -> Option<&'static str> {
+        // Report the first missing part in the order of the `partial!` type.
+        match Reference::missing_part(&staged.1) {
+            None if staged.0.is_none() => Some(<Self::Target as SerdePart<SomePart>>::NAME),
+            missing => missing,
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn write_staged(
+        ptr: *mut <Self::Target as PartialRefTarget>::RawTarget,
+        staged: Self::Staged,
+    ) {
+        Reference::write_staged(ptr, staged.1);
+        if let Some(value) = staged.0 {
+            *<Self::Target as HasPart<SomePart>>::part_ptr_mut(ptr) = value;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Partial references to targets stored in statics.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{FullRefTarget, PartialRef, PartialRefTarget, RawTargetOf};
+
+/// A target stored in a `static`, accessed through a partial reference taken once.
+///
+/// This allows global state, e.g. the singletons of embedded code, to be accessed using partial
+/// references instead of `static mut` items. The first call of [`take`](StaticTarget::take)
+/// returns a partial reference having all parts of the target as mutable parts, which lives for
+/// the rest of the program. It can be split into partial references having fewer parts as usual,
+/// e.g. to hand out disjoint parts of the global state to different subsystems.
+pub struct StaticTarget<Target> {
+    target: UnsafeCell<Target>,
+    taken: AtomicBool,
+}
+
+unsafe impl<Target: Send> Sync for StaticTarget<Target> {}
+
+impl<Target> StaticTarget<Target> {
+    /// Store a target, usually in a `static` item.
+    pub const fn new(target: Target) -> Self {
+        StaticTarget {
+            target: UnsafeCell::new(target),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Take the partial reference having all parts, returning `None` if it was taken before.
+    pub fn take(&'static self) -> Option<Target::FullRef>
+    where
+        Target: FullRefTarget<'static>,
+        Target::RawTarget: RawTargetOf<Target>,
+    {
+        if self.taken.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        let ptr = <Target as PartialRefTarget>::RawTarget::from_target_ptr(self.target.get());
+        Some(unsafe { Target::FullRef::from_raw(ptr) })
+    }
+
+    /// Whether the partial reference was already taken.
+    pub fn is_taken(&self) -> bool {
+        self.taken.load(Ordering::SeqCst)
+    }
+}
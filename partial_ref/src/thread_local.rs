@@ -0,0 +1,35 @@
+//! Parts stored in thread-local storage instead of the target.
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::thread::LocalKey;
+
+use crate::{Part, PartType};
+
+/// A part whose storage is a thread-local `RefCell` instead of a field of the target.
+///
+/// Thread-local parts are declared using `part!(thread_local PartName: FieldType);` and added to a
+/// target using the derive attribute `#[part(thread_local(PartName))]`. They are accessed using
+/// [`with_local_part`](crate::PartialRef::with_local_part) and
+/// [`with_local_part_mut`](crate::PartialRef::with_local_part_mut), which borrow the storage only
+/// for the duration of a closure, as the storage is destroyed when the thread exits. The borrows
+/// are checked at runtime, as every value of every target having the part shares the same storage
+/// on a thread. The storage is initialized with the default value of `FieldType` on first access.
+pub trait ThreadLocalPart: Part<PartType = ThreadLocalField<Self::FieldType>> {
+    /// The type of the stored value.
+    type FieldType: 'static;
+
+    /// The thread-local storage of the part.
+    fn local_key() -> &'static LocalKey<RefCell<Self::FieldType>>;
+}
+
+/// Type of a part that is stored in thread-local storage (see [`ThreadLocalPart`]).
+///
+/// As the value isn't stored in the target, the pointers produced by
+/// [`HasPart`](crate::HasPart) for parts of this type carry no information.
+pub struct ThreadLocalField<FieldType>(PhantomData<*const FieldType>);
+
+impl<FieldType> PartType for ThreadLocalField<FieldType> {
+    type Ptr = ();
+    type PtrMut = ();
+}
@@ -0,0 +1,71 @@
+//! Abstract parts whose ownership is proven by a token value.
+
+use std::marker::PhantomData;
+
+use crate::{
+    AbstractPart, AppendNestedPart, HasPart, Nested, NormalizeNestedPart, Part, PartSpec,
+    PartialRefTarget,
+};
+
+/// A token proving ownership of an abstract resource.
+///
+/// A value of a token type stands for the resource, so a partial reference having the part
+/// [`TokenPart<Token>`](TokenPart) can only be created by borrowing the token, using
+/// [`attach_token`](crate::PartialRef::attach_token) or
+/// [`attach_token_const`](crate::PartialRef::attach_token_const). Token types are declared using
+/// the [`part_token`](crate::part_token) macro.
+///
+/// # Safety
+/// At most one value of an implementing type may ever be created.
+pub unsafe trait PartToken {}
+
+/// The abstract part owned by the holder of a [`PartToken`].
+///
+/// Every target has this part, but partial references to a target never contain it unless the token
+/// was attached. The lifetime `'t` is the lifetime for which the token is borrowed.
+pub struct TokenPart<'t, Token>(PhantomData<&'t Token>);
+
+impl<'t, Token> Default for TokenPart<'t, Token> {
+    fn default() -> Self {
+        TokenPart(PhantomData)
+    }
+}
+
+impl<'t, Token: PartToken> Part for TokenPart<'t, Token> {
+    type PartType = AbstractPart;
+}
+
+impl<'a, 'b, Token: PartToken> PartSpec<TokenPart<'a, Token>> for TokenPart<'b, Token> {}
+
+impl<'t, Token> NormalizeNestedPart for TokenPart<'t, Token> {
+    type Normalized = TokenPart<'t, Token>;
+}
+
+impl<'t, NestedPrefix, Token> AppendNestedPart<NestedPrefix> for TokenPart<'t, Token> {
+    type Output = Nested<NestedPrefix, TokenPart<'t, Token>>;
+}
+
+impl<'t, Target, Token> HasPart<TokenPart<'t, Token>> for Target
+where
+    Target: PartialRefTarget + ?Sized,
+    Token: PartToken,
+{
+    #[inline(always)]
+    unsafe fn part_ptr(_ptr: *const Self::RawTarget) {}
+
+    #[inline(always)]
+    unsafe fn part_ptr_mut(_ptr: *mut Self::RawTarget) {}
+}
+
+/// *(internal)* Private field of token types declared by [`part_token`](crate::part_token).
+#[doc(hidden)]
+pub struct TokenSeal(());
+
+impl TokenSeal {
+    /// # Safety
+    /// Must only be called by the code generated by [`part_token`](crate::part_token).
+    #[doc(hidden)]
+    pub unsafe fn new_unchecked() -> Self {
+        TokenSeal(())
+    }
+}
@@ -0,0 +1,72 @@
+//! Abstract parts tracked in different typestates.
+
+use std::marker::PhantomData;
+
+use crate::{
+    AbstractPart, AppendNestedPart, HasPart, Nested, NormalizeNestedPart, Part, PartSpec, PartType,
+    PartialRefTarget,
+};
+
+/// Type of an abstract part in the typestate `State`.
+///
+/// Like an [`AbstractPart`], partial references only keep track of this part. This is the part
+/// type of [`Typestate`] parts.
+pub struct StatefulAbstractPart<State>(PhantomData<State>);
+
+impl<State> PartType for StatefulAbstractPart<State> {
+    type Ptr = ();
+    type PtrMut = ();
+}
+
+/// The abstract part `StatePart` in the typestate `State`.
+///
+/// This is an abstract part of type [`StatefulAbstractPart<State>`], which every target
+/// having the abstract part `StatePart` has for every `State`. Partial references get this part by
+/// entering a typestate using [`enter_state`](crate::PartialRef::enter_state) and move between
+/// typestates using [`transition_state`](crate::PartialRef::transition_state), both of which
+/// require `StatePart` or the current typestate to be a mutable part. Functions that require a
+/// typestate take a partial reference having this part, e.g.
+/// `partial!(Solver, mut Typestate<Search, Propagating>, mut Trail)`.
+pub struct Typestate<StatePart, State>(PhantomData<(StatePart, State)>);
+
+impl<StatePart, State> Default for Typestate<StatePart, State> {
+    fn default() -> Self {
+        Typestate(PhantomData)
+    }
+}
+
+impl<StatePart, State> Part for Typestate<StatePart, State>
+where
+    StatePart: Part<PartType = AbstractPart>,
+{
+    type PartType = StatefulAbstractPart<State>;
+}
+
+impl<StatePart, StatePartS, State> PartSpec<Typestate<StatePartS, State>>
+    for Typestate<StatePart, State>
+where
+    StatePart: PartSpec<StatePartS>,
+{
+}
+
+impl<StatePart, State> NormalizeNestedPart for Typestate<StatePart, State> {
+    type Normalized = Typestate<StatePart, State>;
+}
+
+impl<NestedPrefix, StatePart, State> AppendNestedPart<NestedPrefix>
+    for Typestate<StatePart, State>
+{
+    type Output = Nested<NestedPrefix, Typestate<StatePart, State>>;
+}
+
+impl<Target, StatePart, State> HasPart<Typestate<StatePart, State>> for Target
+where
+    Target: PartialRefTarget + HasPart<StatePart> + ?Sized,
+    StatePart: Part<PartType = AbstractPart>,
+{
+    #[inline(always)]
+    unsafe fn part_ptr(_ptr: *const Self::RawTarget) {}
+
+    #[inline(always)]
+    unsafe fn part_ptr_mut(_ptr: *mut Self::RawTarget) {}
+}
@@ -7,29 +7,125 @@ use std::collections::HashSet;
 use crate::proc_macro::TokenStream;
 
 use proc_macro2::{Span, TokenTree};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::token::Paren;
 use syn::{
-    parse_macro_input, parse_quote, parse_str, Attribute, Data, DeriveInput, Lifetime, LifetimeDef,
-    Lit, Member, Meta, Type, TypeParen,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote, parse_str,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DeriveInput, Expr, ExprLit, Field, Fields, Generics, Ident, Lifetime,
+    LifetimeDef, Lit, LitStr, Member, Meta, NestedMeta, Token, TraitBound, TraitBoundModifier,
+    Type, TypeParamBound, Visibility, WherePredicate,
 };
 
-fn parse_attribute_as_type(attr: &Attribute) -> Type {
-    if let Some(TokenTree::Group(group)) = attr.tokens.clone().into_iter().next() {
-        let parsed_type: Type = parse_quote!(#group);
-        // This avoids unnecessary parentheses around type warnings from the generated code.
-        if let Type::Paren(TypeParen { elem, .. }) = parsed_type {
-            return *elem;
+/// A parsed `#[part(...)]` attribute.
+enum PartAttr {
+    /// A part declared outside of the derive.
+    Declared(Type),
+    /// A part declared by the derive, named after the field or variant.
+    Auto,
+    /// No part, the field is covered by the rest part instead.
+    Skip,
+    /// Partial references to the target are partial references to the field.
+    Transparent,
+    /// The listed parts of the field's type become parts of the containing type.
+    Flatten(Vec<Type>),
+    /// A part for the value the field points to.
+    Deref(Type),
+    /// A part providing pinned access to the field.
+    Pin(Type),
+    /// A part with a const index parameter for each element of an array field.
+    Elements(Ident),
+    /// Parts of the target computed by an accessor instead of being a field.
+    Virtual(Vec<Type>),
+    /// Parts of the target stored in thread-local storage.
+    ThreadLocal(Vec<Type>),
+    /// Additional predicates for the where clause of the field's `HasPart` impl.
+    Bound(Vec<WherePredicate>),
+}
+
+impl Parse for PartAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![virtual]) && input.peek2(Paren) {
+            input.parse::<Token![virtual]>()?;
+            let content;
+            parenthesized!(content in input);
+            let parts = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+            return Ok(PartAttr::Virtual(parts.into_iter().collect()));
+        }
+
+        let fork = input.fork();
+        if let Ok(ident) = fork.parse::<Ident>() {
+            if fork.is_empty() {
+                let part_attr = match ident.to_string().as_str() {
+                    "auto" => Some(PartAttr::Auto),
+                    "skip" => Some(PartAttr::Skip),
+                    "transparent" => Some(PartAttr::Transparent),
+                    _ => None,
+                };
+                if let Some(part_attr) = part_attr {
+                    input.parse::<Ident>()?;
+                    return Ok(part_attr);
+                }
+            } else if ident == "flatten" && fork.peek(Paren) {
+                input.parse::<Ident>()?;
+                let content;
+                parenthesized!(content in input);
+                let parts = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+                return Ok(PartAttr::Flatten(parts.into_iter().collect()));
+            } else if ident == "thread_local" && fork.peek(Paren) {
+                input.parse::<Ident>()?;
+                let content;
+                parenthesized!(content in input);
+                let parts = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+                return Ok(PartAttr::ThreadLocal(parts.into_iter().collect()));
+            } else if ident == "elements" && fork.peek(Paren) {
+                input.parse::<Ident>()?;
+                let content;
+                parenthesized!(content in input);
+                let part = content.parse()?;
+                if !content.is_empty() {
+                    return Err(content.error("expected a single part name"));
+                }
+                return Ok(PartAttr::Elements(part));
+            } else if ident == "deref" && fork.peek(Token![,]) {
+                input.parse::<Ident>()?;
+                input.parse::<Token![,]>()?;
+                return Ok(PartAttr::Deref(input.parse()?));
+            } else if ident == "pin" && fork.peek(Token![,]) {
+                input.parse::<Ident>()?;
+                input.parse::<Token![,]>()?;
+                return Ok(PartAttr::Pin(input.parse()?));
+            } else if ident == "bound" && fork.peek(Token![=]) {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let string = input.parse::<LitStr>()?;
+                let bounds =
+                    string.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+                return Ok(PartAttr::Bound(bounds.into_iter().collect()));
+            }
         }
-        return parsed_type;
+
+        Ok(PartAttr::Declared(input.parse()?))
+    }
+}
+
+fn parse_part_attribute(attr: &Attribute) -> PartAttr {
+    if let Some(TokenTree::Group(_)) = attr.tokens.clone().into_iter().next() {
+        return attr
+            .parse_args()
+            .unwrap_or_else(|_| panic!("could not parse attribute `{}`", attr.tokens));
     }
 
-    let parse_panic = || panic!("could not parse attribute `{}`", attr.tokens.to_string());
+    let parse_panic = || panic!("could not parse attribute `{}`", attr.tokens);
     let meta = attr.parse_meta().unwrap_or_else(|_| parse_panic());
     if let Meta::NameValue(name_value) = meta {
         if let Lit::Str(string) = name_value.lit {
             match parse_str(&string.value()) {
                 Err(_) => panic!("could not parse type `{}` in attribute", string.value()),
-                Ok(parsed_type) => return parsed_type,
+                Ok(parsed_type) => return PartAttr::Declared(parsed_type),
             }
         }
     }
@@ -56,232 +152,2824 @@ fn generics_to_extra_generics(generics: &impl ToTokens) -> proc_macro2::TokenStr
     extra_tokens
 }
 
-/// Generate a new lifetime that doesn't conflict with the existing lifetimes.
-fn fresh_lifetime<'a>(lifetimes: impl Iterator<Item = &'a LifetimeDef>, name: &str) -> Lifetime {
-    let mut used_idents = HashSet::new();
-    for lifetime in lifetimes {
-        used_idents.insert(lifetime.lifetime.ident.to_string());
-    }
+/// A parsed `#[part_group(GroupPart: field_a, field_b, ...)]` attribute.
+struct PartGroup {
+    part: Ident,
+    fields: Vec<Ident>,
+}
 
-    let mut lifetime_name = name.to_owned();
-    let mut counter = 0;
+fn parse_part_group_attribute(attr: &Attribute) -> PartGroup {
+    attr.parse_args_with(|input: ParseStream| {
+        let part = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let fields = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+        Ok(PartGroup {
+            part,
+            fields: fields.into_iter().collect(),
+        })
+    })
+    .unwrap_or_else(|_| panic!("could not parse attribute `{}`", attr.tokens))
+}
 
-    while used_idents.contains(&lifetime_name) {
-        use std::fmt::Write;
-        counter += 1;
-        lifetime_name.clear();
-        write!(&mut lifetime_name, "{}{}", name, counter).unwrap();
+/// Check that no field is listed more than once in the group parts.
+///
+/// A field contained in two groups, or listed twice in one group, would be mutably borrowed twice
+/// when accessing the groups.
+fn check_part_groups(groups: &[PartGroup]) -> syn::Result<()> {
+    let mut grouped = HashSet::new();
+    for group in groups.iter() {
+        for field in group.fields.iter() {
+            if !grouped.insert(field) {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!("field `{}` is listed in more than one group part", field),
+                ));
+            }
+        }
     }
+    Ok(())
+}
 
-    Lifetime::new(&format!("'{}", lifetime_name), Span::call_site())
+/// Options given by `#[partial_ref(...)]` attributes of the target.
+#[derive(Default)]
+struct TargetOptions {
+    /// Module that contains the parts declared by the derive.
+    parts_mod: Option<Ident>,
+    /// Type used as `RawTarget` instead of the target itself.
+    raw: Option<Type>,
+    /// Hide the generated trait impls from the documentation.
+    doc_hidden: bool,
+    /// Generate a trait with split methods for the parts of fields.
+    split_helpers: bool,
+    /// Don't support using the target as the field of a nested part.
+    no_nested: bool,
+    /// Generate a constructor taking a value for each field having a part.
+    from_parts: bool,
+    /// Generate structs of plain references to the fields having a part.
+    view: bool,
+    /// Prefix of the names of automatic parts.
+    prefix: Option<Ident>,
+    /// Trait implemented by all targets having the parts of the target.
+    parts_trait: Option<Ident>,
+    /// Additional predicates for the where clauses of the generated trait impls.
+    bounds: Vec<WherePredicate>,
 }
 
-/// Derives instances of PartialRefTarget and associated traits.
-///
-/// Can only be used for structs. The attribute `#[part(PartName)]` can be used on the struct itself
-/// for an abstract part or on a field for a field part. Parts have to be declared separately.
-/// `PartName` can be any valid rust type that implements the Part trait. For fields the field type
-/// of the part has to match the actual type of the field.
-///
-/// Example:
-///
-/// ```ignore
-/// use partial_ref::{PartialRefTarget, part};
-///
-/// #[derive(PartialRefTarget)]
-/// #[part(SomeAbstractPart)]
-/// struct ExampleStruct {
-///     field_without_part: usize,
-///     #[part(SomeFieldPart)]
-///     a: usize,
-///     #[part(another_crate::AnotherFieldPart)]
-///     b: usize,
-/// }
-/// ```
-///
-/// Instead of `#[part(PartName)]` it is also possible to use `#[part = "PartName"]` which was the
-/// only supported syntax in previous versions of this crate.
-///
+fn parse_target_options(attrs: &[Attribute]) -> TargetOptions {
+    let mut options = TargetOptions::default();
 
-// TODO figure out how to link to doc items of the partial_ref crate
-#[proc_macro_derive(PartialRefTarget, attributes(part))]
-pub fn derive_partial_ref_target(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+    for attr in attrs.iter() {
+        if !attr.path.is_ident("partial_ref") {
+            continue;
+        }
 
-    let target_ident = input.ident;
+        let parse_panic = || -> ! { panic!("could not parse attribute `{}`", attr.tokens) };
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => parse_panic(),
+        };
 
-    let lt_a = fresh_lifetime(input.generics.lifetimes(), "a");
+        for nested in list.nested.iter() {
+            if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                if path.is_ident("doc_hidden") {
+                    options.doc_hidden = true;
+                    continue;
+                }
+                if path.is_ident("split_helpers") {
+                    options.split_helpers = true;
+                    continue;
+                }
+                if path.is_ident("no_nested") {
+                    options.no_nested = true;
+                    continue;
+                }
+                if path.is_ident("from_parts") {
+                    options.from_parts = true;
+                    continue;
+                }
+                if path.is_ident("view") {
+                    options.view = true;
+                    continue;
+                }
+                panic!("unknown option `{}`", path.to_token_stream());
+            }
 
-    let (impl_generics, target_generics, where_clause) = input.generics.split_for_impl();
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                _ => parse_panic(),
+            };
+            let string = match &name_value.lit {
+                Lit::Str(string) => string,
+                _ => parse_panic(),
+            };
 
-    if where_clause.is_some() {
-        panic!("cannot derive PartialRef target for structs with a where clause");
-        // TODO lift this restriction
+            if name_value.path.is_ident("parts_mod") {
+                options.parts_mod = Some(string.parse().unwrap_or_else(|_| {
+                    panic!("could not parse module name `{}`", string.value())
+                }));
+            } else if name_value.path.is_ident("raw") {
+                options.raw = Some(string.parse().unwrap_or_else(|_| {
+                    panic!("could not parse raw target type `{}`", string.value())
+                }));
+            } else if name_value.path.is_ident("prefix") {
+                options.prefix = Some(string.parse().unwrap_or_else(|_| {
+                    panic!("could not parse part name prefix `{}`", string.value())
+                }));
+            } else if name_value.path.is_ident("parts_trait") {
+                options.parts_trait =
+                    Some(string.parse().unwrap_or_else(|_| {
+                        panic!("could not parse trait name `{}`", string.value())
+                    }));
+            } else if name_value.path.is_ident("bound") {
+                let bounds = string
+                    .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)
+                    .unwrap_or_else(|_| panic!("could not parse bound `{}`", string.value()));
+                options.bounds.extend(bounds);
+            } else {
+                panic!("unknown option `{}`", name_value.path.to_token_stream());
+            }
+        }
     }
 
-    let extra_generics = generics_to_extra_generics(&impl_generics);
+    options
+}
 
-    let target_type = quote!(#target_ident #target_generics);
+/// Visibility of a part declared for a field, which is the visibility of the field limited to the
+/// visibility of the target.
+///
+/// This keeps the parts of public fields of a crate-private target out of the public interface.
+/// When both are restricted to different paths, the visibility of the field is used.
+fn field_part_visibility(field_vis: &Visibility, target_vis: &Visibility) -> Visibility {
+    match (field_vis, target_vis) {
+        (_, Visibility::Public(_)) => field_vis.clone(),
+        (Visibility::Inherited, _) | (_, Visibility::Inherited) => Visibility::Inherited,
+        (Visibility::Public(_), _) | (Visibility::Crate(_), _) => target_vis.clone(),
+        (Visibility::Restricted(restricted), _) if restricted.path.is_ident("crate") => {
+            target_vis.clone()
+        }
+        _ => field_vis.clone(),
+    }
+}
 
-    let data_struct = match input.data {
-        Data::Struct(data_struct) => data_struct,
-        _ => panic!("deriving PartialRefTarget is only supported on structs"),
-    };
+/// Adjust the visibility of an item moved into a child module, so that it stays visible in the
+/// same places.
+fn nested_visibility(vis: &Visibility) -> Visibility {
+    match vis {
+        Visibility::Inherited => parse_quote!(pub(super)),
+        Visibility::Restricted(restricted) => {
+            let path = &restricted.path;
+            match path.segments.first() {
+                Some(first) if first.ident == "self" => {
+                    let rest = path.segments.iter().skip(1);
+                    parse_quote!(pub(in super #(::#rest)*))
+                }
+                Some(first) if first.ident == "super" => parse_quote!(pub(in super::#path)),
+                _ => vis.clone(),
+            }
+        }
+        _ => vis.clone(),
+    }
+}
 
-    let mut abstract_parts: Vec<Type> = vec![];
-    let mut typed_parts: Vec<(Member, Type)> = vec![];
+/// Parse the `#[part(...)]` attribute of an item that can have at most one part.
+fn single_part(attrs: &[Attribute], describe: impl Fn() -> String) -> Option<PartAttr> {
+    let mut part: Option<PartAttr> = None;
 
-    for attr in input.attrs.iter() {
+    for attr in attrs.iter() {
         if attr.path.is_ident("part") {
-            abstract_parts.push(parse_attribute_as_type(&attr));
+            if part.is_some() {
+                panic!("{} has multiple parts", describe());
+            }
+            part = Some(parse_part_attribute(attr));
         }
     }
 
-    for (field_index, field) in data_struct.fields.iter().enumerate() {
-        let mut part: Option<Type> = None;
+    part
+}
 
-        for attr in field.attrs.iter() {
-            if attr.path.is_ident("part") {
-                if part.is_some() {
-                    panic!(
-                        "{} has multiple parts",
-                        field
-                            .ident
-                            .as_ref()
-                            .map_or("unnamed field".to_owned(), |i| format!("field `{}`", i))
-                    );
+/// The parts of a list of fields.
+#[derive(Default)]
+struct FieldParts {
+    /// Fields having a part, with the type of the field.
+    typed: Vec<(Member, Type, Type)>,
+    /// Names of the skipped fields.
+    skipped: Vec<Ident>,
+    /// Flattened fields with their type and the parts they provide.
+    flattened: Vec<(Member, Type, Vec<Type>)>,
+    /// Fields pointing to a value having a part, with the type of the field.
+    dereferenced: Vec<(Member, Type, Type)>,
+    /// Structurally pinned fields with their type and part.
+    pinned: Vec<(Member, Type, Type)>,
+    /// Array fields with their type, their visibility and the name of their element parts.
+    elements: Vec<(Member, Type, Visibility, Ident)>,
+    /// Abstract parts given by additional `#[part(...)]` attributes of fields.
+    aliases: Vec<Type>,
+    /// The field marked as transparent with its type.
+    transparent: Option<(Member, Type)>,
+    /// Fields with additional predicates for the where clause of their `HasPart` impl.
+    bounds: Vec<(Member, Vec<WherePredicate>)>,
+}
+
+/// Collect the parts of a list of fields.
+///
+/// When `auto` is set, fields without a `#[part(...)]` attribute that are not contained in a
+/// group part get an automatic part. The closure `declare_auto_part` is called to declare automatic
+/// parts.
+fn field_parts(
+    fields: &Fields,
+    auto: bool,
+    groups: &[PartGroup],
+    mut declare_auto_part: impl FnMut(&Ident, &Field) -> Type,
+) -> FieldParts {
+    let mut field_parts = FieldParts::default();
+
+    let mut declare = |field: &Field| {
+        let ident = field
+            .ident
+            .as_ref()
+            .unwrap_or_else(|| panic!("automatic parts are not supported for unnamed fields"));
+        declare_auto_part(ident, field)
+    };
+
+    for (field_index, field) in fields.iter().enumerate() {
+        let describe = || {
+            field
+                .ident
+                .as_ref()
+                .map_or("unnamed field".to_owned(), |i| format!("field `{}`", i))
+        };
+
+        let mut bounds = vec![];
+
+        let mut part_attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("part"))
+            .map(parse_part_attribute)
+            .filter_map(|part_attr| match part_attr {
+                PartAttr::Bound(predicates) => {
+                    bounds.extend(predicates);
+                    None
                 }
-                part = Some(parse_attribute_as_type(&attr));
+                part_attr => Some(part_attr),
+            });
+
+        let part = part_attrs.next();
+
+        for alias in part_attrs {
+            match alias {
+                PartAttr::Declared(alias) => field_parts.aliases.push(alias),
+                _ => panic!(
+                    "additional parts of {} have to be declared abstract parts",
+                    describe()
+                ),
             }
         }
 
-        if let Some(part_type) = part {
-            let member = field
-                .ident
-                .as_ref()
-                .map_or(Member::Unnamed(field_index.into()), |ident| {
-                    Member::Named(ident.clone())
-                });
-            typed_parts.push((member, part_type));
+        let group = field.ident.as_ref().and_then(|ident| {
+            groups
+                .iter()
+                .find(|group| group.fields.iter().any(|grouped| grouped == ident))
+        });
+
+        if let Some(group) = group {
+            if part.is_some() || !bounds.is_empty() {
+                panic!(
+                    "field `{}` is contained in group part `{}` and cannot have its own part",
+                    field.ident.as_ref().unwrap(),
+                    group.part
+                );
+            }
+            continue;
+        }
+
+        let member = field_member(field_index, field);
+        let part = part.or(if auto { Some(PartAttr::Auto) } else { None });
+
+        if !bounds.is_empty() {
+            match part {
+                Some(PartAttr::Declared(_))
+                | Some(PartAttr::Auto)
+                | Some(PartAttr::Pin(_))
+                | Some(PartAttr::Deref(_)) => field_parts.bounds.push((member.clone(), bounds)),
+                _ => panic!("bounds of {} require a part of the field", describe()),
+            }
+        }
+
+        match part {
+            Some(PartAttr::Declared(part_type)) => {
+                field_parts
+                    .typed
+                    .push((member, field.ty.clone(), part_type))
+            }
+            Some(PartAttr::Auto) => {
+                field_parts
+                    .typed
+                    .push((member, field.ty.clone(), declare(field)))
+            }
+            Some(PartAttr::Skip) => {
+                field_parts
+                    .skipped
+                    .push(field.ident.clone().unwrap_or_else(|| {
+                        panic!("skipping unnamed fields is not supported");
+                    }));
+            }
+            Some(PartAttr::Flatten(parts)) => {
+                field_parts
+                    .flattened
+                    .push((member, field.ty.clone(), parts))
+            }
+            Some(PartAttr::Transparent) => {
+                if field_parts.transparent.is_some() {
+                    panic!("only one field can be transparent");
+                }
+                field_parts.transparent = Some((member, field.ty.clone()));
+            }
+            Some(PartAttr::Elements(part_ident)) => {
+                field_parts
+                    .elements
+                    .push((member, field.ty.clone(), field.vis.clone(), part_ident))
+            }
+            Some(PartAttr::Pin(part_type)) => {
+                field_parts
+                    .pinned
+                    .push((member, field.ty.clone(), part_type))
+            }
+            Some(PartAttr::Deref(part_type)) => {
+                field_parts
+                    .dereferenced
+                    .push((member, field.ty.clone(), part_type))
+            }
+            Some(PartAttr::Virtual(_)) => panic!("only targets can have virtual parts"),
+            Some(PartAttr::ThreadLocal(_)) => panic!("only targets can have thread-local parts"),
+            Some(PartAttr::Bound(_)) => unreachable!(),
+            None => (),
         }
     }
 
-    let mut const_type = quote!(::partial_ref::Ref<#lt_a, #target_type>);
-    let mut mut_type = quote!(::partial_ref::Ref<#lt_a, #target_type>);
-    let mut split_const_type = quote!(Reference);
-    let mut split_mut_type = quote!(Reference);
+    field_parts
+}
 
-    for part in abstract_parts.iter() {
-        const_type = quote!(::partial_ref::Const<#part, #const_type>);
-        mut_type = quote!(::partial_ref::Mut<#part, #mut_type>);
+/// Convert a field name into the name of its automatic part, e.g. `clause_db` into `ClauseDb`.
+///
+/// When a `prefix` is given, it is prepended to the name, e.g. `GfxClauseDb` for the prefix `Gfx`.
+fn auto_part_ident(ident: &Ident, prefix: Option<&Ident>) -> Ident {
+    let name = ident.to_string();
+    let mut part_name = prefix.map_or(String::new(), |prefix| prefix.to_string());
+    let mut upper = true;
 
-        split_const_type = quote!(
-            ::partial_ref::Const<
-                ::partial_ref::Nested<ContainingPart, #part>,
-                #split_const_type
-            >
-        );
-        split_mut_type = quote!(
-            ::partial_ref::Mut<
-                ::partial_ref::Nested<ContainingPart, #part>,
-                #split_mut_type
-            >
-        );
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            part_name.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            part_name.push(c);
+        }
     }
 
-    for (_, part) in typed_parts.iter() {
-        const_type = quote!(::partial_ref::Const<#part, #const_type>);
-        mut_type = quote!(::partial_ref::Mut<#part, #mut_type>);
+    Ident::new(&part_name, ident.span())
+}
 
-        split_const_type = quote!(
-            ::partial_ref::Const<
-                ::partial_ref::Nested<ContainingPart, #part>,
-                #split_const_type
-            >
-        );
-        split_mut_type = quote!(
-            ::partial_ref::Mut<
-                ::partial_ref::Nested<ContainingPart, #part>,
-                #split_mut_type
-            >
-        );
+/// The member used to access a field with the given index.
+fn field_member(index: usize, field: &Field) -> Member {
+    field
+        .ident
+        .as_ref()
+        .map_or(Member::Unnamed(index.into()), |ident| {
+            Member::Named(ident.clone())
+        })
+}
+
+/// The name of a field without a raw identifier prefix, or the index of an unnamed field.
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string().trim_start_matches("r#").to_owned(),
+        Member::Unnamed(index) => index.index.to_string(),
     }
+}
 
-    let mut result = vec![];
+/// The name of a part as listed in `PartMetadata`, e.g. `Items<T>`.
+fn part_name(part: &Type) -> String {
+    let name = part.to_token_stream().to_string().replace(' ', "");
+    // Parts declared by the derive can have a trailing comma in their arguments.
+    name.replace(",>", ">")
+}
 
-    result.push(TokenStream::from(quote! {
-        impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a> for &#lt_a #target_type {
-            type Ref = #const_type;
-            #[inline(always)]
-            fn into_partial_ref(self) -> Self::Ref {
-                unsafe {
-                    <Self::Ref as ::partial_ref::PartialRef>::from_raw(self as *const _ as *mut _)
+/// Returns the lifetime names and the other identifiers that are used within `ty`.
+fn used_idents(ty: &Type) -> (HashSet<String>, HashSet<String>) {
+    fn collect(
+        tokens: proc_macro2::TokenStream,
+        lifetimes: &mut HashSet<String>,
+        idents: &mut HashSet<String>,
+    ) {
+        let mut after_quote = false;
+        for token in tokens {
+            match &token {
+                TokenTree::Group(group) => collect(group.stream(), lifetimes, idents),
+                TokenTree::Ident(ident) if after_quote => {
+                    lifetimes.insert(ident.to_string());
                 }
+                TokenTree::Ident(ident) => {
+                    idents.insert(ident.to_string());
+                }
+                _ => (),
             }
+            after_quote = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '\'');
         }
+    }
 
-        impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
-        for &#lt_a mut #target_type {
-            type Ref = #mut_type;
-            #[inline(always)]
-            fn into_partial_ref(self) -> Self::Ref {
-                unsafe {
-                    <Self::Ref as ::partial_ref::PartialRef>::from_raw(self as *mut _)
-                }
+    let mut lifetimes = HashSet::new();
+    let mut idents = HashSet::new();
+    collect(ty.to_token_stream(), &mut lifetimes, &mut idents);
+    (lifetimes, idents)
+}
+
+/// The generic parameters of the target type that are used within some types.
+#[derive(Default)]
+struct UsedParams {
+    lifetimes: Vec<Lifetime>,
+    type_params: Vec<Ident>,
+    const_params: Vec<(Ident, Type)>,
+}
+
+impl UsedParams {
+    /// Add the parameters among `generics` that are used within `ty`.
+    fn add(&mut self, ty: &Type, generics: &Generics) {
+        let (lifetimes, idents) = used_idents(ty);
+
+        for lifetime in generics.lifetimes() {
+            let lifetime = &lifetime.lifetime;
+            if lifetimes.contains(&lifetime.ident.to_string()) && !self.lifetimes.contains(lifetime)
+            {
+                self.lifetimes.push(lifetime.clone());
             }
         }
 
-        unsafe impl<#lt_a #extra_generics, ContainingPart, Reference>
-            ::partial_ref::SplitIntoParts<#lt_a, ContainingPart, Reference> for #target_type
-        where
-            ContainingPart: ::partial_ref::Part<PartType=::partial_ref::Field<Self>>,
-            Reference: ::partial_ref::PartialRef<#lt_a>,
-            Reference::Target: ::partial_ref::HasPart<ContainingPart>,
-        {
-            type Result = #split_const_type;
-            type ResultMut = #split_mut_type;
+        for type_param in generics.type_params() {
+            let ident = &type_param.ident;
+            if idents.contains(&ident.to_string()) && !self.type_params.contains(ident) {
+                self.type_params.push(ident.clone());
+            }
         }
 
-        impl #impl_generics ::partial_ref::PartialRefTarget for #target_type {
-            type RawTarget = Self;
+        for const_param in generics.const_params() {
+            let ident = &const_param.ident;
+            if idents.contains(&ident.to_string())
+                && !self.const_params.iter().any(|(used, _)| used == ident)
+            {
+                self.const_params
+                    .push((ident.clone(), const_param.ty.clone()));
+            }
         }
-    }));
+    }
 
-    for part in abstract_parts.iter() {
-        result.push(TokenStream::from(quote! {
-             impl #impl_generics ::partial_ref::HasPart<#part> for #target_type {
-                #[inline(always)]
-                unsafe fn part_ptr(ptr: *const Self) -> () {
-                    unreachable!()
-                }
+    /// Whether any type or const parameters are used.
+    fn has_non_lifetimes(&self) -> bool {
+        !self.type_params.is_empty() || !self.const_params.is_empty()
+    }
 
-                #[inline(always)]
-                unsafe fn part_ptr_mut(ptr: *mut Self) -> () {
-                    unreachable!()
-                }
-            }
-        }));
+    /// The used parameters as generic arguments, e.g. `'a, T, N`.
+    fn args(&self) -> proc_macro2::TokenStream {
+        let lifetimes = &self.lifetimes;
+        let type_params = &self.type_params;
+        let const_params = self.const_params.iter().map(|(ident, _)| ident);
+        quote!(#(#lifetimes,)* #(#type_params,)* #(#const_params),*)
     }
+}
 
-    for (member, part) in typed_parts.iter() {
-        result.push(TokenStream::from(quote! {
-             impl #impl_generics ::partial_ref::HasPart<#part> for #target_type {
-                #[inline(always)]
-                unsafe fn part_ptr(
-                    ptr: *const Self
-                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr  {
-                    ::core::ptr::addr_of!((*ptr).#member)
-                }
+/// Two lists of distinct lifetimes, which `part!` uses to declare the `PartSpec` impl of a part with
+/// `count` lifetimes.
+///
+/// Without these lists, `part!` only supports parts with up to three lifetimes.
+fn spec_lifetimes(count: usize) -> proc_macro2::TokenStream {
+    if count == 0 {
+        return quote!();
+    }
+    let lifetimes = |name: &str| {
+        (1..=count)
+            .map(|index| Lifetime::new(&format!("'{}{}", name, index), Span::call_site()))
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (lifetimes("a"), lifetimes("b"));
+    quote!((#(#a),*) (#(#b),*))
+}
 
-                #[inline(always)]
-                unsafe fn part_ptr_mut(
-                    ptr: *mut Self
-                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
-                    ::core::ptr::addr_of_mut!((*ptr).#member)
+/// Declare a part using the `part!` macro and return the part's type.
+///
+/// For field parts, the part gets all generic parameters among `generics` that appear in the
+/// field's type. When `parts_mod` is given, the declarations are placed in that module.
+fn declare_part(
+    declarations: &mut Vec<proc_macro2::TokenStream>,
+    part_ident: &Ident,
+    vis: &Visibility,
+    part_type: proc_macro2::TokenStream,
+    field_type: Option<&Type>,
+    generics: &Generics,
+    parts_mod: Option<&Ident>,
+) -> Type {
+    let vis = &parts_mod.map_or(vis.clone(), |_| nested_visibility(vis));
+    let parts_path = parts_mod.map(|parts_mod| quote!(#parts_mod::));
+
+    let mut used = UsedParams::default();
+    if let Some(field_type) = field_type {
+        used.add(field_type, generics);
+    }
+
+    let part_lifetimes = &used.lifetimes;
+    let part_args = used.args();
+    let spec_lifetimes = spec_lifetimes(part_lifetimes.len());
+
+    if used.has_non_lifetimes() {
+        let type_params = &used.type_params;
+        let const_params = used
+            .const_params
+            .iter()
+            .map(|(ident, ty)| quote!(#ident: #ty));
+        declarations.push(quote! {
+            ::partial_ref::part!(
+                @generic_template #part_ident
+                (#(#part_lifetimes),*) (#(#type_params),*) (#(#const_params),*)
+                (#vis) (#part_type) (#field_type) #spec_lifetimes
+            );
+        });
+        parse_quote!(#parts_path #part_ident<#part_args>)
+    } else if part_lifetimes.is_empty() {
+        declarations.push(quote! {
+            ::partial_ref::part!(@template #part_ident () (#vis) (#part_type));
+        });
+        parse_quote!(#parts_path #part_ident)
+    } else {
+        declarations.push(quote! {
+            ::partial_ref::part!(
+                @template #part_ident (#(#part_lifetimes),*) (#vis) (#part_type) (#field_type)
+                #spec_lifetimes
+            );
+        });
+        parse_quote!(#parts_path #part_ident<#part_args>)
+    }
+}
+
+/// Declare a part with a const index parameter for the elements of an array field and return the
+/// part's type for each index.
+///
+/// The index is the last parameter of the part, following the generic parameters among `generics`
+/// that appear in the element type.
+fn declare_element_parts(
+    declarations: &mut Vec<proc_macro2::TokenStream>,
+    part_ident: &Ident,
+    vis: &Visibility,
+    field_type: &Type,
+    generics: &Generics,
+    parts_mod: Option<&Ident>,
+) -> Vec<Type> {
+    let (element_type, len) = match field_type {
+        Type::Array(array) => match &array.len {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(len), ..
+            }) => (
+                &*array.elem,
+                len.base10_parse::<usize>()
+                    .unwrap_or_else(|_| panic!("could not parse array length `{}`", len)),
+            ),
+            _ => panic!("element parts require an array length given by an integer literal"),
+        },
+        _ => panic!("element parts require a field with an array type"),
+    };
+
+    let vis = &parts_mod.map_or(vis.clone(), |_| nested_visibility(vis));
+    let parts_path = parts_mod.map(|parts_mod| quote!(#parts_mod::));
+
+    let mut used = UsedParams::default();
+    used.add(element_type, generics);
+
+    let part_lifetimes = &used.lifetimes;
+    let type_params = &used.type_params;
+    let const_params = used
+        .const_params
+        .iter()
+        .map(|(ident, ty)| quote!(#ident: #ty));
+    let spec_lifetimes = spec_lifetimes(part_lifetimes.len());
+
+    declarations.push(quote! {
+        ::partial_ref::part!(
+            @generic_template #part_ident
+            (#(#part_lifetimes),*) (#(#type_params),*) (#(#const_params,)* INDEX: usize)
+            (#vis) (::partial_ref::Field<#element_type>) (#element_type) #spec_lifetimes
+        );
+    });
+
+    let const_args = used.const_params.iter().map(|(ident, _)| ident);
+    let args = quote!(#(#part_lifetimes,)* #(#type_params,)* #(#const_args,)*);
+
+    (0..len)
+        .map(|index| {
+            let index = syn::Index::from(index);
+            parse_quote!(#parts_path #part_ident<#args #index>)
+        })
+        .collect()
+}
+
+/// Whether the attributes contain a representation that stores the discriminant of an enum
+/// separately from the variant fields.
+fn has_tagged_repr(attrs: &[Attribute]) -> bool {
+    const TAGGED_REPRS: &[&str] = &[
+        "C", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    attrs.iter().any(|attr| {
+        if !attr.path.is_ident("repr") {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    TAGGED_REPRS.iter().any(|&repr| path.is_ident(repr))
                 }
-            }
-        }));
+                _ => false,
+            }),
+            _ => false,
+        }
+    })
+}
+
+/// Whether the attributes contain a packed representation, i.e. `packed` or `packed(N)`.
+fn has_packed_repr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path.is_ident("repr") {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("packed"),
+                NestedMeta::Meta(Meta::List(list)) => list.path.is_ident("packed"),
+                _ => false,
+            }),
+            _ => false,
+        }
+    })
+}
+
+/// Whether a type might be dynamically sized, i.e. whether it is a slice, `str`, a trait object or a
+/// type parameter with a `?Sized` bound.
+fn maybe_unsized(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Slice(_) | Type::TraitObject(_) => true,
+        Type::Group(group) => maybe_unsized(&group.elem, generics),
+        Type::Paren(paren) => maybe_unsized(&paren.elem, generics),
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.is_ident("str")
+                || generics.type_params().any(|type_param| {
+                    path.path.is_ident(&type_param.ident)
+                        && type_param.bounds.iter().any(|bound| {
+                            matches!(
+                                bound,
+                                TypeParamBound::Trait(TraitBound {
+                                    modifier: TraitBoundModifier::Maybe(_),
+                                    ..
+                                })
+                            )
+                        })
+                })
+        }
+        _ => false,
     }
+}
 
-    result.into_iter().collect()
+/// Generate a new lifetime that doesn't conflict with the existing lifetimes.
+fn fresh_lifetime<'a>(lifetimes: impl Iterator<Item = &'a LifetimeDef>, name: &str) -> Lifetime {
+    let mut used_idents = HashSet::new();
+    for lifetime in lifetimes {
+        used_idents.insert(lifetime.lifetime.ident.to_string());
+    }
+
+    let mut lifetime_name = name.to_owned();
+    let mut counter = 0;
+
+    while used_idents.contains(&lifetime_name) {
+        use std::fmt::Write;
+        counter += 1;
+        lifetime_name.clear();
+        write!(&mut lifetime_name, "{}{}", name, counter).unwrap();
+    }
+
+    Lifetime::new(&format!("'{}", lifetime_name), Span::call_site())
+}
+
+/// Statement checking that the field type of `part` is `field_type`.
+///
+/// This is spanned at the field's type, so that a mismatch is reported there instead of within
+/// the generated code. As the generated pointer projections cast to the part's pointer type, every
+/// `HasPart` impl of a struct field has to contain this check.
+fn field_type_check(field_type: &Type, part: &Type) -> proc_macro2::TokenStream {
+    quote_spanned! {field_type.span()=>
+        ::partial_ref::check_field_type::<#part, #field_type>();
+    }
+}
+
+/// Check that `part` is an `UnalignedField` part for a field of a packed struct with type
+/// `field_type`.
+///
+/// Like [`field_type_check`] this is spanned at the field's type.
+fn unaligned_field_type_check(field_type: &Type, part: &Type) -> proc_macro2::TokenStream {
+    quote_spanned! {field_type.span()=>
+        ::partial_ref::check_unaligned_field_type::<#part, #field_type>();
+    }
+}
+
+/// Where clause containing `predicates`, which is empty when there are no predicates.
+fn where_bounds_clause(predicates: &[WherePredicate]) -> proc_macro2::TokenStream {
+    if predicates.is_empty() {
+        quote!()
+    } else {
+        quote!(where #(#predicates,)*)
+    }
+}
+
+/// Derives instances of PartialRefTarget and associated traits.
+///
+/// Can be used for structs and enums. The attribute `#[part(PartName)]` can be used on the struct
+/// itself for an abstract part or on a field for a field part. Parts have to be declared
+/// separately. `PartName` can be any valid rust type that implements the Part trait. For fields the
+/// field type of the part has to match the actual type of the field, a mismatch is reported at the
+/// type of the field.
+///
+/// Example:
+///
+/// ```ignore
+/// use partial_ref::{PartialRefTarget, part};
+///
+/// #[derive(PartialRefTarget)]
+/// #[part(SomeAbstractPart)]
+/// struct ExampleStruct {
+///     field_without_part: usize,
+///     #[part(SomeFieldPart)]
+///     a: usize,
+///     #[part(another_crate::AnotherFieldPart)]
+///     b: usize,
+/// }
+/// ```
+///
+/// A field can have additional `#[part(AbstractPart)]` attributes following its first part. These
+/// have to be abstract parts, which are independent of the field's own part, so that e.g. a module
+/// can require exclusive ownership of a field without accessing it. Multiple fields can share such
+/// an abstract part.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleAlias {
+///     #[part(SomeFieldPart)]
+///     #[part(SomeAbstractPart)]
+///     a: usize,
+/// }
+/// ```
+///
+/// For tuple structs the field attributes are placed in front of the unnamed fields, as in
+/// `struct ExampleTuple(#[part(SomeFieldPart)] usize, usize);`.
+///
+/// Parts can use the generic parameters of the struct, e.g. a field `items: Vec<T>` of a struct
+/// `Storage<T>` can have the part `#[part(Items<T>)]` declared using `part!(Items<T>: Vec<T>)`.
+/// The same works for const parameters, e.g. `part!(Front<const N: usize>: [u8; N])`.
+///
+/// Fields and variants that are disabled by a `#[cfg(...)]` attribute are removed before the derive
+/// runs, so they get no parts and are not covered by any group part.
+///
+/// Instead of `#[part(PartName)]` it is also possible to use `#[part = "PartName"]` which was the
+/// only supported syntax in previous versions of this crate.
+///
+/// Using `#[part(auto)]` on a field declares a new part for that field. The part is named after
+/// the field converted to upper camel case, so a field `clause_db` gets a part `ClauseDb`, and has
+/// the visibility of the field. If the field's type uses lifetime, type or const parameters of the
+/// struct, the part has these parameters too. Unlike parts declared using `part!`, which support
+/// at most three lifetimes, automatic parts can have any number of lifetimes. Using
+/// `#[part(auto)]` on the struct itself declares such a part for every field that has no
+/// `#[part(...)]` attribute.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleAuto {
+///     #[part(auto)]
+///     clause_db: Vec<usize>, // part `ClauseDb`
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(prefix = "Gfx")]` prepends a prefix to the names of all automatic
+/// parts, including automatic variant parts, so a field `colors` gets a part `GfxColors`. This
+/// avoids name collisions between parts of different targets declared in the same module.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(prefix = "Gfx")]
+/// #[part(auto)]
+/// struct ExamplePrefix {
+///     colors: Vec<u32>, // part `GfxColors`
+/// }
+/// ```
+///
+/// The attribute `#[part_group(GroupPart: field_a, field_b, ...)]` on a struct declares a part
+/// `GroupPart` of type `FieldGroup` that covers all listed fields. Access to this part yields a
+/// struct `GroupPartRef` or `GroupPartMut` containing a reference to each of the fields. The fields
+/// of a group cannot have a part of their own and each field can be listed in only one group.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[part_group(Stats: hits, misses)]
+/// struct ExampleGroup {
+///     hits: usize,
+///     misses: usize,
+/// }
+///
+/// fn record_hit(mut example: partial!(ExampleGroup, mut Stats)) {
+///     let stats: StatsMut = example.group_mut(Stats);
+///     *stats.hits += 1;
+/// }
+/// ```
+///
+/// A field with a `#[part(skip)]` attribute gets no part of its own, even when `#[part(auto)]` is
+/// used on the struct. Instead all skipped fields are covered by a group part named after the
+/// struct with a `Rest` suffix, e.g. `ExampleStructRest`. This allows partial references to keep
+/// track of the remaining fields.
+///
+/// A field whose type is itself a `PartialRefTarget` can be flattened using
+/// `#[part(flatten(InnerPartA, InnerPartB, ...))]`. This makes the listed parts of the field's type
+/// parts of the containing struct, so they can be used without nesting. The flattened field has no
+/// part of its own.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleOuter {
+///     #[part(flatten(SomeFieldPart, another_crate::AnotherFieldPart))]
+///     inner: ExampleStruct,
+/// }
+/// ```
+///
+/// A struct wrapping a `PartialRefTarget` can mark the wrapped field with `#[part(transparent)]`.
+/// References to the struct then convert into partial references to the wrapped value, so code
+/// using partial references of the wrapped type works unchanged. Such a struct cannot have any
+/// parts and is not a `PartialRefTarget` itself.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleWrapper(#[part(transparent)] ExampleStruct);
+/// ```
+///
+/// A field with an array type `[T; N]` can have a part for each element using
+/// `#[part(elements(ElementPart))]`, where `N` has to be an integer literal. This declares a part
+/// `ElementPart<const INDEX: usize>` with field type `T`, so that `ElementPart<0>` to
+/// `ElementPart<{N - 1}>` are parts of the struct. The index follows the parameters of the struct
+/// used by `T`.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleElements {
+///     #[part(elements(Bank))]
+///     banks: [usize; 4], // parts `Bank<0>` to `Bank<3>`
+/// }
+/// ```
+///
+/// For the parts of struct and union fields, including element parts and pinned parts, the derive
+/// also implements `PartOffset`, which gives the offset of the part within the target.
+///
+/// Every derived target implements `PartMetadata`, which lists the names and kinds of all parts of
+/// the target and the fields they belong to, e.g. for logging. The order of that list is also the
+/// canonical order of parts given by `CanonicalTarget`, which is used by `canonicalize`.
+///
+/// Targets without pinned fields implement `FullRefTarget`, so that a partial reference having
+/// every part as mutable part can be converted back into a mutable reference using `into_full`.
+///
+/// Structs and enums also implement `VisitParts`, which calls a `PartVisitor` with the name and a
+/// reference to the field of each field part, e.g. to dump or checksum all fields without listing
+/// them. This is not implemented for unions and packed structs.
+///
+/// ```ignore
+/// struct Dump;
+///
+/// impl<T: std::fmt::Debug + ?Sized> PartVisitor<T> for Dump {
+///     fn visit(&mut self, name: &'static str, field: &T) {
+///         println!("{}: {:?}", name, field);
+///     }
+/// }
+///
+/// example.visit_parts(&mut Dump);
+/// ```
+///
+/// With the `serde` feature of `partial_ref` enabled, the parts of struct fields, including element
+/// parts and parts of dereferenced fields, implement `SerdePart`, which names the part. Partial
+/// references containing only such parts can then be serialized using `SerializeParts`, which writes
+/// a map from part names to the values of the fields, and mutable partial references can be
+/// restored from such a map using `DeserializeParts`. This serializes exactly the parts listed in
+/// the partial reference, e.g. to checkpoint a part of a larger state.
+///
+/// ```ignore
+/// fn checkpoint(example: partial!(ExampleStruct, SomeFieldPart)) -> String {
+///     serde_json::to_string(&example).unwrap()
+/// }
+/// ```
+///
+/// The last field of a struct can be dynamically sized, e.g. a slice `[T]` or `str`, and have its
+/// own part like any other field. As the offset of such a field can depend on the length of the
+/// slice, its part has no `PartOffset` impl. A field is considered dynamically sized when its type
+/// is a slice, `str`, a trait object or a type parameter with a `?Sized` bound.
+///
+/// A field can be structurally pinned using `#[part(pin, PinnedPart)]`, where the part is declared
+/// as `part!(pin PinnedPart: FieldType)`. Partial references created from a pinned reference
+/// `Pin<&mut Target>` contain such parts, which provide pinned access to the field, while partial
+/// references created from plain references don't. Like for pin projections, a target with pinned
+/// fields cannot implement `Drop` and is only `Unpin` if all pinned fields are `Unpin`.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExamplePin {
+///     #[part(pin, SomePinnedPart)]
+///     future: std::future::Ready<usize>,
+/// }
+/// ```
+///
+/// A field of type `Box<T>` or `&mut T` can have a part for the value it points to using
+/// `#[part(deref, PartName)]`. The field type of such a part is `T`, which can be a trait object,
+/// so a field `Box<dyn Trait>` can have a part declared as `part!(PartName: dyn Trait)`. A field
+/// of type `Rc<T>`, `Arc<T>` or `&T` can have such a part, too, but as the value may be shared, the
+/// part has to be declared as a `SharedField` part, i.e. using `part!(shared PartName: T)`, which
+/// only provides shared access. View structs don't support such parts.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleDeref {
+///     #[part(deref, SomeFieldPart)]
+///     heap: Box<usize>,
+/// }
+/// ```
+///
+/// The attribute `#[part(virtual(PartA, PartB, ...))]` on the target itself adds virtual parts,
+/// which are not fields of the target but computed by the parts' implementations of
+/// `VirtualPart`. This can be used for views into fields that have no parts of their own.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[part(virtual(FirstHalf, SecondHalf))]
+/// struct ExampleVirtual {
+///     samples: Vec<f32>,
+/// }
+/// ```
+///
+/// Similarly, the attribute `#[part(thread_local(PartA, PartB, ...))]` adds parts stored in
+/// thread-local storage, which are declared using `part!(thread_local PartName: FieldType)`. These
+/// are `ThreadLocalField` parts, which are only accessible for the duration of a closure using
+/// `with_local_part` and `with_local_part_mut`.
+///
+/// ```ignore
+/// part!(thread_local Scratch: Vec<u32>);
+///
+/// #[derive(PartialRefTarget)]
+/// #[part(thread_local(Scratch))]
+/// struct ExampleThreadLocal {
+///     #[part(auto)]
+///     values: Vec<u32>,
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(doc_hidden)]` marks all trait impls generated by the derive as
+/// `#[doc(hidden)]`, so they don't show up in the documentation of the target. Parts declared by
+/// the derive are not hidden. Parts declared for fields are never more visible than the target, so
+/// the parts of `pub` fields of a `pub(crate)` target are `pub(crate)`. As trait impls have no
+/// visibility of their own, this keeps partial references of a crate-private target from being
+/// named outside of the crate.
+///
+/// For unions, the parts of fields have to be declared as `UnionField` parts, i.e. using
+/// `part!(union FieldPart: FieldType)`, and automatic parts of union fields are such parts. As the
+/// fields of a union overlap, these parts can only be accessed using the unsafe `part_unchecked`
+/// and `part_unchecked_mut` methods of partial references.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// union ExampleUnion {
+///     #[part(SomeUnionFieldPart)]
+///     a: u32,
+///     #[part(auto)]
+///     b: f32, // part `B`
+/// }
+/// ```
+///
+/// For structs with a packed representation, i.e. `#[repr(packed)]` or `#[repr(packed(N))]`, the
+/// parts of fields have to be declared as `UnalignedField` parts, i.e. using
+/// `part!(unaligned FieldPart: FieldType)`, and automatic parts of their fields are such parts. As
+/// the fields might be unaligned, these parts are accessed using the `unaligned_part` and
+/// `unaligned_part_mut` methods of partial references, which return wrappers that read and write
+/// the field's value instead of references. Fields of packed structs cannot be flattened,
+/// dereferenced, pinned, skipped or contained in groups and cannot have element parts.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[repr(C, packed)]
+/// struct ExamplePacked {
+///     #[part(SomeUnalignedFieldPart)]
+///     tag: u8,
+///     #[part(auto)]
+///     value: u32, // part `Value`
+/// }
+///
+/// fn example(mut example: partial!(ExamplePacked, mut Value)) {
+///     example.unaligned_part_mut(Value).update(|value| *value += 1);
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(parts_mod = "parts")]` on the struct places all parts declared by
+/// the derive, including group parts and their `Ref` and `Mut` structs, in a module `parts` next
+/// to the struct. The module has the visibility of the struct and private parts become visible in
+/// the enclosing module, so they can be used as `parts::PartName`.
+///
+/// The attribute `#[partial_ref(raw = "UnsafeCell<Self>")]` on the struct or enum sets the
+/// `RawTarget` of the target, i.e. the type partial references point to. The given type has to
+/// implement `RawTargetOf` for the target, which is the case for `UnsafeCell<Self>` and
+/// `Cell<Self>`. Such a target cannot be used as the field of a nested part.
+///
+/// The attribute `#[partial_ref(no_nested)]` skips the `SplitIntoParts` impl, which is a large part
+/// of the generated code for targets with many parts. A partial reference containing the part of
+/// a field of such a target cannot be split into nested parts of that field, so nested parts can't
+/// be used with the target.
+///
+/// The attribute `#[partial_ref(split_helpers)]` on a struct declares a trait named after the
+/// struct, e.g. `ExampleSplit` for `Example`, which is implemented for all partial references to
+/// the struct. For each field with a field part it has methods `split_field` and `split_field_mut`
+/// that are equivalent to calling `split_part` and `split_part_mut` with the field's part. For
+/// unnamed fields the methods are named after the index, e.g. `split_0_mut`. When the struct has
+/// lifetime parameters, type parameters used with them in parts need explicit bounds like
+/// `T: 'a`.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(split_helpers)]
+/// struct ExampleHelpers { // trait `ExampleHelpersSplit`
+///     #[part(auto)]
+///     colors: Vec<usize>,
+/// }
+///
+/// fn example(mut example: partial!(ExampleHelpers, mut Colors)) {
+///     let (colors, example) = example.split_colors_mut();
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(view)]` on a struct declares the structs `ExampleView` and
+/// `ExampleViewMut` for a struct `Example`, next to the declared parts. They have a field of plain
+/// references for each field with a field part, using the value for dereferenced fields and an
+/// array of references for fields with element parts. Partial references having all these parts
+/// can be converted into them using `to_view` and, if all parts are mutable, `to_view_mut`. This
+/// requires named fields and is not supported for packed structs.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(view)]
+/// struct Example {
+///     #[part(auto)]
+///     colors: Vec<usize>,
+///     #[part(auto)]
+///     weights: Vec<f32>,
+/// }
+///
+/// fn example(mut example: partial!(Example, mut Colors, mut Weights)) {
+///     let ExampleViewMut { colors, weights } = example.to_view_mut();
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(from_parts)]` on a struct generates an associated function
+/// `from_parts` with the visibility of the struct. It takes a value for each field that has its own
+/// part, in the order of the fields, and uses `Default::default()` for all other fields, e.g. the
+/// fields of group parts. Arguments for unnamed fields are named after their index, e.g. `field_0`.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(from_parts)]
+/// struct ExampleFromParts {
+///     #[part(SomeFieldPart)]
+///     a: usize,
+///     b: Vec<usize>,
+/// }
+///
+/// let example = ExampleFromParts::from_parts(1); // `b` is empty
+/// ```
+///
+/// The attribute `#[partial_ref(parts_trait = "ExampleParts")]` declares a trait `ExampleParts`
+/// next to the target that has `HasPart` for every part of the target as supertraits. It has the
+/// generic parameters of the target and is implemented for every type having all these parts, so
+/// functions bounded by it work for the target as well as for other targets with the same parts,
+/// e.g. a smaller struct used in tests.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(parts_trait = "ExampleParts")]
+/// struct ExamplePartsTrait {
+///     #[part(SomeFieldPart)]
+///     a: usize,
+/// }
+///
+/// fn generic<T: ExampleParts>(mut example: partial!(T, mut SomeFieldPart)) {
+///     *example.part_mut(SomeFieldPart) += 1;
+/// }
+/// ```
+///
+/// The attribute `#[partial_ref(bound = "T: Trait<Assoc = usize>")]` adds the given predicates to
+/// the where clauses of all trait impls generated by the derive. This is needed when the derive
+/// cannot infer a required bound, e.g. when a field's type is an associated type that has to match
+/// the type of its part.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[partial_ref(bound = "T: Iterator<Item = usize>")]
+/// struct ExampleBound<T: Iterator> {
+///     #[part(SomeFieldPart)]
+///     next: Option<T::Item>,
+///     iter: T,
+/// }
+/// ```
+///
+/// A bound that is only needed for a single field can be given using a
+/// `#[part(bound = "T: Trait<Assoc = usize>")]` attribute of that field. It is added to the `HasPart`
+/// impl of the field's part, so generic code that uses other parts of the target doesn't require
+/// it. Impls involving all parts, like those converting references into partial references, still
+/// get the bound. Such bounds are supported for fields of structs having a field, pinned or
+/// dereferenced part.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// struct ExampleFieldBound<T: Iterator> {
+///     #[part(SomeFieldPart)]
+///     #[part(bound = "T: Iterator<Item = usize>")]
+///     next: Option<T::Item>,
+///     #[part(SomeIterPart<T>)]
+///     iter: T,
+/// }
+/// ```
+///
+/// For enums, each variant that has fields with parts needs a variant part, which is an abstract
+/// part given by a `#[part(VariantPart)]` attribute on the variant. The parts of the variant's
+/// fields have to be declared as `VariantField` parts of that variant, i.e. using
+/// `part!(FieldPart in VariantPart: FieldType)`. Such an enum needs a `#[repr(...)]` attribute
+/// that stores the discriminant separately from the fields, i.e. `#[repr(C)]` or a primitive
+/// representation like `#[repr(u8)]`. Automatic variant parts are named after the variant and
+/// automatic parts of variant fields have the visibility of the enum.
+///
+/// ```ignore
+/// #[derive(PartialRefTarget)]
+/// #[repr(u8)]
+/// enum ExampleEnum {
+///     #[part(SomeVariantPart)]
+///     SomeVariant {
+///         #[part(SomeVariantFieldPart)]
+///         a: usize,
+///     },
+///     OtherVariant,
+/// }
+/// ```
+// TODO figure out how to link to doc items of the partial_ref crate
+#[proc_macro_derive(PartialRefTarget, attributes(part, part_group, partial_ref))]
+pub fn derive_partial_ref_target(input: TokenStream) -> TokenStream {
+    expand_partial_ref_target(parse_macro_input!(input as DeriveInput))
+}
+
+/// Generate the impls and part declarations for a target, shared by the derive and the attribute
+/// macro.
+fn expand_partial_ref_target(input: DeriveInput) -> TokenStream {
+    let target_ident = input.ident;
+    let target_vis = input.vis;
+    let generics = &input.generics;
+
+    let lt_a = fresh_lifetime(generics.lifetimes(), "a");
+
+    let (impl_generics, target_generics, where_clause) = generics.split_for_impl();
+
+    if where_clause.is_some() {
+        panic!("cannot derive PartialRef target for structs with a where clause");
+        // TODO lift this restriction
+    }
+
+    let extra_generics = generics_to_extra_generics(&impl_generics);
+
+    let target_type = quote!(#target_ident #target_generics);
+
+    let lifetimes: Vec<Lifetime> = generics
+        .lifetimes()
+        .map(|lifetime| lifetime.lifetime.clone())
+        .collect();
+
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|type_param| type_param.ident.clone())
+        .collect();
+
+    let mut abstract_parts: Vec<Type> = vec![];
+    let mut typed_parts: Vec<(Member, Type, Type)> = vec![];
+    let mut variant_parts: Vec<(Ident, Type)> = vec![];
+    let mut variant_field_parts: Vec<(Ident, Member, Type, Type)> = vec![];
+    let mut auto = false;
+    let mut groups: Vec<PartGroup> = vec![];
+    let mut group_parts: Vec<Type> = vec![];
+    let mut flattened_parts: Vec<(Member, Type, Vec<Type>)> = vec![];
+    let mut deref_parts: Vec<(Member, Type, Type)> = vec![];
+    let mut pinned_parts: Vec<(Member, Type, Type)> = vec![];
+    let mut element_parts: Vec<(Member, Type, Vec<Type>)> = vec![];
+    let mut union_parts: Vec<(Member, Type, Type)> = vec![];
+    let mut virtual_parts: Vec<Type> = vec![];
+    let mut thread_local_parts: Vec<Type> = vec![];
+    let mut field_bounds: Vec<(Member, Vec<WherePredicate>)> = vec![];
+    let mut unsized_member: Option<Member> = None;
+    let mut from_parts_impl = None;
+    let mut declarations = vec![];
+    let mut group_impls = vec![];
+
+    let options = parse_target_options(&input.attrs);
+
+    let doc_hidden = if options.doc_hidden {
+        quote!(#[doc(hidden)])
+    } else {
+        quote!()
+    };
+
+    // Bounds are added to impls that have a where clause and to those that don't. The bounds of
+    // fields are added once the fields are parsed.
+    let mut bounds = options.bounds.clone();
+    let where_bounds = where_bounds_clause(&bounds);
+    let mut all_where_bounds = where_bounds.clone();
+
+    for attr in input.attrs.iter() {
+        if attr.path.is_ident("part") {
+            match parse_part_attribute(attr) {
+                PartAttr::Declared(part) => abstract_parts.push(part),
+                PartAttr::Auto => auto = true,
+                PartAttr::Skip => panic!("only fields can be skipped"),
+                PartAttr::Transparent => panic!("only fields can be transparent"),
+                PartAttr::Flatten(_) => panic!("only fields can be flattened"),
+                PartAttr::Deref(_) => panic!("only fields can be dereferenced"),
+                PartAttr::Pin(_) => panic!("only fields can be pinned"),
+                PartAttr::Elements(_) => panic!("only array fields can have element parts"),
+                PartAttr::Virtual(parts) => virtual_parts.extend(parts),
+                PartAttr::ThreadLocal(parts) => thread_local_parts.extend(parts),
+                PartAttr::Bound(_) => {
+                    panic!("bounds of targets are given using `#[partial_ref(...)]`")
+                }
+            }
+        }
+    }
+
+    if options.from_parts && !matches!(input.data, Data::Struct(_)) {
+        panic!("only structs can have a from_parts constructor");
+    }
+
+    if options.view && !matches!(input.data, Data::Struct(_)) {
+        panic!("only structs can have view structs");
+    }
+
+    // Fields of packed structs might be unaligned, so their parts cannot hand out references.
+    let packed = has_packed_repr(&input.attrs);
+
+    if packed && options.split_helpers {
+        panic!("split_helpers are not supported for packed structs");
+    }
+
+    // References to the fields of unions and packed structs cannot be passed to a visitor.
+    let visit_parts = !packed && !matches!(input.data, Data::Union(_));
+
+    match input.data {
+        Data::Struct(data_struct) => {
+            for attr in input.attrs.iter() {
+                if attr.path.is_ident("part_group") {
+                    groups.push(parse_part_group_attribute(attr));
+                }
+            }
+
+            if let Err(err) = check_part_groups(&groups) {
+                return err.to_compile_error().into();
+            }
+
+            let fields = field_parts(&data_struct.fields, auto, &groups, |ident, field| {
+                let field_type = &field.ty;
+                let part_type = if packed {
+                    quote!(::partial_ref::UnalignedField<#field_type>)
+                } else {
+                    quote!(::partial_ref::Field<#field_type>)
+                };
+                declare_part(
+                    &mut declarations,
+                    &auto_part_ident(ident, options.prefix.as_ref()),
+                    &field_part_visibility(&field.vis, &target_vis),
+                    part_type,
+                    Some(field_type),
+                    generics,
+                    options.parts_mod.as_ref(),
+                )
+            });
+
+            if packed {
+                if fields.transparent.is_some() {
+                    panic!("packed structs cannot have a transparent field");
+                }
+                if !fields.flattened.is_empty() {
+                    panic!("fields of packed structs cannot be flattened");
+                }
+                if !fields.dereferenced.is_empty() {
+                    panic!("fields of packed structs cannot be dereferenced");
+                }
+                if !fields.pinned.is_empty() {
+                    panic!("fields of packed structs cannot be pinned");
+                }
+                if !fields.elements.is_empty() {
+                    panic!("fields of packed structs cannot have element parts");
+                }
+                if !groups.is_empty() || !fields.skipped.is_empty() {
+                    panic!("packed structs cannot have group parts or skipped fields");
+                }
+            }
+
+            if let Some((member, inner_type)) = fields.transparent {
+                let has_parts = !abstract_parts.is_empty()
+                    || !groups.is_empty()
+                    || !fields.typed.is_empty()
+                    || !fields.skipped.is_empty()
+                    || !fields.flattened.is_empty()
+                    || !fields.dereferenced.is_empty()
+                    || !fields.pinned.is_empty()
+                    || !fields.elements.is_empty()
+                    || !fields.aliases.is_empty()
+                    || !virtual_parts.is_empty()
+                    || !thread_local_parts.is_empty();
+                if has_parts {
+                    panic!("a struct with a transparent field cannot have any parts");
+                }
+
+                return TokenStream::from(quote! {
+                    #doc_hidden
+                    impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
+                    for &#lt_a #target_type #where_bounds {
+                        type Ref = <&#lt_a #inner_type as ::partial_ref::IntoPartialRef<#lt_a>>::Ref;
+                        #[inline(always)]
+                        fn into_partial_ref(self) -> Self::Ref {
+                            ::partial_ref::IntoPartialRef::into_partial_ref(&self.#member)
+                        }
+                    }
+
+                    #doc_hidden
+                    impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
+                    for &#lt_a mut #target_type #where_bounds {
+                        type Ref =
+                            <&#lt_a mut #inner_type as ::partial_ref::IntoPartialRef<#lt_a>>::Ref;
+                        #[inline(always)]
+                        fn into_partial_ref(self) -> Self::Ref {
+                            ::partial_ref::IntoPartialRef::into_partial_ref(&mut self.#member)
+                        }
+                    }
+                });
+            }
+
+            // Only the last field of a struct can be dynamically sized.
+            unsized_member = data_struct
+                .fields
+                .iter()
+                .enumerate()
+                .last()
+                .filter(|(_, field)| maybe_unsized(&field.ty, generics))
+                .map(|(index, field)| field_member(index, field));
+
+            typed_parts = fields.typed;
+            field_bounds = fields.bounds;
+            bounds.extend(
+                field_bounds
+                    .iter()
+                    .flat_map(|(_, predicates)| predicates)
+                    .cloned(),
+            );
+            all_where_bounds = where_bounds_clause(&bounds);
+            flattened_parts = fields.flattened;
+            deref_parts = fields.dereferenced;
+            pinned_parts = fields.pinned;
+            abstract_parts.extend(fields.aliases);
+
+            for (member, field_type, vis, part_ident) in fields.elements {
+                let parts = declare_element_parts(
+                    &mut declarations,
+                    &part_ident,
+                    &field_part_visibility(&vis, &target_vis),
+                    &field_type,
+                    generics,
+                    options.parts_mod.as_ref(),
+                );
+                element_parts.push((member, field_type, parts));
+            }
+
+            if options.from_parts {
+                if unsized_member.is_some() {
+                    panic!(
+                        "cannot generate from_parts for a struct with a dynamically sized field"
+                    );
+                }
+
+                let own_parts = typed_parts
+                    .iter()
+                    .map(|(member, _, _)| member)
+                    .chain(flattened_parts.iter().map(|(member, _, _)| member))
+                    .chain(deref_parts.iter().map(|(member, _, _)| member))
+                    .chain(pinned_parts.iter().map(|(member, _, _)| member))
+                    .chain(element_parts.iter().map(|(member, _, _)| member))
+                    .map(member_name)
+                    .collect::<HashSet<_>>();
+
+                let mut params = vec![];
+                let mut inits = vec![];
+                for (index, field) in data_struct.fields.iter().enumerate() {
+                    let member = field_member(index, field);
+                    if own_parts.contains(&member_name(&member)) {
+                        let param = match &member {
+                            Member::Named(ident) => ident.clone(),
+                            Member::Unnamed(_) => format_ident!("field_{}", index),
+                        };
+                        let field_type = &field.ty;
+                        params.push(quote!(#param: #field_type));
+                        inits.push(quote!(#member: #param));
+                    } else {
+                        inits.push(quote!(#member: ::core::default::Default::default()));
+                    }
+                }
+
+                from_parts_impl = Some(quote! {
+                    impl #impl_generics #target_type #where_bounds {
+                        /// Create a value from the values of the fields having a part, using
+                        /// default values for all other fields.
+                        #[allow(clippy::too_many_arguments)]
+                        #target_vis fn from_parts(#(#params),*) -> Self {
+                            Self { #(#inits),* }
+                        }
+                    }
+                });
+            }
+
+            if !fields.skipped.is_empty() {
+                groups.push(PartGroup {
+                    part: format_ident!("{}Rest", target_ident),
+                    fields: fields.skipped,
+                });
+            }
+
+            let lt_group = fresh_lifetime(generics.lifetimes(), "group");
+            let view_vis = options
+                .parts_mod
+                .as_ref()
+                .map_or(target_vis.clone(), |_| nested_visibility(&target_vis));
+            let parts_path = options
+                .parts_mod
+                .as_ref()
+                .map(|parts_mod| quote!(#parts_mod::));
+
+            for group in groups.iter() {
+                let part_type = declare_part(
+                    &mut declarations,
+                    &group.part,
+                    &target_vis,
+                    quote!(::partial_ref::FieldGroup<#target_type>),
+                    Some(&parse_quote!(#target_type)),
+                    generics,
+                    options.parts_mod.as_ref(),
+                );
+
+                let ref_ident = format_ident!("{}Ref", group.part);
+                let mut_ident = format_ident!("{}Mut", group.part);
+
+                let members = &group.fields;
+                let field_types = group
+                    .fields
+                    .iter()
+                    .map(|ident| {
+                        let field = data_struct
+                            .fields
+                            .iter()
+                            .find(|field| field.ident.as_ref() == Some(ident))
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "group part `{}` contains unknown field `{}`",
+                                    group.part, ident
+                                )
+                            });
+                        &field.ty
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut view_params = UsedParams::default();
+                for ty in field_types.iter() {
+                    view_params.add(ty, generics);
+                }
+                let view_args = view_params.args();
+                let view_lifetimes = &view_params.lifetimes;
+                let view_type_params = &view_params.type_params;
+                let view_const_params = view_params
+                    .const_params
+                    .iter()
+                    .map(|(ident, ty)| quote!(const #ident: #ty));
+                let view_generics = quote! {
+                    #lt_group #(, #view_lifetimes)* #(, #view_type_params)* #(, #view_const_params)*
+                };
+
+                declarations.push(quote! {
+                    #view_vis struct #ref_ident<#view_generics> {
+                        #(pub #members: &#lt_group #field_types,)*
+                    }
+
+                    #view_vis struct #mut_ident<#view_generics> {
+                        #(pub #members: &#lt_group mut #field_types,)*
+                    }
+                });
+
+                let ref_ident = quote!(#parts_path #ref_ident);
+                let mut_ident = quote!(#parts_path #mut_ident);
+
+                group_impls.push(quote! {
+                    #doc_hidden
+                    unsafe impl<#lt_group #extra_generics>
+                        ::partial_ref::FieldGroupRefs<#lt_group, #target_type> for #part_type
+                    where
+                        #(#lifetimes: #lt_group,)*
+                        #(#type_params: #lt_group,)*
+                        #(#bounds,)*
+                    {
+                        type Ref = #ref_ident<#lt_group, #view_args>;
+                        type Mut = #mut_ident<#lt_group, #view_args>;
+
+                        #[inline(always)]
+                        unsafe fn group_ref(ptr: *const #target_type) -> Self::Ref {
+                            #ref_ident {
+                                #(#members: &*::core::ptr::addr_of!((*ptr).#members),)*
+                            }
+                        }
+
+                        #[inline(always)]
+                        unsafe fn group_mut(ptr: *mut #target_type) -> Self::Mut {
+                            #mut_ident {
+                                #(#members: &mut *::core::ptr::addr_of_mut!((*ptr).#members),)*
+                            }
+                        }
+                    }
+                });
+
+                group_parts.push(part_type);
+            }
+
+            if options.view {
+                if packed {
+                    panic!("packed structs cannot have view structs");
+                }
+
+                let view_ident = format_ident!("{}View", target_ident);
+                let view_mut_ident = format_ident!("{}ViewMut", target_ident);
+                let lt_view = fresh_lifetime(generics.lifetimes(), "view");
+
+                let mut members = vec![];
+                let mut field_types = vec![];
+                let mut parts = vec![];
+                let mut refs = vec![];
+                let mut muts = vec![];
+
+                let view_member = |member: &Member| match member {
+                    Member::Named(ident) => ident.clone(),
+                    Member::Unnamed(_) => panic!("view structs require named fields"),
+                };
+                let part_ptr = |part: &Type| {
+                    (
+                        quote! {
+                            &*<#target_type as ::partial_ref::HasPart<#part>>::part_ptr(ptr)
+                        },
+                        quote! {
+                            &mut *<#target_type as ::partial_ref::HasPart<#part>>::part_ptr_mut(ptr)
+                        },
+                    )
+                };
+
+                for (member, field_type, part) in typed_parts.iter() {
+                    let (part_ref, part_mut) = part_ptr(part);
+                    members.push(view_member(member));
+                    field_types.push((
+                        quote!(&#lt_view #field_type),
+                        quote!(&#lt_view mut #field_type),
+                    ));
+                    parts.push(part.clone());
+                    refs.push(part_ref);
+                    muts.push(part_mut);
+                }
+                for (member, field_type, part) in deref_parts.iter() {
+                    let (part_ref, part_mut) = part_ptr(part);
+                    let target = quote!(<#field_type as ::partial_ref::DerefField>::Target);
+                    members.push(view_member(member));
+                    field_types.push((quote!(&#lt_view #target), quote!(&#lt_view mut #target)));
+                    parts.push(part.clone());
+                    refs.push(part_ref);
+                    muts.push(part_mut);
+                }
+                for (member, field_type, element_parts) in element_parts.iter() {
+                    let (len, element_type) = match field_type {
+                        Type::Array(array) => (&array.len, &*array.elem),
+                        _ => unreachable!(),
+                    };
+                    let (part_refs, part_muts): (Vec<_>, Vec<_>) =
+                        element_parts.iter().map(part_ptr).unzip();
+                    members.push(view_member(member));
+                    field_types.push((
+                        quote!([&#lt_view #element_type; #len]),
+                        quote!([&#lt_view mut #element_type; #len]),
+                    ));
+                    parts.extend(element_parts.iter().cloned());
+                    refs.push(quote!([#(#part_refs),*]));
+                    muts.push(quote!([#(#part_muts),*]));
+                }
+
+                let mut view_params = UsedParams::default();
+                for field_type in typed_parts
+                    .iter()
+                    .chain(deref_parts.iter())
+                    .map(|(_, field_type, _)| field_type)
+                    .chain(element_parts.iter().map(|(_, field_type, _)| field_type))
+                {
+                    view_params.add(field_type, generics);
+                }
+                let view_args = view_params.args();
+                let view_lifetimes = &view_params.lifetimes;
+                let view_type_params = &view_params.type_params;
+                let view_const_params = view_params
+                    .const_params
+                    .iter()
+                    .map(|(ident, ty)| quote!(const #ident: #ty));
+                let view_generics = quote! {
+                    #lt_view #(, #view_lifetimes)* #(, #view_type_params)* #(, #view_const_params)*
+                };
+
+                let (ref_types, mut_types): (Vec<_>, Vec<_>) = field_types.into_iter().unzip();
+
+                declarations.push(quote! {
+                    /// References to the fields of all field parts.
+                    #view_vis struct #view_ident<#view_generics> {
+                        #(pub #members: #ref_types,)*
+                    }
+
+                    /// Mutable references to the fields of all field parts.
+                    #view_vis struct #view_mut_ident<#view_generics> {
+                        #(pub #members: #mut_types,)*
+                    }
+                });
+
+                let view_ident = quote!(#parts_path #view_ident);
+                let view_mut_ident = quote!(#parts_path #view_mut_ident);
+
+                group_impls.push(quote! {
+                    #doc_hidden
+                    unsafe impl<#lt_view #extra_generics> ::partial_ref::TargetView<#lt_view>
+                        for #target_type
+                    where
+                        #(#lifetimes: #lt_view,)*
+                        #(#type_params: #lt_view,)*
+                        #(#bounds,)*
+                    {
+                        type View = #view_ident<#lt_view, #view_args>;
+                        type ViewMut = #view_mut_ident<#lt_view, #view_args>;
+                        type ViewRef = ::partial_ref::partial!(#lt_view #target_type, #(#parts),*);
+                        type ViewMutRef =
+                            ::partial_ref::partial!(#lt_view #target_type, #(mut #parts),*);
+
+                        #[inline(always)]
+                        unsafe fn view(ptr: *const Self::RawTarget) -> Self::View {
+                            #view_ident {
+                                #(#members: #refs,)*
+                            }
+                        }
+
+                        #[inline(always)]
+                        unsafe fn view_mut(ptr: *mut Self::RawTarget) -> Self::ViewMut {
+                            #view_mut_ident {
+                                #(#members: #muts,)*
+                            }
+                        }
+                    }
+                });
+            }
+        }
+        Data::Enum(data_enum) => {
+            for variant in data_enum.variants.iter() {
+                let variant_ident = &variant.ident;
+
+                let variant_attr =
+                    single_part(&variant.attrs, || format!("variant `{}`", variant_ident));
+
+                let variant_part =
+                    match variant_attr.or(if auto { Some(PartAttr::Auto) } else { None }) {
+                        Some(PartAttr::Declared(part)) => Some(part),
+                        Some(PartAttr::Skip) => panic!("only fields can be skipped"),
+                        Some(PartAttr::Transparent) => panic!("only fields can be transparent"),
+                        Some(PartAttr::Flatten(_)) => panic!("only fields can be flattened"),
+                        Some(PartAttr::Deref(_)) => panic!("only fields can be dereferenced"),
+                        Some(PartAttr::Pin(_)) => panic!("only fields can be pinned"),
+                        Some(PartAttr::Elements(_)) => {
+                            panic!("only array fields can have element parts")
+                        }
+                        Some(PartAttr::Virtual(_)) => {
+                            panic!("only targets can have virtual parts")
+                        }
+                        Some(PartAttr::ThreadLocal(_)) => {
+                            panic!("only targets can have thread-local parts")
+                        }
+                        Some(PartAttr::Bound(_)) => panic!("only fields can have bounds"),
+                        Some(PartAttr::Auto) => Some(declare_part(
+                            &mut declarations,
+                            &options
+                                .prefix
+                                .as_ref()
+                                .map_or(variant_ident.clone(), |prefix| {
+                                    format_ident!("{}{}", prefix, variant_ident)
+                                }),
+                            &target_vis,
+                            quote!(::partial_ref::AbstractPart),
+                            None,
+                            generics,
+                            options.parts_mod.as_ref(),
+                        )),
+                        None => None,
+                    };
+
+                let no_variant_part = || -> ! {
+                    panic!(
+                        "variant `{}` has fields with parts but no variant part",
+                        variant_ident
+                    )
+                };
+
+                let fields = field_parts(&variant.fields, auto, &[], |ident, field| {
+                    let field_type = &field.ty;
+                    let variant_part = variant_part.as_ref().unwrap_or_else(|| no_variant_part());
+                    declare_part(
+                        &mut declarations,
+                        &auto_part_ident(ident, options.prefix.as_ref()),
+                        &target_vis,
+                        quote!(::partial_ref::VariantField<#variant_part, #field_type>),
+                        Some(field_type),
+                        generics,
+                        options.parts_mod.as_ref(),
+                    )
+                });
+
+                if !fields.flattened.is_empty() {
+                    panic!("flattening fields of enum variants is not supported");
+                }
+
+                if !fields.dereferenced.is_empty() {
+                    panic!("dereferencing fields of enum variants is not supported");
+                }
+
+                if !fields.elements.is_empty() {
+                    panic!("element parts for fields of enum variants are not supported");
+                }
+
+                if fields.transparent.is_some() {
+                    panic!("transparent fields of enum variants are not supported");
+                }
+
+                if !fields.pinned.is_empty() {
+                    panic!("pinned fields of enum variants are not supported");
+                }
+
+                if !fields.bounds.is_empty() {
+                    panic!("bounds of fields of enum variants are not supported");
+                }
+
+                if let Some(variant_part) = variant_part {
+                    variant_parts.push((variant_ident.clone(), variant_part));
+                } else if !fields.typed.is_empty() {
+                    no_variant_part();
+                }
+
+                abstract_parts.extend(fields.aliases);
+
+                for (member, field_type, part) in fields.typed {
+                    variant_field_parts.push((variant_ident.clone(), member, field_type, part));
+                }
+            }
+
+            if !variant_field_parts.is_empty() && !has_tagged_repr(&input.attrs) {
+                panic!(
+                    "deriving PartialRefTarget for enums with variant field parts requires \
+                     #[repr(C)] or a primitive representation"
+                );
+            }
+        }
+        Data::Union(data_union) => {
+            if packed {
+                panic!("packed unions are not supported");
+            }
+            let fields = field_parts(
+                &Fields::Named(data_union.fields),
+                auto,
+                &[],
+                |ident, field| {
+                    let field_type = &field.ty;
+                    declare_part(
+                        &mut declarations,
+                        &auto_part_ident(ident, options.prefix.as_ref()),
+                        &field_part_visibility(&field.vis, &target_vis),
+                        quote!(::partial_ref::UnionField<#field_type>),
+                        Some(field_type),
+                        generics,
+                        options.parts_mod.as_ref(),
+                    )
+                },
+            );
+
+            if !fields.skipped.is_empty() {
+                panic!("skipping fields of unions is not supported");
+            }
+
+            if !fields.flattened.is_empty() {
+                panic!("flattening fields of unions is not supported");
+            }
+
+            if !fields.dereferenced.is_empty() {
+                panic!("dereferencing fields of unions is not supported");
+            }
+
+            if !fields.elements.is_empty() {
+                panic!("element parts for fields of unions are not supported");
+            }
+
+            if fields.transparent.is_some() {
+                panic!("transparent fields of unions are not supported");
+            }
+
+            if !fields.pinned.is_empty() {
+                panic!("pinned fields of unions are not supported");
+            }
+
+            if !fields.bounds.is_empty() {
+                panic!("bounds of fields of unions are not supported");
+            }
+
+            union_parts = fields.typed;
+            abstract_parts.extend(fields.aliases);
+        }
+    }
+
+    // Several fields can share an abstract part.
+    let mut seen_abstract_parts = HashSet::new();
+    abstract_parts.retain(|part| seen_abstract_parts.insert(part.to_token_stream().to_string()));
+
+    let all_parts = abstract_parts
+        .iter()
+        .chain(variant_parts.iter().map(|(_, part)| part))
+        .chain(typed_parts.iter().map(|(_, _, part)| part))
+        .chain(group_parts.iter())
+        .chain(flattened_parts.iter().flat_map(|(_, _, parts)| parts))
+        .chain(deref_parts.iter().map(|(_, _, part)| part))
+        .chain(element_parts.iter().flat_map(|(_, _, parts)| parts))
+        .chain(union_parts.iter().map(|(_, _, part)| part))
+        .chain(variant_field_parts.iter().map(|(_, _, _, part)| part))
+        .chain(virtual_parts.iter())
+        .chain(thread_local_parts.iter())
+        .collect::<Vec<_>>();
+
+    let mut const_type = quote!(::partial_ref::Ref<#lt_a, #target_type>);
+    let mut mut_type = quote!(::partial_ref::Ref<#lt_a, #target_type>);
+    let mut split_const_type = quote!(Reference);
+    let mut split_mut_type = quote!(Reference);
+
+    for part in all_parts.iter() {
+        const_type = quote!(::partial_ref::Const<#part, #const_type>);
+        mut_type = quote!(::partial_ref::Mut<#part, #mut_type>);
+
+        split_const_type = quote!(
+            ::partial_ref::Const<
+                ::partial_ref::Nested<ContainingPart, #part>,
+                #split_const_type
+            >
+        );
+        split_mut_type = quote!(
+            ::partial_ref::Mut<
+                ::partial_ref::Nested<ContainingPart, #part>,
+                #split_mut_type
+            >
+        );
+    }
+
+    // Pinned parts are only contained in partial references created from pinned references. As
+    // these cannot be nested, the parts are also not contained in the split types.
+    let mut pin_const_type = const_type.clone();
+    let mut pin_mut_type = mut_type.clone();
+
+    for (_, _, part) in pinned_parts.iter() {
+        pin_const_type = quote!(::partial_ref::Const<#part, #pin_const_type>);
+        pin_mut_type = quote!(::partial_ref::Mut<#part, #pin_mut_type>);
+    }
+
+    if let Some(parts_mod) = &options.parts_mod {
+        declarations = vec![quote! {
+            #target_vis mod #parts_mod {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #(#declarations)*
+            }
+        }];
+    }
+
+    // Pointers to a custom raw target are converted to pointers to the target before projecting
+    // them to a part.
+    let raw_type = options
+        .raw
+        .as_ref()
+        .map_or(quote!(Self), |raw| quote!(#raw));
+    let (project, project_mut) = if options.raw.is_some() {
+        (
+            quote! {
+                let ptr = <Self::RawTarget as ::partial_ref::RawTargetOf<Self>>::target_ptr(ptr);
+            },
+            quote! {
+                let ptr = <Self::RawTarget as ::partial_ref::RawTargetOf<Self>>::target_ptr_mut(ptr);
+            },
+        )
+    } else {
+        (quote!(), quote!())
+    };
+    let from_target = |ptr: proc_macro2::TokenStream| {
+        if options.raw.is_some() {
+            quote! {
+                <
+                    <#target_type as ::partial_ref::PartialRefTarget>::RawTarget
+                    as ::partial_ref::RawTargetOf<#target_type>
+                >::from_target_ptr(#ptr)
+            }
+        } else {
+            ptr
+        }
+    };
+    let const_raw_ptr = from_target(quote!(self as *const _ as *mut _));
+    let mut_raw_ptr = from_target(quote!(self as *mut _));
+    let pin_const_raw_ptr =
+        from_target(quote!(::core::pin::Pin::get_ref(self) as *const _ as *mut _));
+    let pin_mut_raw_ptr = from_target(quote!(::core::pin::Pin::get_unchecked_mut(self) as *mut _));
+
+    let mut result: Vec<TokenStream> = declarations
+        .into_iter()
+        .chain(group_impls)
+        .map(TokenStream::from)
+        .collect();
+
+    // The `HasPart` impl of a field only gets the bounds given for that field, so that generic
+    // code using other parts doesn't require them.
+    let field_where_bounds = |member: &Member| {
+        let predicates: Vec<_> = options
+            .bounds
+            .iter()
+            .chain(
+                field_bounds
+                    .iter()
+                    .filter(|(bounded, _)| bounded == member)
+                    .flat_map(|(_, predicates)| predicates),
+            )
+            .cloned()
+            .collect();
+        where_bounds_clause(&predicates)
+    };
+
+    result.push(TokenStream::from(quote! {
+        #doc_hidden
+        impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a> for &#lt_a #target_type
+        #all_where_bounds
+        {
+            type Ref = #const_type;
+            #[inline(always)]
+            fn into_partial_ref(self) -> Self::Ref {
+                unsafe {
+                    <Self::Ref as ::partial_ref::PartialRef>::from_raw(#const_raw_ptr)
+                }
+            }
+        }
+
+        #doc_hidden
+        impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
+        for &#lt_a mut #target_type
+        #all_where_bounds
+        {
+            type Ref = #mut_type;
+            #[inline(always)]
+            fn into_partial_ref(self) -> Self::Ref {
+                unsafe {
+                    <Self::Ref as ::partial_ref::PartialRef>::from_raw(#mut_raw_ptr)
+                }
+            }
+        }
+
+        #doc_hidden
+        impl #impl_generics ::partial_ref::PartialRefTarget for #target_type #where_bounds {
+            type RawTarget = #raw_type;
+        }
+    }));
+
+    // Recovering a mutable reference from a partial reference created from a pinned reference
+    // would allow moving the pinned fields.
+    if pinned_parts.is_empty() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            unsafe impl<#lt_a #extra_generics> ::partial_ref::FullRefTarget<#lt_a> for #target_type
+            where
+                #(#bounds,)*
+                #target_type: #lt_a,
+            {
+                type FullRef = #mut_type;
+
+                #[inline(always)]
+                fn target_ptr_mut(ptr: *mut Self::RawTarget) -> *mut Self {
+                    #project_mut
+                    ptr
+                }
+            }
+        }));
+    }
+
+    result.extend(from_parts_impl.map(TokenStream::from));
+
+    if !pinned_parts.is_empty() {
+        let params = &generics.params;
+        let target_args = generics_to_extra_generics(&target_generics);
+        let pinned_types = pinned_parts.iter().map(|(_, field_type, _)| field_type);
+
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
+            for ::core::pin::Pin<&#lt_a #target_type>
+            #all_where_bounds
+            {
+                type Ref = #pin_const_type;
+                #[inline(always)]
+                fn into_partial_ref(self) -> Self::Ref {
+                    unsafe {
+                        <Self::Ref as ::partial_ref::PartialRef>::from_raw(#pin_const_raw_ptr)
+                    }
+                }
+            }
+
+            #doc_hidden
+            impl<#lt_a #extra_generics> ::partial_ref::IntoPartialRef<#lt_a>
+            for ::core::pin::Pin<&#lt_a mut #target_type>
+            #all_where_bounds
+            {
+                type Ref = #pin_mut_type;
+                #[inline(always)]
+                fn into_partial_ref(self) -> Self::Ref {
+                    unsafe {
+                        <Self::Ref as ::partial_ref::PartialRef>::from_raw(#pin_mut_raw_ptr)
+                    }
+                }
+            }
+
+            // Pinned fields must not be moved out by a Drop impl and the target may only be Unpin
+            // when all pinned fields are.
+            const _: () = {
+                trait MustNotImplDrop {}
+                #[allow(drop_bounds)]
+                impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+                impl #impl_generics MustNotImplDrop for #target_type {}
+
+                #[allow(dead_code)]
+                pub struct PinnedFields<#lt_a, #params>(
+                    ::core::marker::PhantomData<(&#lt_a (), fn() -> #target_type)>,
+                    #(#pinned_types,)*
+                );
+
+                impl<#lt_a #extra_generics> ::core::marker::Unpin for #target_type
+                where
+                    PinnedFields<#lt_a #target_args>: ::core::marker::Unpin,
+                {
+                }
+            };
+        }));
+    }
+
+    if let Some(parts_trait) = &options.parts_trait {
+        let parts = all_parts
+            .iter()
+            .copied()
+            .chain(pinned_parts.iter().map(|(_, _, part)| part))
+            .collect::<Vec<_>>();
+
+        result.push(TokenStream::from(quote! {
+            #target_vis trait #parts_trait #impl_generics:
+                ::partial_ref::PartialRefTarget #(+ ::partial_ref::HasPart<#parts>)*
+            {
+            }
+
+            impl<Target #extra_generics> #parts_trait #target_generics for Target
+            where
+                Target: ?Sized + ::partial_ref::PartialRefTarget,
+                #(Target: ::partial_ref::HasPart<#parts>,)*
+            {
+            }
+        }));
+    }
+
+    if options.split_helpers {
+        let split_trait = format_ident!("{}Split", target_ident);
+        let target_args = generics_to_extra_generics(&target_generics);
+
+        let methods = typed_parts.iter().map(|(member, field_type, part)| {
+            let name = member_name(member);
+            let split = format_ident!("split_{}", name);
+            let split_mut = format_ident!("split_{}_mut", name);
+            quote! {
+                #[inline(always)]
+                fn #split<PartIndex>(
+                    &#lt_a mut self,
+                ) -> (
+                    &#lt_a #field_type,
+                    <Self as ::partial_ref::PluckConst<#lt_a, #part, PartIndex>>::Remainder,
+                )
+                where
+                    Self: ::partial_ref::PluckConst<#lt_a, #part, PartIndex>,
+                    #target_type: #lt_a,
+                {
+                    ::partial_ref::PartialRef::split_part(self, <#part as ::core::default::Default>::default())
+                }
+
+                #[inline(always)]
+                fn #split_mut<PartIndex>(
+                    &#lt_a mut self,
+                ) -> (
+                    &#lt_a mut #field_type,
+                    <Self as ::partial_ref::PluckMut<#lt_a, #part, PartIndex>>::Remainder,
+                )
+                where
+                    Self: ::partial_ref::PluckMut<#lt_a, #part, PartIndex>,
+                    #target_type: #lt_a,
+                {
+                    ::partial_ref::PartialRef::split_part_mut(self, <#part as ::core::default::Default>::default())
+                }
+            }
+        });
+
+        result.push(TokenStream::from(quote! {
+            #target_vis trait #split_trait<#lt_a #extra_generics>:
+                ::partial_ref::PartialRef<#lt_a, Target = #target_type>
+            #where_bounds
+            {
+                #(#methods)*
+            }
+
+            impl<#lt_a #extra_generics, Reference> #split_trait<#lt_a #target_args> for Reference
+            where
+                Reference: ::partial_ref::PartialRef<#lt_a, Target = #target_type>,
+                #(#bounds,)*
+            {
+            }
+        }));
+    }
+
+    let part_infos: Vec<(&Type, proc_macro2::TokenStream, Option<String>)> = abstract_parts
+        .iter()
+        .map(|part| (part, quote!(Abstract), None))
+        .chain(
+            variant_parts
+                .iter()
+                .map(|(_, part)| (part, quote!(Abstract), None)),
+        )
+        .chain(
+            typed_parts
+                .iter()
+                .map(|(member, _, part)| (part, quote!(Field), Some(member_name(member)))),
+        )
+        .chain(group_parts.iter().map(|part| (part, quote!(Group), None)))
+        .chain(flattened_parts.iter().flat_map(|(member, _, parts)| {
+            parts
+                .iter()
+                .map(move |part| (part, quote!(Flattened), Some(member_name(member))))
+        }))
+        .chain(
+            deref_parts
+                .iter()
+                .map(|(member, _, part)| (part, quote!(Field), Some(member_name(member)))),
+        )
+        .chain(element_parts.iter().flat_map(|(member, _, parts)| {
+            parts
+                .iter()
+                .map(move |part| (part, quote!(Field), Some(member_name(member))))
+        }))
+        .chain(
+            union_parts
+                .iter()
+                .map(|(member, _, part)| (part, quote!(Field), Some(member_name(member)))),
+        )
+        .chain(
+            variant_field_parts
+                .iter()
+                .map(|(_, member, _, part)| (part, quote!(Field), Some(member_name(member)))),
+        )
+        .chain(
+            pinned_parts
+                .iter()
+                .map(|(member, _, part)| (part, quote!(Field), Some(member_name(member)))),
+        )
+        .collect();
+
+    let part_names = part_infos
+        .iter()
+        .map(|(part, _, _)| part_name(part))
+        .collect::<Vec<_>>();
+    let part_kinds = part_infos.iter().map(|(_, kind, _)| kind);
+    let part_fields = part_infos.iter().map(|(_, _, field)| match field {
+        Some(field) => quote!(::core::option::Option::Some(#field)),
+        None => quote!(::core::option::Option::None),
+    });
+
+    result.push(TokenStream::from(quote! {
+        #doc_hidden
+        impl #impl_generics ::partial_ref::PartMetadata for #target_type #where_bounds {
+            const PARTS: &'static [::partial_ref::PartInfo] = &[
+                #(
+                    ::partial_ref::PartInfo {
+                        name: #part_names,
+                        kind: ::partial_ref::PartKind::#part_kinds,
+                        field: #part_fields,
+                    },
+                )*
+            ];
+            const PART_NAMES: &'static [&'static str] = &[#(#part_names),*];
+        }
+    }));
+
+    // The position of a part is its index in `PartMetadata::PARTS`. Parts listed more than once,
+    // like shared abstract parts, get the position of their first occurrence.
+    let mut canonical_names = HashSet::new();
+    let mut canonical_order = quote!(());
+    let mut next_position = quote!(::partial_ref::IndexHere);
+    for ((part, _, _), name) in part_infos.iter().zip(part_names.iter()) {
+        let position = next_position;
+        next_position = quote!(::partial_ref::IndexNext<#position>);
+        if !canonical_names.insert(name) {
+            continue;
+        }
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            unsafe impl #impl_generics ::partial_ref::CanonicalPart<#target_type> for #part
+            #where_bounds
+            {
+                type Position = #position;
+            }
+        }));
+        canonical_order = quote!((#part, #canonical_order));
+    }
+
+    result.push(TokenStream::from(quote! {
+        #doc_hidden
+        unsafe impl #impl_generics ::partial_ref::CanonicalTarget for #target_type #where_bounds {
+            type CanonicalOrder = #canonical_order;
+        }
+    }));
+
+    // The fields of packed structs cannot be serialized in place.
+    if cfg!(feature = "serde") && !packed {
+        let serde_parts =
+            typed_parts
+                .iter()
+                .chain(deref_parts.iter())
+                .map(|(member, _, part)| (part, field_where_bounds(member)))
+                .chain(element_parts.iter().flat_map(|(_, _, parts)| {
+                    parts.iter().map(|part| (part, where_bounds.clone()))
+                }));
+
+        for (part, where_bounds) in serde_parts {
+            let name = part_name(part);
+            result.push(TokenStream::from(quote! {
+                #doc_hidden
+                impl #impl_generics ::partial_ref::SerdePart<#part> for #target_type #where_bounds {
+                    const NAME: &'static str = #name;
+                }
+            }));
+        }
+    }
+
+    if visit_parts {
+        let mut visited_types: Vec<proc_macro2::TokenStream> = vec![];
+        let mut visits = vec![];
+
+        for (member, field_type, part) in typed_parts.iter().chain(pinned_parts.iter()) {
+            let name = part_name(part);
+            visited_types.push(quote!(#field_type));
+            visits.push(quote!(visitor.visit(#name, &self.#member);));
+        }
+        for (member, field_type, part) in deref_parts.iter() {
+            let name = part_name(part);
+            visited_types.push(quote!(<#field_type as ::core::ops::Deref>::Target));
+            visits.push(quote!(visitor.visit(#name, &*self.#member);));
+        }
+        for (member, field_type, parts) in element_parts.iter() {
+            let element_type = match field_type {
+                Type::Array(array) => &*array.elem,
+                _ => unreachable!(),
+            };
+            visited_types.push(quote!(#element_type));
+            for (index, part) in parts.iter().enumerate() {
+                let name = part_name(part);
+                visits.push(quote!(visitor.visit(#name, &self.#member[#index]);));
+            }
+        }
+
+        let mut arms = vec![];
+        for (variant, _) in variant_parts.iter() {
+            let fields = variant_field_parts
+                .iter()
+                .filter(|(field_variant, _, _, _)| field_variant == variant)
+                .collect::<Vec<_>>();
+            if fields.is_empty() {
+                continue;
+            }
+            let members = fields.iter().map(|(_, member, _, _)| member);
+            let bindings = (0..fields.len())
+                .map(|index| format_ident!("field_{}", index))
+                .collect::<Vec<_>>();
+            let names = fields.iter().map(|(_, _, _, part)| part_name(part));
+            visited_types.extend(
+                fields
+                    .iter()
+                    .map(|(_, _, field_type, _)| quote!(#field_type)),
+            );
+            arms.push(quote! {
+                Self::#variant { #(#members: #bindings,)* .. } => {
+                    #(visitor.visit(#names, #bindings);)*
+                }
+            });
+        }
+        if !arms.is_empty() {
+            visits.push(quote! {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#arms)*
+                    _ => (),
+                }
+            });
+        }
+
+        let mut visit_generics = generics.clone();
+        visit_generics.params.push(parse_quote!(Visitor: ?Sized));
+        let (visit_impl_generics, _, _) = visit_generics.split_for_impl();
+
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #visit_impl_generics ::partial_ref::VisitParts<Visitor> for #target_type
+            where
+                #(Visitor: ::partial_ref::PartVisitor<#visited_types>,)*
+                #(#bounds,)*
+            {
+                #[allow(unused_variables)]
+                fn visit_parts(&self, visitor: &mut Visitor) {
+                    #(#visits)*
+                }
+            }
+        }));
+    }
+
+    // Nested parts require the containing field to be its own raw target.
+    if options.raw.is_none() && !options.no_nested {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            unsafe impl<#lt_a #extra_generics, ContainingPart, Reference>
+                ::partial_ref::SplitIntoParts<#lt_a, ContainingPart, Reference> for #target_type
+            where
+                ContainingPart: ::partial_ref::Part<PartType=::partial_ref::Field<Self>>,
+                Reference: ::partial_ref::PartialRef<#lt_a>,
+                Reference::Target: ::partial_ref::HasPart<ContainingPart>,
+                #(#bounds,)*
+            {
+                type Result = #split_const_type;
+                type ResultMut = #split_mut_type;
+            }
+        }));
+    }
+
+    for part in abstract_parts
+        .iter()
+        .chain(variant_parts.iter().map(|(_, part)| part))
+    {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(ptr: *const Self::RawTarget) -> () {
+                    unreachable!()
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> () {
+                    unreachable!()
+                }
+            }
+        }));
+    }
+
+    for part in virtual_parts.iter() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr  {
+                    #project
+                    <#part as ::partial_ref::VirtualPart<Self>>::virtual_ptr(ptr)
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                    #project_mut
+                    <#part as ::partial_ref::VirtualPart<Self>>::virtual_ptr_mut(ptr)
+                }
+            }
+        }));
+    }
+
+    for part in thread_local_parts.iter() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    _ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr {
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    _ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                }
+            }
+        }));
+    }
+
+    for part in group_parts.iter() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(ptr: *const Self::RawTarget) -> *const Self {
+                    #project
+                    ptr
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(ptr: *mut Self::RawTarget) -> *mut Self {
+                    #project_mut
+                    ptr
+                }
+            }
+        }));
+    }
+
+    for (variant, part) in variant_parts.iter() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            unsafe impl #impl_generics ::partial_ref::HasVariant<#part> for #target_type
+            #where_bounds
+            {
+                #[inline(always)]
+                unsafe fn is_variant(ptr: *const Self::RawTarget) -> bool {
+                    #project
+                    #[allow(unreachable_patterns)]
+                    match *ptr {
+                        Self::#variant { .. } => true,
+                        _ => false,
+                    }
+                }
+            }
+        }));
+    }
+
+    for (member, field_type, part) in typed_parts.iter().chain(pinned_parts.iter()) {
+        let check = if packed {
+            unaligned_field_type_check(field_type, part)
+        } else {
+            field_type_check(field_type, part)
+        };
+        let where_bounds = field_where_bounds(member);
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr  {
+                    #check
+                    #project
+                    ::core::ptr::addr_of!((*ptr).#member)
+                        as <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                    #project_mut
+                    ::core::ptr::addr_of_mut!((*ptr).#member)
+                        as <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut
+                }
+            }
+        }));
+    }
+
+    for (member, field_type, parts) in flattened_parts.iter() {
+        for part in parts.iter() {
+            result.push(TokenStream::from(quote! {
+                #doc_hidden
+                impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                    #[inline(always)]
+                    unsafe fn part_ptr(
+                        ptr: *const Self::RawTarget
+                    ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr {
+                        #project
+                        <#field_type as ::partial_ref::HasPart<#part>>::part_ptr(
+                            ::core::ptr::addr_of!((*ptr).#member)
+                        )
+                    }
+
+                    #[inline(always)]
+                    unsafe fn part_ptr_mut(
+                        ptr: *mut Self::RawTarget
+                    ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                        #project_mut
+                        <#field_type as ::partial_ref::HasPart<#part>>::part_ptr_mut(
+                            ::core::ptr::addr_of_mut!((*ptr).#member)
+                        )
+                    }
+                }
+            }));
+        }
+    }
+
+    for (member, _, parts) in element_parts.iter() {
+        for (index, part) in parts.iter().enumerate() {
+            let index = syn::Index::from(index);
+            result.push(TokenStream::from(quote! {
+                #doc_hidden
+                impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                    #[inline(always)]
+                    unsafe fn part_ptr(
+                        ptr: *const Self::RawTarget
+                    ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr {
+                        #project
+                        ::core::ptr::addr_of!((*ptr).#member[#index])
+                    }
+
+                    #[inline(always)]
+                    unsafe fn part_ptr_mut(
+                        ptr: *mut Self::RawTarget
+                    ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                        #project_mut
+                        ::core::ptr::addr_of_mut!((*ptr).#member[#index])
+                    }
+                }
+            }));
+        }
+    }
+
+    for (member, field_type, part) in deref_parts.iter() {
+        let where_bounds = field_where_bounds(member);
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr {
+                    #project
+                    <
+                        <#part as ::partial_ref::Part>::PartType
+                        as ::partial_ref::DerefPartType<#field_type>
+                    >::deref_ptr(::core::ptr::addr_of!((*ptr).#member))
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                    #project_mut
+                    <
+                        <#part as ::partial_ref::Part>::PartType
+                        as ::partial_ref::DerefPartType<#field_type>
+                    >::deref_ptr_mut(::core::ptr::addr_of_mut!((*ptr).#member))
+                }
+            }
+        }));
+    }
+
+    // Union fields overlap, so their parts must not provide safe access.
+    for (member, field_type, part) in union_parts.iter() {
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type
+            where
+                #part: ::partial_ref::Part<PartType = ::partial_ref::UnionField<#field_type>>,
+                #(#bounds,)*
+            {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr {
+                    #project
+                    ::core::ptr::addr_of!((*ptr).#member)
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                    #project_mut
+                    ::core::ptr::addr_of_mut!((*ptr).#member)
+                }
+            }
+        }));
+    }
+
+    for (variant, member, field_type, part) in variant_field_parts.iter() {
+        // The projections aren't casts, so a mismatching field type of the part is reported at the
+        // type of the field. Fields of an inactive variant have no address, which is represented
+        // by a null pointer. A `Witness` checks the variant before dereferencing the pointer.
+        let field_ptr = quote_spanned!(field_type.span()=> field as *const #field_type);
+        let field_ptr_mut = quote_spanned!(field_type.span()=> field as *mut #field_type);
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            impl #impl_generics ::partial_ref::HasPart<#part> for #target_type #where_bounds {
+                #[inline(always)]
+                unsafe fn part_ptr(
+                    ptr: *const Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::Ptr  {
+                    #project
+                    #[allow(unreachable_patterns)]
+                    match *ptr {
+                        Self::#variant { #member: ref field, .. } => #field_ptr,
+                        _ => ::core::ptr::null(),
+                    }
+                }
+
+                #[inline(always)]
+                unsafe fn part_ptr_mut(
+                    ptr: *mut Self::RawTarget
+                ) -> <<#part as ::partial_ref::Part>::PartType as ::partial_ref::PartType>::PtrMut {
+                    #project_mut
+                    #[allow(unreachable_patterns)]
+                    match *ptr {
+                        Self::#variant { #member: ref mut field, .. } => #field_ptr_mut,
+                        _ => ::core::ptr::null_mut(),
+                    }
+                }
+            }
+        }));
+    }
+
+    // Fields of structs and unions have a fixed offset, as opposed to fields of enum variants and
+    // the values pointed to by dereferenced fields. The offset of a dynamically sized field can
+    // depend on the pointer metadata, so such a field has no offset.
+    for (member, _, part) in typed_parts
+        .iter()
+        .chain(pinned_parts.iter())
+        .chain(union_parts.iter())
+        .filter(|(member, _, _)| Some(member) != unsized_member.as_ref())
+    {
+        let where_bounds = field_where_bounds(member);
+        result.push(TokenStream::from(quote! {
+            #doc_hidden
+            unsafe impl #impl_generics ::partial_ref::PartOffset<#part> for #target_type
+            #where_bounds
+            {
+                const OFFSET: usize = ::core::mem::offset_of!(Self, #member);
+            }
+        }));
+    }
+
+    for (member, field_type, parts) in element_parts.iter() {
+        let len = parts.len();
+        for (index, part) in parts.iter().enumerate() {
+            // The size of an array is the size of its elements times its length.
+            result.push(TokenStream::from(quote! {
+                #doc_hidden
+                unsafe impl #impl_generics ::partial_ref::PartOffset<#part> for #target_type
+                #where_bounds
+                {
+                    const OFFSET: usize = ::core::mem::offset_of!(Self, #member)
+                        + #index * (::core::mem::size_of::<#field_type>() / #len);
+                }
+            }));
+        }
+    }
+
+    result.into_iter().collect()
+}
+
+/// Attribute macro form of `#[derive(PartialRefTarget)]` that rewrites the item.
+///
+/// `#[partial_ref_target]` on a struct, enum or union generates the same parts and impls as
+/// deriving PartialRefTarget and supports the same `#[part(...)]` and `#[part_group(...)]`
+/// attributes. Options can be passed as arguments, so `#[partial_ref_target(parts_mod = "parts")]`
+/// is the same as deriving with a `#[partial_ref(parts_mod = "parts")]` attribute.
+///
+/// Unlike the derive, the attribute removes these helper attributes from the item, so they are not
+/// seen by other attribute macros or derives of the item, and the item doesn't depend on helper
+/// attributes being registered. As attribute macros see fields and variants before conditional
+/// compilation is applied, fields and variants with `#[cfg(...)]` or `#[cfg_attr(...)]` attributes
+/// are rejected, use the derive for such targets.
+///
+/// ```ignore
+/// use partial_ref::partial_ref_target;
+///
+/// #[partial_ref_target(doc_hidden)]
+/// struct ExampleAttribute {
+///     #[part(SomeFieldPart)]
+///     a: usize,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn partial_ref_target(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let mut item = parse_macro_input!(item as DeriveInput);
+
+    if let Err(err) = check_no_cfg_members(&item.data) {
+        return err.to_compile_error().into();
+    }
+
+    let mut target = item.clone();
+    if !args.is_empty() {
+        target.attrs.push(parse_quote!(#[partial_ref(#args)]));
+    }
+
+    strip_helper_attributes(&mut item);
+
+    let mut result = TokenStream::from(item.into_token_stream());
+    result.extend(expand_partial_ref_target(target));
+    result
+}
+
+/// Check that no field or variant is conditionally compiled.
+///
+/// An attribute macro runs before `#[cfg(...)]` attributes of fields and variants are evaluated, so
+/// parts would be generated for fields that don't exist.
+fn check_no_cfg_members(data: &Data) -> syn::Result<()> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        Data::Union(data_union) => data_union.fields.named.iter().collect(),
+        Data::Enum(data_enum) => {
+            for variant in data_enum.variants.iter() {
+                check_no_cfg(&variant.attrs)?;
+            }
+            data_enum
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter())
+                .collect()
+        }
+    };
+    for field in fields {
+        check_no_cfg(&field.attrs)?;
+    }
+    Ok(())
+}
+
+fn check_no_cfg(attrs: &[Attribute]) -> syn::Result<()> {
+    for attr in attrs.iter() {
+        if attr.path.is_ident("cfg") || attr.path.is_ident("cfg_attr") {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[partial_ref_target] does not support conditionally compiled fields or \
+                 variants, derive PartialRefTarget instead",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Remove the `#[part(...)]`, `#[part_group(...)]` and `#[partial_ref(...)]` attributes from the
+/// item, its variants and its fields.
+fn strip_helper_attributes(item: &mut DeriveInput) {
+    fn strip(attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| {
+            !(attr.path.is_ident("part")
+                || attr.path.is_ident("part_group")
+                || attr.path.is_ident("partial_ref"))
+        });
+    }
+
+    strip(&mut item.attrs);
+    match &mut item.data {
+        Data::Struct(data_struct) => data_struct
+            .fields
+            .iter_mut()
+            .for_each(|f| strip(&mut f.attrs)),
+        Data::Union(data_union) => data_union
+            .fields
+            .named
+            .iter_mut()
+            .for_each(|f| strip(&mut f.attrs)),
+        Data::Enum(data_enum) => {
+            for variant in data_enum.variants.iter_mut() {
+                strip(&mut variant.attrs);
+                variant.fields.iter_mut().for_each(|f| strip(&mut f.attrs));
+            }
+        }
+    }
 }
@@ -0,0 +1,67 @@
+mod isolated {
+    // The attribute macro must not require importing the derive or registering its helper
+    // attributes, as it removes them from the item.
+    use partial_ref::partial_ref_target;
+
+    #[partial_ref_target]
+    #[derive(Default)]
+    #[part(auto)]
+    pub struct Counters {
+        pub hits: usize,
+        pub misses: usize,
+    }
+
+    #[partial_ref_target(parts_mod = "queue_parts", doc_hidden)]
+    #[derive(Default)]
+    #[part_group(Ends: head, tail)]
+    pub struct Queue {
+        #[part(auto)]
+        pub items: Vec<u32>,
+        pub head: usize,
+        pub tail: usize,
+    }
+
+    partial_ref::part!(pub Running);
+    partial_ref::part!(pub Pid in Running: u32);
+
+    #[partial_ref_target]
+    #[repr(u8)]
+    pub enum Process {
+        #[part(Running)]
+        Running {
+            #[part(Pid)]
+            pid: u32,
+        },
+        Exited,
+    }
+}
+
+use self::isolated::queue_parts::*;
+use self::isolated::*;
+use partial_ref::*;
+
+#[test]
+fn test_attribute_macro() {
+    let mut counters = Counters::default();
+    let mut counters_ref = counters.into_partial_ref_mut();
+    *counters_ref.part_mut(Hits) += 1;
+    assert_eq!((counters.hits, counters.misses), (1, 0));
+
+    let mut queue = Queue::default();
+    let mut queue_ref = queue.into_partial_ref_mut();
+    let (items, mut queue_ref) = queue_ref.split_part_mut(Items);
+    items.extend([1, 2]);
+    *queue_ref.group_mut(Ends).tail = items.len();
+    assert_eq!((queue.head, queue.tail), (0, 2));
+}
+
+#[test]
+fn test_attribute_macro_enum() {
+    let process = Process::Running { pid: 1 };
+    let process_ref = process.into_partial_ref();
+    let running = process_ref.witness(Running).ok().unwrap();
+    assert_eq!(*running.part(Pid), 1);
+
+    let process = Process::Exited;
+    assert!(process.into_partial_ref().witness(Running).is_err());
+}
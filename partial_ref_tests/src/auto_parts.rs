@@ -0,0 +1,212 @@
+mod isolated {
+    // Automatic parts must not require importing the `part` macro
+    use partial_ref::PartialRefTarget;
+
+    #[derive(PartialRefTarget, Default)]
+    pub struct Solver {
+        #[part(auto)]
+        pub clause_db: Vec<usize>,
+        #[part(auto)]
+        pub assignment: Vec<bool>,
+        pub unused: u32,
+    }
+
+    #[derive(PartialRefTarget, Default)]
+    #[part(auto)]
+    pub struct Everything {
+        pub first: u32,
+        pub second_field: u32,
+    }
+
+    #[derive(PartialRefTarget, Default)]
+    #[part(auto)]
+    pub struct Skipping {
+        pub kept: u32,
+        #[part(skip)]
+        pub scratch: Vec<u32>,
+        #[part(skip)]
+        pub counter: usize,
+    }
+
+    #[derive(PartialRefTarget)]
+    #[part(auto)]
+    pub struct Borrowing<'a, 'b> {
+        pub input: &'a [u32],
+        pub output: &'b mut Vec<u32>,
+        pub count: usize,
+    }
+
+    #[derive(PartialRefTarget)]
+    #[part(auto)]
+    pub struct Joining<'a, 'b, 'c, 'd> {
+        pub words: (&'a str, &'b str, &'c str, &'d str),
+        pub joined: String,
+        #[part(elements(Piece))]
+        pub pieces: [(&'a str, &'d str); 2],
+    }
+
+    #[derive(PartialRefTarget)]
+    #[repr(C)]
+    #[part(auto)]
+    pub enum Phase {
+        Search { depth: usize },
+        Done,
+    }
+
+    #[derive(PartialRefTarget, Default)]
+    #[partial_ref(prefix = "Gfx")]
+    #[part(auto)]
+    pub struct Graphics {
+        pub colors: Vec<u32>,
+        pub weights: Vec<f32>,
+    }
+
+    #[derive(PartialRefTarget, Default)]
+    #[partial_ref(prefix = "Net")]
+    #[part(auto)]
+    pub struct Network {
+        pub weights: Vec<f32>,
+    }
+
+    #[derive(PartialRefTarget)]
+    #[repr(C)]
+    #[partial_ref(prefix = "Net")]
+    #[part(auto)]
+    pub enum Layer {
+        Dense { width: usize },
+    }
+}
+
+use self::isolated::*;
+use partial_ref::*;
+
+fn assign(mut solver: partial!(Solver, ClauseDb, mut Assignment)) {
+    let len = solver.part(ClauseDb).len();
+    solver.part_mut(Assignment).resize(len, true);
+}
+
+#[test]
+fn test_field_auto_parts() {
+    let mut solver = Solver::default();
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    solver_ref.part_mut(ClauseDb).extend(&[1, 2]);
+    assign(solver_ref.borrow());
+
+    assert_eq!(solver.assignment, vec![true, true]);
+}
+
+#[test]
+fn test_struct_auto_parts() {
+    let mut everything = Everything::default();
+    let mut everything_ref = everything.into_partial_ref_mut();
+
+    let (first, mut everything_ref) = everything_ref.split_part_mut(First);
+    *first = 1;
+    *everything_ref.part_mut(SecondField) = 2;
+
+    assert_eq!(everything.first, 1);
+    assert_eq!(everything.second_field, 2);
+}
+
+fn use_rest(mut skipping: partial!(Skipping, mut SkippingRest)) {
+    let rest: SkippingRestMut = skipping.group_mut(SkippingRest);
+    rest.scratch.push(1);
+    *rest.counter += 1;
+}
+
+#[test]
+fn test_skipped_fields() {
+    let mut skipping = Skipping::default();
+    let mut skipping_ref = skipping.into_partial_ref_mut();
+
+    use_rest(skipping_ref.borrow());
+    let (kept, mut skipping_ref) = skipping_ref.split_part_mut(Kept);
+    *kept = 2;
+    use_rest(skipping_ref.borrow());
+
+    assert_eq!(skipping.kept, 2);
+    assert_eq!(skipping.scratch, vec![1, 1]);
+    assert_eq!(skipping.counter, 2);
+}
+
+#[test]
+fn test_auto_parts_with_lifetimes() {
+    let input = [1, 2, 3];
+    let mut output = vec![];
+    let mut borrowing = Borrowing {
+        input: &input,
+        output: &mut output,
+        count: 0,
+    };
+    let mut borrowing_ref = borrowing.into_partial_ref_mut();
+
+    let (out, mut borrowing_ref) = borrowing_ref.split_part_mut(Output);
+    out.extend(borrowing_ref.part(Input).iter());
+    *borrowing_ref.part_mut(Count) += out.len();
+
+    assert_eq!(borrowing.count, 3);
+    assert_eq!(output, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_auto_parts_with_many_lifetimes() {
+    let (a, b, c, d) = (
+        "a".to_owned(),
+        "b".to_owned(),
+        "c".to_owned(),
+        "d".to_owned(),
+    );
+    let mut joining = Joining {
+        words: (&a, &b, &c, &d),
+        joined: String::new(),
+        pieces: [("", ""); 2],
+    };
+    let mut joining_ref = joining.into_partial_ref_mut();
+
+    let (words, mut joining_ref) = joining_ref.split_part(Words);
+    let joined = joining_ref.part_mut(Joined);
+    for word in [words.0, words.1, words.2, words.3] {
+        joined.push_str(word);
+    }
+    *joining_ref.part_mut(Piece::<1>::default()) = (words.0, words.3);
+
+    assert_eq!(joining.joined, "abcd");
+    assert_eq!(joining.pieces[1], ("a", "d"));
+}
+
+#[test]
+fn test_enum_auto_parts() {
+    let mut phase = Phase::Search { depth: 1 };
+    let mut phase_ref = phase.into_partial_ref_mut();
+
+    assert!(phase_ref.reborrow().witness(Done).is_err());
+    let mut search = phase_ref.witness(Search).ok().unwrap();
+    *search.part_mut(Depth) += 1;
+
+    match phase {
+        Phase::Search { depth } => assert_eq!(depth, 2),
+        Phase::Done => unreachable!(),
+    }
+}
+
+#[test]
+fn test_prefixed_auto_parts() {
+    let mut graphics = Graphics::default();
+    let mut network = Network::default();
+    let mut graphics_ref = graphics.into_partial_ref_mut();
+    let mut network_ref = network.into_partial_ref_mut();
+
+    graphics_ref.part_mut(GfxColors).push(3);
+    graphics_ref.part_mut(GfxWeights).push(0.5);
+    network_ref.part_mut(NetWeights).push(1.5);
+
+    assert_eq!(graphics.colors, vec![3]);
+    assert_eq!(graphics.weights, vec![0.5]);
+    assert_eq!(network.weights, vec![1.5]);
+
+    let mut layer = Layer::Dense { width: 4 };
+    let layer_ref = layer.into_partial_ref_mut();
+    let dense = layer_ref.witness(NetDense).ok().unwrap();
+    assert_eq!(*dense.part(NetWidth), 4);
+}
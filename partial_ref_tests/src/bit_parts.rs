@@ -0,0 +1,68 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Uart {
+    #[part(auto)]
+    pub control: u32,
+    #[part(auto)]
+    pub status: u8,
+    #[part(auto)]
+    pub sent: usize,
+}
+
+type Enable = BitPart<Control, 0, 1>;
+type Parity = BitPart<Control, 1, 3>;
+type BaudDivisor = BitPart<Control, 16, 32>;
+
+type ControlRef<'a> = partial!('a Uart, mut Enable, mut Parity, mut BaudDivisor);
+
+fn configure(mut control: partial!(Uart, mut Parity, mut BaudDivisor), divisor: u32) {
+    control.bit_part_mut(Parity::default()).set(0b10);
+    control.bit_part_mut(BaudDivisor::default()).set(divisor);
+}
+
+fn enable(mut control: partial!(Uart, mut Enable)) {
+    control.bit_part_mut(Enable::default()).set(1);
+}
+
+#[test]
+fn test_bit_parts() {
+    let mut uart = Uart {
+        control: 0x0000_ff00,
+        ..Uart::default()
+    };
+    let mut uart_ref = uart.into_partial_ref_mut();
+
+    let (mut control, mut rest): (ControlRef, _) = uart_ref.split_bit_parts_mut(Control);
+
+    let (mut enable_bit, mut control) = control.split_bit_part_mut(Enable::default());
+    configure(control.borrow(), 0x1_2345);
+    assert_eq!(control.bit_part(BaudDivisor::default()).get(), 0x2345);
+    enable_bit.update(|bit| *bit ^= 1);
+    assert_eq!(enable_bit.as_bits().get(), 1);
+
+    *rest.part_mut(Sent) += 1;
+
+    assert_eq!(uart.control, 0x2345_ff05);
+    assert_eq!(uart.sent, 1);
+}
+
+#[test]
+fn test_full_width_bit_part() {
+    type Status = BitPart<self::Status, 0, 8>;
+
+    let mut uart = Uart::default();
+    let mut uart_ref = uart.into_partial_ref_mut();
+
+    let (mut status, _): (partial!(Uart, mut Status), _) =
+        uart_ref.split_bit_parts_mut(self::Status);
+    status.bit_part_mut(Status::default()).set(0xa5);
+    let (status, _) = status.split_bit_part(Status::default());
+    assert_eq!(status.get(), 0xa5);
+
+    let mut control = uart.into_partial_ref_mut();
+    let (mut control, _): (partial!(Uart, mut Enable), _) = control.split_bit_parts_mut(Control);
+    enable(control.borrow());
+    assert_eq!(uart.control, 1);
+    assert_eq!(uart.status, 0xa5);
+}
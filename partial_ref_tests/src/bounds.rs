@@ -0,0 +1,78 @@
+use partial_ref::*;
+
+pub trait Source {
+    type Output;
+
+    fn produce(&mut self) -> Self::Output;
+}
+
+pub struct Counter(u32);
+
+impl Source for Counter {
+    type Output = u32;
+
+    fn produce(&mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+part!(pub Latest: u32);
+part!(pub Input<T>: T);
+
+#[derive(PartialRefTarget)]
+#[partial_ref(bound = "T: Source<Output = u32>")]
+pub struct Pipeline<T: Source> {
+    #[part(Latest)]
+    pub latest: T::Output,
+    #[part(Input<T>)]
+    pub input: T,
+}
+
+fn pull<T: Source<Output = u32>>(mut pipeline: partial!(Pipeline<T>, mut Latest, mut Input<T>)) {
+    let (input, mut pipeline) = pipeline.split_part_mut(Input::default());
+    *pipeline.part_mut(Latest) = input.produce();
+}
+
+#[test]
+fn test_bounds() {
+    let mut pipeline = Pipeline {
+        latest: 0,
+        input: Counter(10),
+    };
+    let mut pipeline_ref = pipeline.into_partial_ref_mut();
+
+    pull(pipeline_ref.borrow());
+    pull(pipeline_ref.borrow());
+
+    assert_eq!(*pipeline_ref.part(Latest), 12);
+    assert_eq!(pipeline.input.0, 12);
+}
+
+#[derive(PartialRefTarget)]
+pub struct Buffer<T: Source> {
+    #[part(Latest)]
+    #[part(bound = "T: Source<Output = u32>")]
+    pub latest: T::Output,
+    #[part(Input<T>)]
+    pub input: T,
+}
+
+// Only the part of the bounded field requires the bound.
+fn produce<T: Source>(mut buffer: partial!(Buffer<T>, mut Input<T>)) -> T::Output {
+    buffer.part_mut(Input::default()).produce()
+}
+
+#[test]
+fn test_field_bounds() {
+    let mut buffer = Buffer {
+        latest: 0,
+        input: Counter(3),
+    };
+    let mut buffer_ref = buffer.into_partial_ref_mut();
+
+    let value = produce(buffer_ref.borrow());
+    *buffer_ref.part_mut(Latest) = value;
+
+    assert_eq!(buffer.latest, 4);
+}
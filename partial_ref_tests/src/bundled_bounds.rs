@@ -0,0 +1,56 @@
+use partial_ref::*;
+
+part!(pub Hits: u32);
+part!(pub Misses: u32);
+part!(pub Entries<T>: Vec<T>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Cache {
+    #[part(Hits)]
+    pub hits: u32,
+    #[part(Misses)]
+    pub misses: u32,
+    #[part(Entries<u64>)]
+    pub entries: Vec<u64>,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Lookup {
+    #[part(Entries<u64>)]
+    pub entries: Vec<u64>,
+    #[part(Misses)]
+    pub misses: u32,
+    #[part(Hits)]
+    pub hits: u32,
+}
+
+fn record<Target: HasParts<(Hits, Misses, Entries<u64>)>>(
+    mut target: partial!(Target, mut Hits, mut Misses, Entries<u64>),
+    key: u64,
+) {
+    if target.part(Entries::default()).contains(&key) {
+        *target.part_mut(Hits) += 1;
+    } else {
+        *target.part_mut(Misses) += 1;
+    }
+}
+
+#[test]
+fn test_bundled_bounds() {
+    let mut cache = Cache {
+        entries: vec![1, 2],
+        ..Cache::default()
+    };
+    let mut cache_ref = cache.into_partial_ref_mut();
+    record(cache_ref.borrow(), 1);
+    record(cache_ref.borrow(), 3);
+    assert_eq!((cache.hits, cache.misses), (1, 1));
+
+    let mut lookup = Lookup {
+        entries: vec![3],
+        ..Lookup::default()
+    };
+    let mut lookup_ref = lookup.into_partial_ref_mut();
+    record(lookup_ref.borrow(), 3);
+    assert_eq!((lookup.hits, lookup.misses), (1, 0));
+}
@@ -0,0 +1,46 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Scene {
+    #[part(auto)]
+    pub lights: Vec<f32>,
+    #[part(auto)]
+    pub objects: Vec<&'static str>,
+    #[part(auto)]
+    pub frame: usize,
+}
+
+type ViewRef<'a> = CanonicalRef<'a, partial!('a Scene, Objects, Lights)>;
+
+fn render<'a>(mut scene: CanonicalRef<'a, partial!('a Scene, mut Frame, Lights)>) {
+    let total = scene.part(Lights).iter().sum::<f32>();
+    *scene.part_mut(Frame) += total as usize;
+}
+
+#[test]
+fn test_canonical_refs() {
+    let mut scene = Scene {
+        lights: vec![1.0, 2.0],
+        ..Scene::default()
+    };
+    let mut scene_ref = scene.into_partial_ref_mut();
+
+    let frame_first: partial!(Scene, mut Frame, Lights) = scene_ref.borrow();
+    render(frame_first.canonicalize());
+    let lights_first: partial!(Scene, Lights, mut Frame) = scene_ref.borrow();
+    render(lights_first.canonicalize());
+    assert_eq!(*scene_ref.part(Frame), 6);
+
+    let views: Vec<ViewRef> = vec![
+        scene_ref
+            .borrow_const::<partial!(Scene, Objects, Lights), _>()
+            .canonicalize(),
+        scene_ref
+            .borrow_const::<partial!(Scene, Lights, Objects), _>()
+            .canonicalize(),
+    ];
+    for view in views {
+        let _: partial!(Scene, Lights, Objects) = view;
+        assert_eq!(view.part(Lights).len(), 2);
+    }
+}
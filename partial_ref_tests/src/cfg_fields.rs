@@ -0,0 +1,54 @@
+use partial_ref::*;
+
+// Fields disabled by `#[cfg(...)]` are removed before the derive sees them, so they must not
+// produce any parts or impls referring to them.
+#[derive(PartialRefTarget, Default)]
+#[part(auto)]
+pub struct Tracked {
+    pub value: u32,
+    #[cfg(any())]
+    pub disabled: NotAType,
+    #[cfg(test)]
+    pub enabled: Vec<u32>,
+    #[cfg(any())]
+    #[part(skip)]
+    pub disabled_scratch: NotAType,
+}
+
+#[derive(PartialRefTarget)]
+#[repr(u8)]
+#[part(auto)]
+pub enum Event {
+    Set {
+        amount: u32,
+        #[cfg(any())]
+        disabled: NotAType,
+    },
+    #[cfg(any())]
+    Disabled { disabled: NotAType },
+}
+
+#[test]
+fn test_cfg_fields() {
+    let mut tracked = Tracked::default();
+    let mut tracked_ref = tracked.into_partial_ref_mut();
+
+    let (value, mut tracked_ref) = tracked_ref.split_part_mut(Value);
+    *value = 1;
+    tracked_ref.part_mut(Enabled).push(*value);
+
+    assert_eq!(tracked.enabled, vec![1]);
+}
+
+#[test]
+fn test_cfg_variant_fields() {
+    let mut event = Event::Set { amount: 1 };
+    let event_ref = event.into_partial_ref_mut();
+
+    let mut set = event_ref.witness(Set).ok().unwrap();
+    *set.part_mut(Amount) += 1;
+
+    match event {
+        Event::Set { amount } => assert_eq!(amount, 2),
+    }
+}
@@ -0,0 +1,90 @@
+use std::ops::Range;
+use std::thread;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Scheduler {
+    #[part(auto)]
+    pub jobs: Vec<u64>,
+    #[part(auto)]
+    pub scale: u64,
+    #[part(auto)]
+    pub batches: usize,
+}
+
+fn run_job(mut job: Extended<Chunk<Jobs>, partial!(Scheduler, Scale)>) {
+    let scale = *job.part(Scale);
+    for value in job.extra_part_mut(Chunk::<Jobs>::default()) {
+        *value *= scale;
+    }
+}
+
+fn run(mut scheduler: partial!(Scheduler, mut Jobs, Scale, mut Batches), ranges: &[Range<usize>]) {
+    let (chunks, mut scheduler) = scheduler.split_chunks_part_mut(Jobs, ranges);
+    let chunks = chunks.unwrap();
+    *scheduler.part_mut(Batches) += chunks.len();
+
+    let (_, mut scheduler) = scheduler.split_part_mut(Batches);
+    let shared = scheduler.freeze();
+    thread::scope(|scope| {
+        for chunk in chunks {
+            scope.spawn(move || run_job(shared.into_inner().extend_with::<Chunk<Jobs>>(chunk)));
+        }
+    });
+}
+
+#[test]
+fn test_split_chunks() {
+    let mut scheduler = Scheduler {
+        jobs: (0..8).collect(),
+        scale: 10,
+        batches: 0,
+    };
+
+    run(
+        scheduler.into_partial_ref_mut().borrow(),
+        &[4..6, 0..3, 7..8],
+    );
+
+    assert_eq!(scheduler.jobs, [0, 10, 20, 3, 40, 50, 6, 70]);
+    assert_eq!(scheduler.batches, 3);
+}
+
+#[test]
+fn test_split_chunks_order() {
+    let mut scheduler = Scheduler {
+        jobs: (0..6).collect(),
+        ..Scheduler::default()
+    };
+    let mut scheduler = scheduler.into_partial_ref_mut();
+
+    let (chunks, _) = scheduler.split_chunks_part_mut(Jobs, &[3..6, 0..0, 1..3]);
+    let chunks = chunks.unwrap();
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0], [3, 4, 5]);
+    assert!(chunks[1].is_empty());
+    assert_eq!(chunks[2], [1, 2]);
+}
+
+#[test]
+fn test_split_chunks_errors() {
+    let mut scheduler = Scheduler {
+        jobs: (0..6).collect(),
+        ..Scheduler::default()
+    };
+    let mut scheduler = scheduler.into_partial_ref_mut();
+
+    let (chunks, _) = scheduler.split_chunks_part_mut(Jobs, &[0..3, 2..4]);
+    assert_eq!(chunks.unwrap_err(), DisjointMutError::OverlappingIndices);
+
+    let (chunks, _) = scheduler.split_chunks_part_mut(Jobs, &[0..3, 4..7]);
+    assert_eq!(chunks.unwrap_err(), DisjointMutError::IndexOutOfBounds);
+
+    let (start, end) = (4, 2);
+    let (chunks, mut rest) = scheduler.split_chunks_part_mut(Jobs, &[0..1, start..end]);
+    assert_eq!(chunks.unwrap_err(), DisjointMutError::IndexOutOfBounds);
+
+    *rest.part_mut(Batches) += 1;
+}
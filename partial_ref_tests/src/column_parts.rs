@@ -0,0 +1,113 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Motion {
+    #[part(auto)]
+    pub dx: f32,
+    #[part(auto)]
+    pub dy: f32,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Particle {
+    #[part(auto)]
+    pub x: f32,
+    #[part(auto)]
+    pub y: f32,
+    #[part(auto)]
+    pub velocity: Motion,
+    #[part(auto)]
+    pub name: String,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Simulation {
+    #[part(auto)]
+    pub particles: Vec<Particle>,
+    #[part(auto)]
+    pub steps: usize,
+}
+
+type PosX = ColumnPart<Particles, X>;
+type PosY = ColumnPart<Particles, Y>;
+type VelX = ColumnPart<Particles, Nested<Velocity, Dx>>;
+type VelY = ColumnPart<Particles, Nested<Velocity, Dy>>;
+type Names = ColumnPart<Particles, Name>;
+
+type ColumnsRef<'a> = partial!('a Simulation, mut PosX, mut PosY, VelX, VelY, mut Names);
+type StepRef<'a> = partial!('a Simulation, mut PosX, mut PosY, VelX, VelY);
+
+fn step(mut columns: StepRef) {
+    let (mut xs, mut columns) = columns.split_column_part_mut(PosX::default());
+    let (mut ys, columns) = columns.split_column_part_mut(PosY::default());
+    let dxs = columns.column_part(VelX::default());
+    let dys = columns.column_part(VelY::default());
+
+    for (x, dx) in xs.iter_mut().zip(dxs.iter()) {
+        *x += dx;
+    }
+    for index in 0..ys.len() {
+        ys[index] += dys[index];
+    }
+}
+
+fn rename(mut names: partial!(Simulation, mut Names)) {
+    let mut names = names.column_part_mut(Names::default());
+    if let Some(name) = names.get_mut(0) {
+        name.push_str(" (first)");
+    }
+    assert!(names.get_mut(names.len()).is_none());
+}
+
+fn particle(name: &str, x: f32, dx: f32) -> Particle {
+    Particle {
+        x,
+        y: -x,
+        velocity: Motion { dx, dy: -dx },
+        name: name.to_owned(),
+    }
+}
+
+#[test]
+fn test_column_parts() {
+    let mut simulation = Simulation {
+        particles: vec![particle("a", 1.0, 0.5), particle("b", 2.0, 1.0)],
+        ..Simulation::default()
+    };
+    let mut simulation_ref = simulation.into_partial_ref_mut();
+
+    let (mut all_columns, mut rest): (ColumnsRef, _) =
+        simulation_ref.split_column_parts_mut(Particles);
+    let (mut columns, mut names) = all_columns.split_borrow::<StepRef, _>();
+
+    step(columns.borrow());
+    rename(names.borrow());
+    step(columns);
+    *rest.part_mut(Steps) += 2;
+
+    let xs: Vec<f32> = simulation.particles.iter().map(|p| p.x).collect();
+    let ys: Vec<f32> = simulation.particles.iter().map(|p| p.y).collect();
+    assert_eq!(xs, [2.0, 4.0]);
+    assert_eq!(ys, [-2.0, -4.0]);
+    assert_eq!(simulation.particles[0].name, "a (first)");
+    assert_eq!(simulation.steps, 2);
+}
+
+#[test]
+fn test_column_views() {
+    let mut simulation = Simulation {
+        particles: vec![particle("a", 1.0, 0.5), particle("b", 2.0, 1.0)],
+        ..Simulation::default()
+    };
+    let mut simulation_ref = simulation.into_partial_ref_mut();
+
+    let (columns, _): (partial!(Simulation, VelX, mut Names), _) =
+        simulation_ref.split_column_parts_mut(Particles);
+
+    let dxs = columns.column_part(VelX::default());
+    assert_eq!(dxs.len(), 2);
+    assert!(!dxs.is_empty());
+    assert_eq!(dxs.get(1), Some(&1.0));
+    assert_eq!(dxs.get(2), None);
+    assert_eq!(dxs.iter().len(), 2);
+}
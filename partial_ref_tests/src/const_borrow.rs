@@ -0,0 +1,38 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Config {
+    #[part(auto)]
+    pub name: String,
+    #[part(auto)]
+    pub retries: u32,
+    #[part(auto)]
+    pub log: Vec<String>,
+}
+
+fn describe(config: partial!(Config, Name, Retries)) -> String {
+    format!("{} ({})", config.part(Name), config.part(Retries))
+}
+
+fn describe_shared(config: &partial!(Config, mut Log, Name, Retries)) -> String {
+    describe(config.borrow_const())
+}
+
+#[test]
+fn test_borrow_const() {
+    let mut config = Config {
+        name: "server".to_owned(),
+        retries: 3,
+        ..Config::default()
+    };
+    let mut config_ref = config.into_partial_ref_mut();
+    let mut config_ref: partial!(Config, mut Log, Name, Retries) = config_ref.borrow();
+
+    let first: partial!(Config, Name) = config_ref.borrow_const();
+    let second: partial!(Config, Retries, Name) = config_ref.borrow_const();
+    assert_eq!(first.part(Name), second.part(Name));
+
+    let description = describe_shared(&config_ref);
+    config_ref.part_mut(Log).push(description);
+    assert_eq!(config.log, ["server (3)"]);
+}
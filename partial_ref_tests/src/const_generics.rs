@@ -0,0 +1,34 @@
+use partial_ref::*;
+
+part!(pub Front<const N: usize>: [u8; N]);
+
+#[derive(PartialRefTarget)]
+#[part_group(Meta: len, generation)]
+pub struct Buffers<const N: usize = 4> {
+    #[part(Front<N>)]
+    pub front: [u8; N],
+    #[part(auto)]
+    pub back: [u8; N],
+    pub len: usize,
+    pub generation: usize,
+}
+
+#[test]
+fn test_const_generic_target() {
+    let mut buffers: Buffers = Buffers {
+        front: [1, 2, 3, 4],
+        back: [0; 4],
+        len: 0,
+        generation: 0,
+    };
+    let mut buffers_ref = buffers.into_partial_ref_mut();
+
+    let (front, mut buffers_ref) = buffers_ref.split_part(Front::default());
+    let (meta, mut buffers_ref) = buffers_ref.split_group_mut(Meta::default());
+    buffers_ref.part_mut(Back::default()).copy_from_slice(front);
+    *meta.len = front.len();
+    *meta.generation += 1;
+
+    assert_eq!(buffers.back, [1, 2, 3, 4]);
+    assert_eq!((buffers.len, buffers.generation), (4, 1));
+}
@@ -0,0 +1,41 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget)]
+#[repr(C)]
+pub struct Table {
+    #[part(auto)]
+    pub scale: u32,
+    #[part(auto)]
+    pub cells: [u32],
+}
+
+fn duplicate<Reference: ConstOnly>(reference: Reference, count: usize) -> Vec<Reference> {
+    vec![reference; count]
+}
+
+fn scaled(table: partial!(Table, Scale, Cells), row: usize) -> u32 {
+    table.part(Scale) * table.part(Cells)[row]
+}
+
+#[test]
+fn test_const_only() {
+    // A table with a scale of 2 followed by 3 cells.
+    let mut storage = [2u32, 1, 2, 3];
+    let table = unsafe {
+        &mut *(std::ptr::slice_from_raw_parts_mut(storage.as_mut_ptr(), 3) as *mut Table)
+    };
+    let mut table_ref = table.into_partial_ref_mut();
+
+    let tables = duplicate(table_ref.as_const(), 3);
+    let sums = tables
+        .into_iter()
+        .enumerate()
+        .map(|(row, mut table)| scaled(table.borrow(), row))
+        .collect::<Vec<_>>();
+    assert_eq!(sums, [2, 4, 6]);
+
+    let cells: partial!(Table, Scale, Cells) = table_ref.borrow();
+    let scaled_row = move |row| scaled(cells, row);
+    assert_eq!((0..3).map(scaled_row).sum::<u32>(), 12);
+    assert_eq!(scaled_row(2), 6);
+}
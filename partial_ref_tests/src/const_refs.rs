@@ -0,0 +1,92 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Graph {
+    #[part(auto)]
+    pub edges: Vec<(usize, usize)>,
+    #[part(auto)]
+    pub labels: Vec<&'static str>,
+    #[part(auto)]
+    pub visits: usize,
+}
+
+fn edge_count(graph: partial!(Graph, Edges)) -> usize {
+    graph.part(Edges).len()
+}
+
+fn describe(graph: partial!(Graph, Edges, Labels)) -> String {
+    graph
+        .part(Edges)
+        .iter()
+        .map(|&(from, to)| format!("{}->{}", graph.part(Labels)[from], graph.part(Labels)[to]))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[test]
+fn test_as_const() {
+    let mut graph = Graph {
+        edges: vec![(0, 1), (1, 0)],
+        labels: vec!["a", "b"],
+        visits: 0,
+    };
+    let mut graph_ref = graph.into_partial_ref_mut();
+    let mut graph_ref: partial!(Graph, mut Edges, mut Labels) = graph_ref.borrow();
+
+    let mut const_ref = graph_ref.as_const();
+    let copy = const_ref;
+    assert_eq!(edge_count(const_ref.borrow()), 2);
+    assert_eq!(describe(copy), "a->b,b->a");
+
+    graph_ref.part_mut(Edges).pop();
+    assert_eq!(describe(graph_ref.as_const()), "a->b");
+}
+
+fn visit(mut graph: partial!(Graph, mut Edges, mut Labels, mut Visits), log: &mut Vec<String>) {
+    {
+        let mut guard = graph.downgrade_scope();
+        log.push(describe(guard.borrow()));
+        log.push(edge_count(guard.borrow()).to_string());
+    }
+    *graph.part_mut(Visits) += 1;
+    graph.part_mut(Edges).clear();
+    let mut guard = graph.downgrade_scope();
+    assert_eq!(*guard.part(Visits), 1);
+    log.push(describe(guard.borrow()));
+}
+
+#[test]
+fn test_downgrade_scope() {
+    let mut graph = Graph {
+        edges: vec![(0, 1)],
+        labels: vec!["a", "b"],
+        visits: 0,
+    };
+    let mut graph_ref = graph.into_partial_ref_mut();
+    let mut log = vec![];
+
+    visit(graph_ref.borrow(), &mut log);
+    assert_eq!(*graph_ref.downgrade_scope().part(Visits), 1);
+    assert_eq!(log, ["a->b", "1", ""]);
+}
+
+fn count_visits(mut graph: partial!(Graph, Edges, mut Visits, Labels)) -> String {
+    let (mut const_ref, mut mut_ref) = graph.split_const();
+    let copy = const_ref;
+    *mut_ref.part_mut(Visits) += edge_count(const_ref.borrow());
+    *mut_ref.part_mut(Visits) += 1;
+    describe(copy)
+}
+
+#[test]
+fn test_split_const() {
+    let mut graph = Graph {
+        edges: vec![(0, 1)],
+        labels: vec!["a", "b"],
+        visits: 0,
+    };
+    let mut graph_ref = graph.into_partial_ref_mut();
+
+    assert_eq!(count_visits(graph_ref.borrow()), "a->b");
+    assert_eq!(graph.visits, 2);
+}
@@ -0,0 +1,32 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Particle {
+    #[part(auto)]
+    pub position: f64,
+    #[part(auto)]
+    pub velocity: f64,
+    #[part(auto)]
+    pub time_step: f64,
+}
+
+fn step(mut particle: partial!(Particle, mut Position, mut Velocity, TimeStep)) {
+    let time_step = particle.part_copied(TimeStep);
+    let velocity = particle.part_copied(Velocity);
+    *particle.part_mut(Position) += velocity * time_step;
+    *particle.part_mut(Velocity) -= particle.part_copied(Position) * time_step;
+}
+
+#[test]
+fn test_part_copied() {
+    let mut particle = Particle {
+        velocity: 2.0,
+        time_step: 0.5,
+        ..Particle::default()
+    };
+    let mut particle_ref = particle.into_partial_ref_mut();
+
+    step(particle_ref.borrow());
+    assert_eq!(particle_ref.part_copied(Position), 1.0);
+    assert_eq!(particle_ref.part_copied(Velocity), 1.5);
+}
@@ -0,0 +1,80 @@
+use partial_ref::*;
+
+part!(pub Colors: Vec<u32>);
+part!(pub Weights: Vec<f32>);
+part!(pub Scale: f32);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Palette {
+    #[part(Colors)]
+    pub colors: Vec<u32>,
+    #[part(Weights)]
+    pub weights: Vec<f32>,
+    #[part(Scale)]
+    pub scale: f32,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Snapshot {
+    #[part(Scale)]
+    pub scale: f32,
+    #[part(Colors)]
+    pub colors: Vec<u32>,
+}
+
+fn restore(mut palette: partial!(Palette, mut Colors, mut Scale, Weights), snapshot: &Snapshot) {
+    palette.copy_parts_from(&snapshot.into_partial_ref());
+}
+
+#[test]
+fn test_copy_parts() {
+    let mut source = Palette {
+        colors: vec![1, 2],
+        weights: vec![0.5],
+        scale: 2.0,
+    };
+    let mut dest = Palette::default();
+
+    let source_ref = source.into_partial_ref_mut();
+    let mut dest_ref = dest.into_partial_ref_mut();
+    let mut colors_ref: partial!(Palette, mut Colors, Weights) = dest_ref.borrow();
+    colors_ref.copy_parts_from(&source_ref);
+
+    assert_eq!(dest.colors, vec![1, 2]);
+    assert!(dest.weights.is_empty());
+    assert_eq!(dest.scale, 0.0);
+
+    let snapshot = Snapshot {
+        scale: 3.0,
+        colors: vec![4],
+    };
+    restore(source.into_partial_ref_mut().borrow(), &snapshot);
+
+    assert_eq!(source.colors, vec![4]);
+    assert_eq!(source.weights, vec![0.5]);
+    assert_eq!(source.scale, 3.0);
+}
+
+#[test]
+fn test_clone_parts_into() {
+    let mut palette = Palette {
+        colors: vec![1, 2],
+        weights: vec![0.5],
+        scale: 2.0,
+    };
+    let mut snapshot = Snapshot::default();
+
+    let mut palette_ref = palette.into_partial_ref_mut();
+    let state_ref: partial!(Palette, Scale, mut Colors) = palette_ref.borrow();
+    state_ref.clone_parts_into(&mut snapshot.into_partial_ref_mut());
+
+    assert_eq!(snapshot.colors, vec![1, 2]);
+    assert_eq!(snapshot.scale, 2.0);
+
+    let mut copy = Palette::default();
+    let weights_ref: partial!(Palette, Weights) = palette_ref.borrow();
+    weights_ref.clone_parts_into(&mut copy.into_partial_ref_mut());
+
+    assert_eq!(copy.weights, vec![0.5]);
+    assert!(copy.colors.is_empty());
+}
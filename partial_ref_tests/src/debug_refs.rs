@@ -0,0 +1,44 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Graph {
+    #[part(auto)]
+    pub neighbors: Vec<Vec<usize>>,
+    #[part(auto)]
+    pub colors: Vec<usize>,
+    #[part(auto)]
+    pub weights: Vec<f32>,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Slots<T> {
+    #[part(elements(Slot))]
+    pub slots: [T; 2],
+}
+
+#[test]
+fn test_debug_refs() {
+    let mut g = Graph::default();
+    let mut g_ref = g.into_partial_ref_mut();
+
+    let colors_ref: partial!(Graph, mut Colors, Weights) = g_ref.borrow();
+    assert_eq!(
+        format!("{:?}", colors_ref),
+        "PartialRef<Graph> { mut Colors, Weights }"
+    );
+
+    let empty_ref: partial!(Graph) = g_ref.borrow();
+    assert_eq!(format!("{:?}", empty_ref), "PartialRef<Graph> { }");
+}
+
+#[test]
+fn test_debug_generic_parts() {
+    let mut slots = Slots::<Vec<u8>>::default();
+    let mut slots_ref = slots.into_partial_ref_mut();
+    let slots_ref: partial!(Slots<Vec<u8>>, mut Slot<Vec<u8>, 1>) = slots_ref.borrow();
+
+    assert_eq!(
+        format!("{:?}", slots_ref),
+        "PartialRef<Slots<Vec<u8>>> { mut Slot<Vec<u8>, 1> }"
+    );
+}
@@ -0,0 +1,86 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use partial_ref::*;
+
+#[derive(Default)]
+pub struct BigState {
+    pub values: Vec<usize>,
+}
+
+part!(pub HeapState: BigState);
+part!(pub Names: Vec<&'static str>);
+part!(pub Config: usize);
+part!(pub Hot: usize);
+part!(pub shared Labels: Vec<&'static str>);
+part!(pub shared Limit: usize);
+
+#[derive(PartialRefTarget)]
+pub struct Engine<'a> {
+    #[part(Hot)]
+    pub hot: usize,
+    #[part(deref, HeapState)]
+    pub heap: Box<BigState>,
+    #[part(deref, Names)]
+    pub names: &'a mut Vec<&'static str>,
+    #[part(deref, Config)]
+    pub config: Box<usize>,
+    #[part(deref, Labels)]
+    pub labels: Rc<Vec<&'static str>>,
+    #[part(deref, Limit)]
+    pub limit: Arc<usize>,
+}
+
+fn step(mut engine: partial!(Engine<'_>, mut Hot, mut HeapState, Config, Limit)) {
+    let (state, mut engine) = engine.split_part_mut(HeapState);
+    let (config, mut engine) = engine.split_part(Config);
+    let (limit, mut engine) = engine.split_shared_part(Limit);
+    if state.values.len() < *limit {
+        state.values.push(*config);
+    }
+    *engine.part_mut(Hot) += 1;
+}
+
+#[test]
+fn test_deref_parts() {
+    let mut names = vec![];
+    let mut engine = Engine {
+        hot: 0,
+        heap: Box::default(),
+        names: &mut names,
+        config: Box::new(7),
+        labels: Rc::new(vec![]),
+        limit: Arc::new(1),
+    };
+    let mut engine_ref = engine.into_partial_ref_mut();
+
+    step(engine_ref.borrow());
+    step(engine_ref.borrow());
+    engine_ref.part_mut(Names).push("a");
+
+    assert_eq!(engine_ref.part(HeapState).values, vec![7]);
+    assert_eq!(*engine_ref.part(Hot), 2);
+    assert_eq!(names, vec!["a"]);
+}
+
+#[test]
+fn test_shared_deref_parts() {
+    let labels = Rc::new(vec!["x", "y"]);
+    let mut names = vec![];
+    let mut engine = Engine {
+        hot: 0,
+        heap: Box::default(),
+        names: &mut names,
+        config: Box::new(7),
+        labels: labels.clone(),
+        limit: Arc::new(2),
+    };
+    let mut engine_ref = engine.into_partial_ref_mut();
+
+    let (shared_labels, mut engine_ref) = engine_ref.split_shared_part(Labels);
+    engine_ref.part_mut(Names).extend(shared_labels.iter());
+
+    assert_eq!(*engine_ref.shared_part(Limit), 2);
+    assert_eq!(names, ["x", "y"]);
+    assert_eq!(Rc::strong_count(&labels), 2);
+}
@@ -0,0 +1,63 @@
+use partial_ref::*;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+}
+
+pub struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+part!(pub Body<'a>: dyn Shape + 'a);
+part!(pub Outline<'a>: dyn Shape + 'a);
+
+#[derive(PartialRefTarget)]
+pub struct Scene<'a> {
+    #[part(deref, Body<'a>)]
+    pub body: Box<dyn Shape + 'a>,
+    #[part(deref, Outline<'a>)]
+    pub outline: &'a mut (dyn Shape + 'a),
+    #[part(auto)]
+    pub name: String,
+}
+
+fn grow<'a>(mut scene: partial!(Scene<'a>, mut Body<'a>, Outline<'a>, Name)) -> f64 {
+    let outline = scene.part(Outline).area();
+    let body = scene.part_mut(Body);
+    body.scale(2.0);
+    body.area() - outline
+}
+
+fn describe(shape: &dyn Shape, name: &str) -> String {
+    format!("{}: {}", name, shape.area())
+}
+
+#[test]
+fn test_dyn_fields() {
+    let mut square = Square(1.0);
+    let mut scene = Scene {
+        body: Box::new(Square(2.0)),
+        outline: &mut square,
+        name: "scene".to_owned(),
+    };
+    let mut scene_ref = scene.into_partial_ref_mut();
+
+    assert_eq!(grow(scene_ref.borrow()), 15.0);
+
+    let (outline, scene_ref) = scene_ref.split_part_mut(Outline);
+    outline.scale(3.0);
+    assert_eq!(
+        describe(scene_ref.part(Body), scene_ref.part(Name)),
+        "scene: 16"
+    );
+    assert_eq!(describe(outline, "outline"), "outline: 9");
+}
@@ -0,0 +1,57 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Vault {
+    #[part(auto)]
+    pub banks: [i64; 4],
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Ledger {
+    #[part(auto)]
+    pub accounts: Vec<i64>,
+    #[part(auto)]
+    pub main: Vault,
+    #[part(auto)]
+    pub transfers: usize,
+}
+
+type First = ElemPart<Accounts, 0>;
+type Last = ElemPart<Accounts, 2>;
+
+fn transfer(mut accounts: partial!(Ledger, mut First, mut Last), amount: i64) {
+    *accounts.part_mut(First::default()) -= amount;
+    *accounts.part_mut(Last::default()) += amount;
+}
+
+type Bank<const INDEX: usize> = ElemPart<Nested<Main, Banks>, INDEX>;
+
+fn swap_banks(mut banks: partial!(Ledger, mut Bank<0>, mut Bank<3>)) {
+    let (first, mut banks) = banks.split_part_mut(Bank::<0>::default());
+    std::mem::swap(first, banks.part_mut(Bank::<3>::default()));
+}
+
+#[test]
+fn test_elem_parts() {
+    let mut ledger = Ledger {
+        accounts: vec![10, 20, 30],
+        main: Vault {
+            banks: [1, 2, 3, 4],
+        },
+        ..Ledger::default()
+    };
+    let mut ledger_ref = ledger.into_partial_ref_mut();
+
+    let (accounts, mut rest): (partial!(Ledger, mut First, mut Last), _) =
+        ledger_ref.split_range_parts_mut(Accounts);
+    transfer(accounts, 5);
+    *rest.part_mut(Transfers) += 1;
+
+    let (banks, _): (partial!(Ledger, mut Bank<0>, mut Bank<3>), _) =
+        rest.split_range_parts_mut(Main | Banks);
+    swap_banks(banks);
+
+    assert_eq!(ledger.accounts, [5, 20, 35]);
+    assert_eq!(ledger.main.banks, [4, 2, 3, 1]);
+    assert_eq!(ledger.transfers, 1);
+}
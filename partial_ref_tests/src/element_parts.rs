@@ -0,0 +1,59 @@
+use partial_ref::*;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Bank {
+    pub balance: i64,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Ledger {
+    #[part(elements(BankPart))]
+    pub banks: [Bank; 4],
+    #[part(auto)]
+    pub transfers: usize,
+}
+
+fn transfer(
+    mut ledger: partial!(Ledger, mut BankPart<0>, mut BankPart<3>, mut Transfers),
+    amount: i64,
+) {
+    let (from, mut ledger) = ledger.split_part_mut(BankPart::<0>::default());
+    let (to, mut ledger) = ledger.split_part_mut(BankPart::<3>::default());
+    from.balance -= amount;
+    to.balance += amount;
+    *ledger.part_mut(Transfers) += 1;
+}
+
+#[derive(PartialRefTarget)]
+pub struct Slots<'a, T> {
+    #[part(elements(Slot))]
+    pub slots: [&'a [T]; 2],
+}
+
+#[test]
+fn test_element_parts() {
+    let mut ledger = Ledger::default();
+    let mut ledger_ref = ledger.into_partial_ref_mut();
+
+    transfer(ledger_ref.borrow(), 5);
+    transfer(ledger_ref.borrow(), 2);
+
+    assert_eq!(ledger_ref.part(BankPart::<0>::default()).balance, -7);
+    assert_eq!(*ledger_ref.part(Transfers), 2);
+    assert_eq!(ledger.banks[3], Bank { balance: 7 });
+    assert_eq!(ledger.banks[1], Bank { balance: 0 });
+}
+
+#[test]
+fn test_generic_element_parts() {
+    let values = [1, 2, 3];
+    let mut slots = Slots {
+        slots: [&values[..1], &values[1..]],
+    };
+    let mut slots_ref = slots.into_partial_ref_mut();
+
+    let (first, slots_ref) = slots_ref.split_part_mut(Slot::<_, 0>::default());
+    *first = &values[2..];
+    assert_eq!(*slots_ref.part(Slot::<_, 1>::default()), &[2, 3]);
+    assert_eq!(slots.slots, [&[3][..], &[2, 3][..]]);
+}
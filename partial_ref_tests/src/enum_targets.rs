@@ -0,0 +1,88 @@
+use partial_ref::*;
+
+part!(pub Running);
+part!(pub Finished);
+part!(pub Progress in Running: usize);
+part!(pub Log in Running: Vec<String>);
+part!(pub Status in Finished: i32);
+
+#[derive(PartialRefTarget)]
+#[repr(u8)]
+pub enum Job {
+    Waiting,
+    #[part(Running)]
+    Running {
+        #[part(Progress)]
+        progress: usize,
+        #[part(Log)]
+        log: Vec<String>,
+    },
+    #[part(Finished)]
+    Finished(#[part(Status)] i32),
+}
+
+fn advance(job: partial!(Job, Running, mut Progress, Log)) -> bool {
+    if let Ok(mut running) = job.witness(Running) {
+        *running.part_mut(Progress) += 1;
+        true
+    } else {
+        false
+    }
+}
+
+#[test]
+fn test_variant_witness() {
+    let mut job = Job::Running {
+        progress: 1,
+        log: vec![],
+    };
+    let mut job_ref = job.into_partial_ref_mut();
+
+    assert!(job_ref.reborrow().witness(Finished).is_err());
+    assert!(advance(job_ref.borrow()));
+
+    let running = job_ref.witness(Running).ok().unwrap();
+
+    let (log, running) = running.split_part_mut(Log);
+    let progress = running.part(Progress);
+
+    log.push(format!("progress {}", progress));
+
+    match job {
+        Job::Running { progress, log } => {
+            assert_eq!(progress, 2);
+            assert_eq!(log, vec!["progress 2".to_owned()]);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_inactive_variant() {
+    let mut job = Job::Finished(3);
+    let mut job_ref = job.into_partial_ref_mut();
+
+    assert!(!advance(job_ref.borrow()));
+
+    let mut finished = job_ref.witness(Finished).ok().unwrap();
+    *finished.part_mut(Status) = 0;
+
+    let mut job = Job::Waiting;
+    let mut job_ref = job.into_partial_ref_mut();
+
+    assert!(job_ref.reborrow().witness(Running).is_err());
+    assert!(job_ref.witness(Finished).is_err());
+}
+
+#[test]
+fn test_witness_into_inner() {
+    let mut job = Job::Finished(3);
+    let job_ref = job.into_partial_ref_mut();
+
+    let finished = job_ref.witness(Finished).ok().unwrap();
+    let (status, finished) = finished.split_part(Status);
+    let job_ref = finished.into_inner();
+
+    assert!(job_ref.witness(Running).is_err());
+    assert_eq!(*status, 3);
+}
@@ -0,0 +1,94 @@
+use partial_ref::*;
+
+part!(pub Scratch: Vec<usize>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Graph {
+    #[part(auto)]
+    pub neighbors: Vec<Vec<usize>>,
+    #[part(auto)]
+    pub colors: Vec<usize>,
+}
+
+fn count_colors(mut g: Extended<Scratch, partial!(Graph, Colors)>) {
+    let (scratch, g) = g.split_extra_part();
+    scratch.clear();
+    for &color in g.part(Colors) {
+        if scratch.len() <= color {
+            scratch.resize(color + 1, 0);
+        }
+        scratch[color] += 1;
+    }
+}
+
+fn most_common_color(mut g: Extended<Scratch, partial!(Graph, mut Colors, Neighbors)>) -> usize {
+    count_colors(g.borrow());
+    let counts = g.extra_part(Scratch);
+    (0..counts.len())
+        .max_by_key(|&color| counts[color])
+        .unwrap()
+}
+
+#[test]
+fn test_extended() {
+    let mut g = Graph {
+        neighbors: vec![vec![]; 3],
+        colors: vec![2, 0, 2],
+    };
+    let mut scratch = vec![];
+    let mut g_ref = g.into_partial_ref_mut();
+
+    let color = most_common_color(Extended::new(g_ref.borrow(), &mut scratch));
+    assert_eq!(color, 2);
+    assert_eq!(scratch, [1, 0, 2]);
+
+    let mut extended = g_ref.reborrow().extend_with::<Scratch>(&mut scratch);
+    extended.extra_part_mut(Scratch).push(7);
+    extended.part_mut(Colors)[1] = 1;
+    let (_, scratch) = extended.into_inner();
+    assert_eq!(scratch.len(), 4);
+    assert_eq!(g.colors, [2, 1, 2]);
+}
+
+#[derive(Default)]
+pub struct Limits {
+    pub max_colors: usize,
+}
+
+#[derive(Default)]
+pub struct Options {
+    pub limits: Limits,
+    pub verbose: bool,
+}
+
+part!(pub LimitsPart: Limits);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Coloring {
+    #[part(auto)]
+    pub config: Options,
+    #[part(auto)]
+    pub palette: Vec<usize>,
+}
+
+fn clamp_colors(mut coloring: Extended<LimitsPart, partial!(Coloring, mut Palette)>) {
+    let (limits, coloring) = coloring.split_extra_part();
+    limits.max_colors = limits.max_colors.max(1);
+    for color in coloring.part_mut(Palette) {
+        *color = (*color).min(limits.max_colors - 1);
+    }
+}
+
+#[test]
+fn test_map_part_mut() {
+    let mut coloring = Coloring {
+        palette: vec![0, 3, 1],
+        ..Coloring::default()
+    };
+    coloring.config.limits.max_colors = 2;
+    let mut coloring_ref = coloring.into_partial_ref_mut();
+
+    clamp_colors(coloring_ref.map_part_mut(Config, |config| &mut config.limits));
+    assert_eq!(coloring_ref.part(Palette), &[0, 1, 1]);
+    assert!(!coloring_ref.part(Config).verbose);
+}
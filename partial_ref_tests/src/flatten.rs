@@ -0,0 +1,71 @@
+use partial_ref::*;
+
+part!(pub Buffer: Vec<u8>);
+part!(pub Cursor: usize);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Reader {
+    #[part(Buffer)]
+    pub buffer: Vec<u8>,
+    #[part(Cursor)]
+    pub cursor: usize,
+}
+
+part!(pub Checksum: u32);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Decoder {
+    #[part(flatten(Buffer, Cursor))]
+    pub reader: Reader,
+    #[part(Checksum)]
+    pub checksum: u32,
+}
+
+fn read_byte(mut reader: partial!(Reader, Buffer, mut Cursor)) -> Option<u8> {
+    let (buffer, mut reader) = reader.split_part(Buffer);
+    let cursor = reader.part_mut(Cursor);
+    let byte = buffer.get(*cursor).cloned();
+    *cursor += byte.is_some() as usize;
+    byte
+}
+
+fn decode_byte(mut decoder: partial!(Decoder, Buffer, mut Cursor, mut Checksum)) -> Option<u8> {
+    let (checksum, mut decoder) = decoder.split_part_mut(Checksum);
+    let (buffer, mut decoder) = decoder.split_part(Buffer);
+    let cursor = decoder.part_mut(Cursor);
+    let byte = buffer.get(*cursor).cloned()?;
+    *cursor += 1;
+    *checksum = checksum.wrapping_add(byte as u32);
+    Some(byte)
+}
+
+#[test]
+fn test_flattened_parts() {
+    let mut decoder = Decoder::default();
+    decoder.reader.buffer = vec![1, 2, 3];
+
+    let mut decoder_ref = decoder.into_partial_ref_mut();
+
+    assert_eq!(decode_byte(decoder_ref.borrow()), Some(1));
+    assert_eq!(decode_byte(decoder_ref.borrow()), Some(2));
+
+    assert_eq!(*decoder_ref.part(Cursor), 2);
+    assert_eq!(*decoder_ref.part(Checksum), 3);
+
+    assert_eq!(decode_byte(decoder_ref.borrow()), Some(3));
+    assert_eq!(decode_byte(decoder_ref.borrow()), None);
+
+    assert_eq!(decoder.reader.cursor, 3);
+    assert_eq!(decoder.checksum, 6);
+}
+
+#[test]
+fn test_flattened_and_inner_parts_agree() {
+    let mut decoder = Decoder::default();
+    decoder.reader.buffer = vec![7];
+
+    let mut reader_ref = decoder.reader.into_partial_ref_mut();
+    assert_eq!(read_byte(reader_ref.borrow()), Some(7));
+
+    assert_eq!(*decoder.into_partial_ref().part(Cursor), 1);
+}
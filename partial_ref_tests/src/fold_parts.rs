@@ -0,0 +1,107 @@
+use std::marker::PhantomData;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Device {
+    #[part(auto)]
+    pub registers: [u32; 4],
+    #[part(auto)]
+    pub buffer: Vec<u8>,
+    #[part(auto)]
+    pub irq: bool,
+}
+
+// Counts the mutable parts using type-level numbers.
+struct CountMut;
+
+struct Zero;
+struct Succ<N>(PhantomData<N>);
+
+trait Number {
+    const VALUE: usize;
+}
+
+impl Number for Zero {
+    const VALUE: usize = 0;
+}
+
+impl<N: Number> Number for Succ<N> {
+    const VALUE: usize = N::VALUE + 1;
+}
+
+impl<SomePart, Acc> PartFolder<SomePart, AccessConst, Acc> for CountMut {
+    type Output = Acc;
+}
+
+impl<SomePart, Acc> PartFolder<SomePart, AccessMut, Acc> for CountMut {
+    type Output = Succ<Acc>;
+}
+
+fn count_mut<Reference: FoldParts<CountMut, Zero>>(_reference: &Reference) -> usize
+where
+    Reference::Output: Number,
+{
+    Reference::Output::VALUE
+}
+
+// Computes a bitmask of the accessed parts, e.g. for a scheduler.
+struct PartMask;
+
+struct Mask<Acc, SomePart>(PhantomData<(Acc, SomePart)>);
+
+trait PartBit {
+    const BIT: u32;
+}
+
+impl PartBit for Registers {
+    const BIT: u32 = 1;
+}
+
+impl PartBit for Buffer {
+    const BIT: u32 = 2;
+}
+
+impl PartBit for Irq {
+    const BIT: u32 = 4;
+}
+
+trait MaskValue {
+    const MASK: u32;
+}
+
+impl MaskValue for () {
+    const MASK: u32 = 0;
+}
+
+impl<Acc: MaskValue, SomePart: PartBit> MaskValue for Mask<Acc, SomePart> {
+    const MASK: u32 = Acc::MASK | SomePart::BIT;
+}
+
+impl<SomePart, Access, Acc> PartFolder<SomePart, Access, Acc> for PartMask {
+    type Output = Mask<Acc, SomePart>;
+}
+
+fn mask<Reference: FoldParts<PartMask, ()>>(_reference: &Reference) -> u32
+where
+    Reference::Output: MaskValue,
+{
+    Reference::Output::MASK
+}
+
+#[test]
+fn test_fold_parts() {
+    let mut device = Device::default();
+    let mut device_ref = device.into_partial_ref_mut();
+
+    assert_eq!(count_mut(&device_ref), 3);
+    assert_eq!(mask(&device_ref), 7);
+
+    let irq_ref: partial!(Device, mut Irq, Registers) = device_ref.borrow();
+    assert_eq!(count_mut(&irq_ref), 1);
+    assert_eq!(mask(&irq_ref), 5);
+
+    let empty_ref: partial!(Device) = device_ref.borrow();
+    assert_eq!(count_mut(&empty_ref), 0);
+    assert_eq!(mask(&empty_ref), 0);
+}
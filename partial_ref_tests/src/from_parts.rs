@@ -0,0 +1,43 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget)]
+#[partial_ref(from_parts)]
+#[part_group(Scratch: buffer)]
+pub struct Graph {
+    #[part(auto)]
+    pub colors: Vec<usize>,
+    #[part(auto)]
+    pub weights: Vec<f32>,
+    pub buffer: Vec<usize>,
+    pub visits: usize,
+}
+
+#[derive(PartialRefTarget)]
+#[partial_ref(from_parts)]
+pub struct Pair<T>(
+    #[part(PairFirst<T>)] pub T,
+    pub usize,
+    #[part(PairLast<T>)] pub T,
+);
+
+part!(pub PairFirst<T>: T);
+part!(pub PairLast<T>: T);
+
+fn total_weight(graph: partial!(Graph, Colors, Weights)) -> f32 {
+    graph
+        .part(Colors)
+        .iter()
+        .map(|&color| graph.part(Weights)[color])
+        .sum()
+}
+
+#[test]
+fn test_from_parts() {
+    let mut graph = Graph::from_parts(vec![0, 1, 1], vec![0.5, 2.0]);
+    assert_eq!(total_weight(graph.into_partial_ref_mut().borrow()), 4.5);
+    assert!(graph.buffer.is_empty());
+    assert_eq!(graph.visits, 0);
+
+    let pair = Pair::from_parts('a', 'b');
+    assert_eq!((pair.0, pair.1, pair.2), ('a', 0, 'b'));
+}
@@ -0,0 +1,45 @@
+use std::thread;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Index {
+    #[part(auto)]
+    pub documents: Vec<String>,
+    #[part(auto)]
+    pub lengths: Vec<usize>,
+}
+
+fn total_length(index: partial!(Index, Documents)) -> usize {
+    index
+        .part(Documents)
+        .iter()
+        .map(|document| document.len())
+        .sum()
+}
+
+fn analyze(mut index: partial!(Index, mut Documents, mut Lengths)) -> usize {
+    index.part_mut(Documents).push("ghi".to_owned());
+
+    let frozen = index.freeze();
+    let (total, count) = thread::scope(|scope| {
+        let total = scope.spawn(move || total_length(frozen.into_inner().borrow_const()));
+        let count = scope.spawn(move || frozen.part(Documents).len());
+        (total.join().unwrap(), count.join().unwrap())
+    });
+
+    index.part_mut(Lengths).push(total);
+    count
+}
+
+#[test]
+fn test_freeze() {
+    let mut index = Index {
+        documents: vec!["a".to_owned(), "bc".to_owned()],
+        ..Index::default()
+    };
+    let mut index_ref = index.into_partial_ref_mut();
+
+    assert_eq!(analyze(index_ref.borrow()), 3);
+    assert_eq!(index.lengths, [6]);
+}
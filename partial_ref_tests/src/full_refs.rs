@@ -0,0 +1,47 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default, Debug, PartialEq)]
+pub struct Counter {
+    #[part(auto)]
+    pub count: usize,
+    #[part(auto)]
+    pub history: Vec<usize>,
+}
+
+fn reset(counter: &mut Counter) {
+    *counter = Counter::default();
+}
+
+fn record(mut counter: partial!(Counter, mut History, mut Count)) {
+    let count = *counter.part(Count);
+    counter.part_mut(History).push(count);
+    reset(counter.into_full());
+}
+
+#[derive(PartialRefTarget)]
+#[partial_ref(raw = "std::cell::UnsafeCell<Self>")]
+pub struct Shared {
+    #[part(auto)]
+    pub value: u32,
+}
+
+#[test]
+fn test_into_full() {
+    let mut counter = Counter {
+        count: 3,
+        history: vec![],
+    };
+    let mut counter_ref = counter.into_partial_ref_mut();
+
+    record(counter_ref.borrow());
+    assert_eq!(counter_ref.into_full(), &mut Counter::default());
+}
+
+#[test]
+fn test_into_full_raw_target() {
+    let mut shared = Shared { value: 1 };
+    let shared_ref = shared.into_partial_ref_mut();
+
+    shared_ref.into_full().value = 2;
+    assert_eq!(shared.value, 2);
+}
@@ -0,0 +1,44 @@
+use partial_ref::*;
+
+part!(pub Trail: Vec<usize>);
+part!(pub Stats: usize);
+
+pub trait Solver: PartialRefTarget<RawTarget = Self> + HasPart<Trail> {}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Cdcl {
+    #[part(Trail)]
+    pub trail: Vec<usize>,
+    #[part(Stats)]
+    pub stats: usize,
+}
+
+impl Solver for Cdcl {}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Ctx<S: Solver> {
+    #[part(auto)]
+    pub engine: S,
+    #[part(auto)]
+    pub steps: usize,
+}
+
+fn step<S: Solver>(mut ctx: partial!(Ctx<S>, mut Engine<S> | Trail, mut Steps)) {
+    let (trail, mut ctx) = ctx.split_part_mut(Engine::default() | Trail);
+    trail.push(1);
+    *ctx.part_mut(Steps) += 1;
+}
+
+#[test]
+fn test_generic_field_nested() {
+    let mut ctx = Ctx::<Cdcl>::default();
+    let mut ctx_ref = ctx.into_partial_ref_mut();
+
+    step(ctx_ref.borrow());
+    let (stats, _) = ctx_ref.split_part_mut(Engine::<Cdcl>::default() | Stats);
+    *stats += 1;
+
+    assert_eq!(ctx.engine.trail, vec![1]);
+    assert_eq!(ctx.engine.stats, 1);
+    assert_eq!(ctx.steps, 1);
+}
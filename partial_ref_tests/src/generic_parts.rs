@@ -0,0 +1,64 @@
+use partial_ref::*;
+
+part!(pub Items<T>: Vec<T>);
+part!(pub Len: usize);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Storage<T> {
+    #[part(Items<T>)]
+    pub items: Vec<T>,
+    #[part(Len)]
+    pub len: usize,
+}
+
+fn push<T>(mut storage: partial!(Storage<T>, mut Items<T>, mut Len), item: T) {
+    let (items, mut storage) = storage.split_part_mut(Items::default());
+    items.push(item);
+    *storage.part_mut(Len) = items.len();
+}
+
+#[test]
+fn test_generic_part() {
+    let mut storage = Storage::default();
+    let mut storage_ref = storage.into_partial_ref_mut();
+
+    push(storage_ref.borrow(), "a");
+    push(storage_ref.borrow(), "b");
+
+    assert_eq!(*storage_ref.part(Len), 2);
+    assert_eq!(storage_ref.part(Items::<&str>::default()), &["a", "b"]);
+}
+
+part!(pub Window<'a, T>: &'a [T]);
+
+#[derive(PartialRefTarget)]
+#[part_group(Bounds: lower, upper)]
+pub struct Cursor<'a, T, U> {
+    #[part(Window<'a, T>)]
+    pub window: &'a [T],
+    #[part(auto)]
+    pub pending: Vec<U>,
+    pub lower: T,
+    pub upper: T,
+}
+
+#[test]
+fn test_generic_auto_and_group_parts() {
+    let values = [3, 1, 2];
+    let mut cursor = Cursor {
+        window: &values,
+        pending: vec![],
+        lower: 0,
+        upper: 0,
+    };
+    let mut cursor_ref = cursor.into_partial_ref_mut();
+
+    let (bounds, mut cursor_ref) = cursor_ref.split_group_mut(Bounds::default());
+    let window = *cursor_ref.part(Window::default());
+    *bounds.lower = *window.iter().min().unwrap();
+    *bounds.upper = *window.iter().max().unwrap();
+    cursor_ref.part_mut(Pending::default()).push("done");
+
+    assert_eq!((cursor.lower, cursor.upper), (1, 3));
+    assert_eq!(cursor.pending, vec!["done"]);
+}
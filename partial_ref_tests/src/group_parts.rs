@@ -0,0 +1,86 @@
+use partial_ref::*;
+
+part!(pub Entries: Vec<u32>);
+
+#[derive(PartialRefTarget, Default)]
+#[part_group(Stats: hits, misses)]
+pub struct Cache {
+    #[part(Entries)]
+    pub entries: Vec<u32>,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+fn lookup(mut cache: partial!(Cache, Entries, mut Stats), value: u32) -> bool {
+    let (stats, cache) = cache.split_group_mut(Stats);
+    if cache.part(Entries).contains(&value) {
+        *stats.hits += 1;
+        true
+    } else {
+        *stats.misses += 1;
+        false
+    }
+}
+
+#[test]
+fn test_group_part() {
+    let mut cache = Cache::default();
+    let mut cache_ref = cache.into_partial_ref_mut();
+
+    cache_ref.part_mut(Entries).push(3);
+
+    assert!(lookup(cache_ref.borrow(), 3));
+    assert!(!lookup(cache_ref.borrow(), 4));
+    assert!(!lookup(cache_ref.borrow(), 5));
+
+    let stats: StatsRef = cache_ref.group(Stats);
+    assert_eq!((*stats.hits, *stats.misses), (1, 2));
+}
+
+part!(pub CachePart: Cache);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Outer<'a> {
+    #[part(CachePart)]
+    pub cache: Cache,
+    #[part(auto)]
+    pub name: &'a str,
+}
+
+#[test]
+fn test_nested_group_part() {
+    let mut outer = Outer::default();
+    let mut outer_ref = outer.into_partial_ref_mut();
+
+    let (stats, mut outer_ref) = outer_ref.split_group_mut(CachePart | Stats);
+    outer_ref.part_mut(CachePart | Entries).push(1);
+    *stats.misses += 1;
+
+    assert_eq!(outer.cache.misses, 1);
+    assert_eq!(outer.cache.entries, vec![1]);
+}
+
+#[derive(PartialRefTarget)]
+#[part_group(Span: text, end)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    #[part(auto)]
+    pub start: usize,
+    pub end: usize,
+}
+
+#[test]
+fn test_group_part_with_lifetime() {
+    let mut token = Token {
+        text: "abc",
+        start: 0,
+        end: 0,
+    };
+    let mut token_ref = token.into_partial_ref_mut();
+
+    let (span, mut token_ref) = token_ref.split_group_mut(Span);
+    *span.end = span.text.len();
+    *token_ref.part_mut(Start) = 1;
+
+    assert_eq!((token.start, token.end), (1, 3));
+}
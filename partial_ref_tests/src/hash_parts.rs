@@ -0,0 +1,48 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Query {
+    #[part(auto)]
+    pub text: String,
+    #[part(auto)]
+    pub limit: usize,
+    #[part(auto)]
+    pub elapsed: f64,
+}
+
+fn cache_key(query: partial!(Query, Text, Limit)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    query.hash_parts(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_hash_parts() {
+    let mut first = Query {
+        text: "users".to_owned(),
+        limit: 10,
+        elapsed: 0.5,
+    };
+    let mut second = Query {
+        text: "users".to_owned(),
+        limit: 10,
+        elapsed: 2.0,
+    };
+
+    let mut cache = HashMap::new();
+    cache.insert(cache_key(first.into_partial_ref_mut().borrow()), 1);
+    let key = cache_key(second.into_partial_ref_mut().borrow());
+    assert_eq!(cache.get(&key), Some(&1));
+
+    let mut expected = DefaultHasher::new();
+    "users".hash(&mut expected);
+    10usize.hash(&mut expected);
+    assert_eq!(key, expected.finish());
+
+    second.limit = 20;
+    assert_ne!(cache_key(second.into_partial_ref_mut().borrow()), key);
+}
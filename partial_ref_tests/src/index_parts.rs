@@ -0,0 +1,34 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Board {
+    #[part(auto)]
+    pub cells: Vec<u8>,
+    #[part(auto)]
+    pub moves: usize,
+    #[part(auto)]
+    pub width: usize,
+}
+
+fn play(mut board: partial!(Board, mut Cells, mut Moves, Width), x: usize, y: usize) {
+    let index = y * board[Width] + x;
+    board[Cells][index] = 1;
+    board[Moves] += 1;
+}
+
+#[test]
+fn test_index_parts() {
+    let mut board = Board {
+        cells: vec![0; 4],
+        width: 2,
+        ..Board::default()
+    };
+    let mut board_ref = board.into_partial_ref_mut();
+
+    play(board_ref.borrow(), 1, 0);
+    play(board_ref.borrow(), 0, 1);
+
+    assert_eq!(board_ref[Cells], [0, 1, 1, 0]);
+    assert_eq!(board_ref[Moves], 2);
+    assert_eq!(board.moves, 2);
+}
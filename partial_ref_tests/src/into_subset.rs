@@ -0,0 +1,50 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Editor {
+    #[part(auto)]
+    pub text: String,
+    #[part(auto)]
+    pub cursor: usize,
+    #[part(auto)]
+    pub history: Vec<String>,
+}
+
+fn insert<'a, SubsetIndex>(
+    editor: impl IntoSubset<'a, partial!('a Editor, mut Text, mut Cursor), SubsetIndex>,
+    input: &str,
+) {
+    let mut editor = editor.into_subset();
+    let (text, mut editor) = editor.split_part_mut(Text);
+    let cursor = editor.part_mut(Cursor);
+    text.insert_str(*cursor, input);
+    *cursor += input.len();
+}
+
+fn cursor<'a, SubsetIndex>(
+    editor: impl IntoSubset<'a, partial!('a Editor, Cursor), SubsetIndex>,
+) -> usize {
+    *editor.into_subset().part(Cursor)
+}
+
+#[test]
+fn test_into_subset() {
+    let mut editor = Editor::default();
+    let mut editor_ref = editor.into_partial_ref_mut();
+
+    insert(
+        editor_ref.borrow::<partial!(Editor, mut Text, mut Cursor), _>(),
+        "world",
+    );
+    insert(
+        editor_ref.borrow::<partial!(Editor, mut Cursor, mut Text, History), _>(),
+        "!",
+    );
+    assert_eq!(
+        cursor(editor_ref.borrow::<partial!(Editor, Cursor), _>()),
+        6
+    );
+    assert_eq!(cursor(editor_ref), 6);
+
+    assert_eq!(editor.text, "world!");
+}
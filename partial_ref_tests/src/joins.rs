@@ -0,0 +1,54 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Inventory {
+    #[part(auto)]
+    pub items: Vec<&'static str>,
+    #[part(auto)]
+    pub counts: Vec<usize>,
+    #[part(auto)]
+    pub capacity: usize,
+}
+
+fn add_item(
+    mut inventory: partial!(Inventory, mut Items, mut Counts, Capacity),
+    item: &'static str,
+) {
+    assert!(inventory.part(Items).len() < *inventory.part(Capacity));
+    inventory.part_mut(Items).push(item);
+    inventory.part_mut(Counts).push(1);
+}
+
+#[test]
+fn test_join() {
+    let mut inventory = Inventory {
+        capacity: 3,
+        ..Inventory::default()
+    };
+    let mut inventory_ref = inventory.into_partial_ref_mut();
+
+    let (mut items, mut rest): (partial!(Inventory, mut Items, Capacity), _) =
+        inventory_ref.split_borrow();
+    items.part_mut(Items).push("apple");
+    rest.part_mut(Counts).push(2);
+
+    let mut joined: partial!(Inventory, mut Items, mut Counts, Capacity) = items.join(rest);
+    add_item(joined.borrow(), "pear");
+
+    assert_eq!(inventory.items, ["apple", "pear"]);
+    assert_eq!(inventory.counts, [2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "cannot join partial references to different values")]
+fn test_join_different_values() {
+    let mut first = Inventory::default();
+    let mut second = Inventory::default();
+    let mut first_ref = first.into_partial_ref_mut();
+    let mut second_ref = second.into_partial_ref_mut();
+
+    let (items, _): (partial!(Inventory, mut Items), _) = first_ref.split_borrow();
+    let (_, rest): (partial!(Inventory, mut Items), _) = second_ref.split_borrow();
+
+    let _: partial!(Inventory, mut Items, mut Counts, mut Capacity) = items.join(rest);
+}
@@ -0,0 +1,93 @@
+use std::collections::{BTreeMap, HashMap};
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct World {
+    #[part(auto)]
+    pub health: HashMap<u32, i32>,
+    #[part(auto)]
+    pub names: BTreeMap<String, u32>,
+    #[part(auto)]
+    pub damage: i32,
+    #[part(auto)]
+    pub hits: usize,
+}
+
+type EntityHealth = KeyPart<Health>;
+
+fn hit(mut entity: Extended<EntityHealth, partial!(World, Damage, mut Hits)>) {
+    let damage = *entity.part(Damage);
+    *entity.part_mut(Hits) += 1;
+    *entity.extra_part_mut(EntityHealth::default()) -= damage;
+}
+
+fn attack(mut world: partial!(World, mut Health, Damage, mut Hits), attacker: u32, target: u32) {
+    let (entities, world) = world.get_disjoint_keys_part_mut(Health, [&attacker, &target]);
+    let [attacker, target] = entities.unwrap();
+    *attacker += 1;
+    hit(world.extend_with::<EntityHealth>(target));
+}
+
+#[test]
+fn test_keyed_parts() {
+    let mut world = World {
+        health: HashMap::from([(1, 10), (2, 20)]),
+        damage: 5,
+        ..World::default()
+    };
+
+    attack(world.into_partial_ref_mut().borrow(), 1, 2);
+    attack(world.into_partial_ref_mut().borrow(), 2, 1);
+
+    assert_eq!(world.health[&1], 6);
+    assert_eq!(world.health[&2], 16);
+    assert_eq!(world.hits, 2);
+}
+
+#[test]
+fn test_keyed_parts_errors() {
+    let mut world = World {
+        health: HashMap::from([(1, 10), (2, 20)]),
+        ..World::default()
+    };
+    let mut world = world.into_partial_ref_mut();
+
+    let (entities, _) = world.get_disjoint_keys_part_mut(Health, [&1, &1]);
+    assert_eq!(entities.unwrap_err(), DisjointMutError::OverlappingIndices);
+
+    let (entities, mut world) = world.get_disjoint_keys_part_mut(Health, [&1, &3]);
+    assert_eq!(entities.unwrap_err(), DisjointMutError::IndexOutOfBounds);
+
+    *world.part_mut(Hits) += 1;
+}
+
+#[test]
+fn test_keyed_parts_btree() {
+    let mut world = World {
+        names: BTreeMap::from([
+            ("a".to_owned(), 1),
+            ("b".to_owned(), 2),
+            ("c".to_owned(), 3),
+            ("d".to_owned(), 4),
+        ]),
+        ..World::default()
+    };
+    let mut world = world.into_partial_ref_mut();
+
+    let keys = ["d".to_owned(), "a".to_owned(), "c".to_owned()];
+    let (ids, _) = world.get_disjoint_keys_part_mut(Names, [&keys[0], &keys[1], &keys[2]]);
+    let [d, a, c] = ids.unwrap();
+    std::mem::swap(d, a);
+    *c += 10;
+
+    let keys = ["b".to_owned(), "e".to_owned()];
+    let (ids, _) = world.get_disjoint_keys_part_mut(Names, [&keys[0], &keys[1]]);
+    assert_eq!(ids.unwrap_err(), DisjointMutError::IndexOutOfBounds);
+
+    let names = world.part(Names);
+    assert_eq!(names["a"], 4);
+    assert_eq!(names["b"], 2);
+    assert_eq!(names["c"], 13);
+    assert_eq!(names["d"], 1);
+}
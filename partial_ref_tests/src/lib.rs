@@ -1,5 +1,170 @@
 pub mod doctest_helpers;
 
+#[cfg(test)]
+mod attribute_macro;
+#[cfg(test)]
+mod auto_parts;
+#[cfg(test)]
+mod bit_parts;
+#[cfg(test)]
+mod bounds;
+#[cfg(test)]
+mod bundled_bounds;
+#[cfg(test)]
+mod canonical_refs;
+#[cfg(test)]
+mod cfg_fields;
+#[cfg(test)]
+mod chunk_parts;
+#[cfg(test)]
+mod column_parts;
+#[cfg(test)]
+mod const_borrow;
+#[cfg(test)]
+mod const_generics;
+#[cfg(test)]
+mod const_only;
+#[cfg(test)]
+mod const_refs;
+#[cfg(test)]
+mod copied_parts;
+#[cfg(test)]
+mod copy_parts;
+#[cfg(test)]
+mod debug_refs;
+#[cfg(test)]
+mod deref_parts;
+#[cfg(test)]
+mod dyn_fields;
+#[cfg(test)]
+mod elem_parts;
+#[cfg(test)]
+mod element_parts;
+#[cfg(test)]
+mod enum_targets;
+#[cfg(test)]
+mod extended_refs;
+#[cfg(test)]
+mod flatten;
+#[cfg(test)]
+mod fold_parts;
+#[cfg(test)]
+mod from_parts;
+#[cfg(test)]
+mod frozen_refs;
+#[cfg(test)]
+mod full_refs;
+#[cfg(test)]
+mod generic_fields;
+#[cfg(test)]
+mod generic_parts;
+#[cfg(test)]
+mod group_parts;
+#[cfg(test)]
+mod hash_parts;
+#[cfg(test)]
+mod index_parts;
+#[cfg(test)]
+mod into_subset;
+#[cfg(test)]
+mod joins;
+#[cfg(test)]
+mod keyed_parts;
+#[cfg(test)]
+mod lock_parts;
+#[cfg(test)]
+mod multi_parts;
+#[cfg(test)]
+mod multi_splits;
+#[cfg(test)]
+mod nested_forms;
+#[cfg(test)]
+mod nested_variants;
+#[cfg(test)]
+mod no_nested;
+#[cfg(test)]
+mod owned_refs;
+#[cfg(test)]
+mod packed_structs;
+#[cfg(test)]
+mod pairs;
+#[cfg(test)]
+mod part_aliases;
+#[cfg(test)]
+mod part_claims;
+#[cfg(test)]
+mod part_lifetimes;
+#[cfg(test)]
+mod part_metadata;
+#[cfg(test)]
+mod part_offsets;
+#[cfg(test)]
+mod part_sets;
+#[cfg(test)]
+mod part_tokens;
+#[cfg(test)]
+mod parts_eq;
+#[cfg(test)]
+mod parts_mod;
+#[cfg(test)]
+mod parts_trait;
+#[cfg(test)]
+mod pinned_fields;
+#[cfg(test)]
+mod range_parts;
+#[cfg(test)]
+mod raw_refs;
+#[cfg(test)]
+mod raw_target;
+#[cfg(test)]
+mod reborrow;
+#[cfg(test)]
+mod ref_cell_parts;
+#[cfg(test)]
+mod remainders;
+#[cfg(test)]
+mod reset_parts;
+#[cfg(test)]
+mod same_parts;
+#[cfg(test)]
+mod scoped_parts;
+#[cfg(test)]
+mod serde_parts;
+#[cfg(test)]
+mod slice_parts;
+#[cfg(test)]
+mod smart_pointers;
+#[cfg(test)]
+mod split_helpers;
+#[cfg(test)]
+mod static_targets;
+#[cfg(test)]
+mod swap_parts;
+#[cfg(test)]
+mod tagged_parts;
+#[cfg(test)]
+mod thread_local_parts;
+#[cfg(test)]
+mod transparent;
+#[cfg(test)]
+mod try_parts;
+#[cfg(test)]
+mod tuple_structs;
+#[cfg(test)]
+mod typed_access;
+#[cfg(test)]
+mod typestate_parts;
+#[cfg(test)]
+mod union_targets;
+#[cfg(test)]
+mod unsized_fields;
+#[cfg(test)]
+mod views;
+#[cfg(test)]
+mod virtual_parts;
+#[cfg(test)]
+mod visit_parts;
+
 pub mod isolated {
     // This module imports just the minimum required to partially test macro hygiene
     use partial_ref::{part, PartialRefTarget};
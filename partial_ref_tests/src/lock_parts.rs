@@ -0,0 +1,47 @@
+use std::sync::{Mutex, RwLock};
+
+use partial_ref::*;
+
+part!(pub lock LockedRequests: Mutex<Vec<String>>);
+part!(pub lock Config: RwLock<String>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Server {
+    #[part(LockedRequests)]
+    pub requests: Mutex<Vec<String>>,
+    #[part(Config)]
+    pub config: RwLock<String>,
+    #[part(auto)]
+    pub handled: usize,
+}
+
+fn handle(mut server: partial!(Server, mut LockedRequests, Config, mut Handled), path: &str) {
+    let (requests, mut server) = server.split_lock_part_mut(LockedRequests);
+    let config = server.lock_part(Config).unwrap();
+    requests.unwrap().push(format!("{}{}", config, path));
+    drop(config);
+    *server.part_mut(Handled) += 1;
+}
+
+fn reconfigure(mut server: partial!(Server, mut Config), prefix: &str) {
+    *server.lock_part_mut(Config).unwrap() = prefix.to_owned();
+}
+
+#[test]
+fn test_lock_parts() {
+    let mut server = Server::default();
+    let mut server_ref = server.into_partial_ref_mut();
+
+    reconfigure(server_ref.borrow(), "/api");
+    handle(server_ref.borrow(), "/users");
+    handle(server_ref.borrow(), "/groups");
+
+    {
+        let (requests, server_ref) = server_ref.split_lock_part_mut(LockedRequests);
+        let config = server_ref.lock_part(Config).unwrap();
+        assert_eq!(*config, "/api");
+        assert_eq!(*requests.unwrap(), ["/api/users", "/api/groups"]);
+    }
+
+    assert_eq!(server.handled, 2);
+}
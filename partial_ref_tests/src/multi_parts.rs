@@ -0,0 +1,37 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Graph {
+    #[part(auto)]
+    pub neighbors: Vec<Vec<usize>>,
+    #[part(auto)]
+    pub colors: Vec<usize>,
+    #[part(auto)]
+    pub weights: Vec<f32>,
+}
+
+fn recolor(mut g: partial!(Graph, mut Neighbors, mut Colors, Weights)) {
+    let (colors, weights, neighbors) = g.parts((MutPart(Colors), Weights, MutPart(Neighbors)));
+
+    for (node, edges) in neighbors.iter_mut().enumerate() {
+        edges.retain(|&neighbor| weights[neighbor] > 0.0);
+        colors[node] = edges.len();
+    }
+}
+
+#[test]
+fn test_parts() {
+    let mut g = Graph {
+        neighbors: vec![vec![1, 2], vec![0], vec![0]],
+        colors: vec![0; 3],
+        weights: vec![1.0, 0.0, 1.0],
+    };
+    let mut g_ref = g.into_partial_ref_mut();
+
+    recolor(g_ref.borrow());
+
+    let (colors, colors_again) = g_ref.parts((Colors, Colors));
+    assert_eq!(colors, colors_again);
+    assert_eq!(g.colors, [1, 1, 1]);
+    assert_eq!(g.neighbors, [vec![2], vec![0], vec![0]]);
+}
@@ -0,0 +1,51 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Solver {
+    #[part(auto)]
+    pub clauses: Vec<Vec<i32>>,
+    #[part(auto)]
+    pub assignment: Vec<bool>,
+    #[part(auto)]
+    pub trail: Vec<i32>,
+    #[part(auto)]
+    pub conflicts: usize,
+}
+
+fn assign(mut solver: partial!(Solver, mut Assignment, mut Trail), literal: i32) {
+    solver.part_mut(Assignment)[literal.unsigned_abs() as usize] = literal > 0;
+    solver.part_mut(Trail).push(literal);
+}
+
+#[test]
+fn test_split_n() {
+    let mut solver = Solver {
+        clauses: vec![vec![1, -2], vec![2]],
+        assignment: vec![false; 3],
+        ..Solver::default()
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    let (clauses, mut propagation, mut stats) = solver_ref.split_n::<(
+        partial!(Solver, Clauses),
+        partial!(Solver, mut Assignment, mut Trail),
+    ), _>();
+    for clause in clauses.part(Clauses) {
+        if clause.len() == 1 {
+            assign(propagation.borrow(), clause[0]);
+        } else {
+            *stats.part_mut(Conflicts) += 1;
+        }
+    }
+    assert_eq!(stats.part(Clauses).len(), 2);
+
+    let (mut trail, assignment, conflicts, _rest) = solver_ref.split_n::<(
+        partial!(Solver, mut Trail),
+        partial!(Solver, Assignment),
+        partial!(Solver, mut Conflicts),
+    ), _>();
+    trail.part_mut(Trail).push(-1);
+    assert_eq!(assignment.part(Assignment), &[false, false, true]);
+    assert_eq!(*conflicts.part(Conflicts), 1);
+    assert_eq!(solver.trail, [2, -1]);
+}
@@ -0,0 +1,61 @@
+use partial_ref::*;
+
+part!(pub LeafPart: Leaf);
+part!(pub MidPart: Mid);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Leaf {
+    #[part(auto)]
+    pub value: u32,
+    #[part(auto)]
+    pub other: u32,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Mid {
+    #[part(LeafPart)]
+    pub leaf: Leaf,
+    #[part(auto)]
+    pub count: u32,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Top {
+    #[part(MidPart)]
+    pub mid: Mid,
+    #[part(auto)]
+    pub total: u32,
+}
+
+type LeafValue = Nested<LeafPart, Value>;
+
+fn increment_left(mut top: partial!(Top, mut MidPart | LeafPart | Value, Total)) {
+    *top.part_mut(MidPart | LeafPart | Value) += *top.part(Total);
+}
+
+fn increment_right(mut top: partial!(Top, mut Nested<MidPart, LeafValue>, mut Total)) {
+    *top.part_mut(Total) += 1;
+    *top.part_mut(Nested::<MidPart, LeafValue>::default()) += 1;
+}
+
+#[test]
+fn test_reassociate() {
+    let mut top = Top::default();
+    let mut top_ref = top.into_partial_ref_mut();
+
+    increment_right(top_ref.borrow_reassociated());
+
+    let mut left_ref: partial!(Top, mut MidPart | LeafPart | Value, mut Total, MidPart | Count) =
+        top_ref.borrow();
+    increment_right(left_ref.borrow_reassociated());
+
+    let mut right_ref: partial!(Top, mut Nested<MidPart, LeafValue>, Total) =
+        left_ref.borrow_reassociated();
+    increment_left(right_ref.borrow_reassociated());
+
+    let left_ref: partial!(Top, mut MidPart | LeafPart | Value) = right_ref.reassociate();
+    assert_eq!(*left_ref.part(MidPart | LeafPart | Value), 4);
+
+    assert_eq!(top.mid.leaf.value, 4);
+    assert_eq!(top.total, 2);
+}
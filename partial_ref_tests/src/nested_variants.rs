@@ -0,0 +1,103 @@
+use partial_ref::*;
+
+part!(pub Idle);
+part!(pub Connected);
+part!(pub Address in Connected: String);
+part!(pub Sent in Connected: usize);
+part!(pub Retries in Idle: u32);
+
+#[derive(PartialRefTarget)]
+#[repr(u8)]
+pub enum Connection {
+    #[part(Idle)]
+    Idle {
+        #[part(Retries)]
+        retries: u32,
+    },
+    #[part(Connected)]
+    Connected {
+        #[part(Address)]
+        address: String,
+        #[part(Sent)]
+        sent: usize,
+    },
+}
+
+#[derive(PartialRefTarget)]
+pub struct Client {
+    #[part(auto)]
+    pub primary: Connection,
+    #[part(auto)]
+    pub backup: Connection,
+    #[part(auto)]
+    pub log: Vec<String>,
+}
+
+fn send(
+    mut client: partial!(Client, Primary | Connected, Primary | Address, mut Primary | Sent, mut Log),
+) -> bool {
+    if let Ok(connected) = client.reborrow().witness(Primary | Connected) {
+        let (sent, connected) = connected.split_part_mut(Primary | Sent);
+        *sent += 1;
+        let (address, connected) = connected.split_part(Primary | Address);
+        let message = format!("sent {} to {}", sent, address);
+        connected.into_inner().part_mut(Log).push(message);
+        true
+    } else {
+        false
+    }
+}
+
+fn retry(client: partial!(Client, Backup | Idle, mut Backup | Retries)) {
+    if let Ok(mut idle) = client.witness(Backup | Idle) {
+        *idle.part_mut(Backup | Retries) += 1;
+    }
+}
+
+#[test]
+fn test_nested_variants() {
+    let mut client = Client {
+        primary: Connection::Connected {
+            address: "primary".to_owned(),
+            sent: 0,
+        },
+        backup: Connection::Idle { retries: 0 },
+        log: vec![],
+    };
+    let mut client_ref = client.into_partial_ref_mut();
+
+    assert!(client_ref.reborrow().witness(Primary | Idle).is_err());
+    assert!(client_ref.reborrow().witness(Backup | Connected).is_err());
+
+    assert!(send(client_ref.borrow()));
+    assert!(send(client_ref.borrow()));
+    retry(client_ref.borrow());
+
+    assert_eq!(client.log, ["sent 1 to primary", "sent 2 to primary"]);
+    match client.backup {
+        Connection::Idle { retries } => assert_eq!(retries, 1),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_inactive_nested_variant() {
+    let mut client = Client {
+        primary: Connection::Idle { retries: 3 },
+        backup: Connection::Connected {
+            address: "backup".to_owned(),
+            sent: 0,
+        },
+        log: vec![],
+    };
+    let mut client_ref = client.into_partial_ref_mut();
+
+    assert!(!send(client_ref.borrow()));
+    retry(client_ref.borrow());
+
+    assert!(client.log.is_empty());
+    match client.backup {
+        Connection::Connected { sent, .. } => assert_eq!(sent, 0),
+        _ => unreachable!(),
+    }
+}
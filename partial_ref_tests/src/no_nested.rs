@@ -0,0 +1,41 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(no_nested)]
+pub struct Totals {
+    #[part(auto)]
+    pub values: Vec<u32>,
+    #[part(auto)]
+    pub total: u32,
+}
+
+part!(pub TotalsPart: Totals);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Outer {
+    #[part(TotalsPart)]
+    pub totals: Totals,
+}
+
+fn add(mut inner: partial!(Totals, mut Values, mut Total), value: u32) {
+    inner.part_mut(Values).push(value);
+    *inner.part_mut(Total) += value;
+}
+
+#[test]
+fn test_no_nested() {
+    let mut outer = Outer::default();
+    add(outer.totals.into_partial_ref_mut().borrow(), 2);
+
+    let mut outer_ref = outer.into_partial_ref_mut();
+    add(
+        outer_ref
+            .part_mut(TotalsPart)
+            .into_partial_ref_mut()
+            .borrow(),
+        3,
+    );
+
+    assert_eq!(outer.totals.values, vec![2, 3]);
+    assert_eq!(outer.totals.total, 5);
+}
@@ -0,0 +1,41 @@
+use std::thread;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Job {
+    #[part(auto)]
+    pub input: Vec<u32>,
+    #[part(auto)]
+    pub output: u32,
+    #[part(auto)]
+    pub attempts: usize,
+}
+
+type SumParts = part_set!(Input, mut Output);
+
+fn sum(mut job: partial!(Job, Input, mut Output)) {
+    *job.part_mut(Output) = job.part(Input).iter().sum();
+}
+
+#[test]
+fn test_owned_partial_ref() {
+    let job = Box::new(Job {
+        input: vec![1, 2, 3],
+        ..Job::default()
+    });
+    let mut owned: OwnedPartialRef<Box<Job>, SumParts> = OwnedPartialRef::new(job);
+
+    let mut owned = thread::spawn(move || {
+        sum(owned.as_partial_ref_mut());
+        owned
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*owned.as_partial_ref().part(Output), 6);
+    *owned.as_partial_ref_mut().part_mut(Output) = 1;
+    let job = owned.into_inner();
+    assert_eq!(job.output, 1);
+    assert_eq!(job.attempts, 0);
+}
@@ -0,0 +1,74 @@
+use partial_ref::*;
+
+part!(pub unaligned Kind: u8);
+
+#[derive(PartialRefTarget, Default)]
+#[repr(C, packed)]
+pub struct Header {
+    #[part(Kind)]
+    pub kind: u8,
+    #[part(auto)]
+    pub length: u32,
+    #[part(auto)]
+    pub checksum: u16,
+    #[part(auto)]
+    pub name: String,
+}
+
+#[derive(PartialRefTarget, Default)]
+#[repr(C, packed(2))]
+pub struct Pair {
+    #[part(auto)]
+    pub first: u8,
+    #[part(auto)]
+    pub second: u64,
+}
+
+fn append(mut header: partial!(Header, mut Length, mut Checksum, Kind), byte: u8) {
+    let (kind, mut header) = header.split_unaligned_part(Kind);
+    let (mut length, mut header) = header.split_unaligned_part_mut(Length);
+    length.update(|length| *length += 1);
+    header
+        .unaligned_part_mut(Checksum)
+        .update(|checksum| *checksum += (byte ^ kind.get()) as u16);
+}
+
+#[test]
+fn test_packed_parts() {
+    let mut header = Header {
+        kind: 3,
+        ..Header::default()
+    };
+    let mut header_ref = header.into_partial_ref_mut();
+
+    append(header_ref.borrow(), 1);
+    append(header_ref.borrow(), 2);
+
+    assert_eq!(header_ref.unaligned_part(Length).get(), 2);
+    assert_eq!(header_ref.unaligned_part(Checksum).get(), 2 + 1);
+
+    let previous = header_ref
+        .unaligned_part_mut(Name)
+        .replace("header".to_owned());
+    assert_eq!(previous, "");
+    header_ref.unaligned_part_mut(Kind).set(7);
+
+    assert_eq!(<Header as PartOffset<Length>>::OFFSET, 1);
+    assert_eq!({ header.kind }, 7);
+    assert_eq!({ header.length }, 2);
+    let Header { name, .. } = header;
+    assert_eq!(name, "header");
+}
+
+#[test]
+fn test_packed_alignment() {
+    let mut pair = Pair::default();
+    let mut pair_ref = pair.into_partial_ref_mut();
+
+    let (mut first, mut pair_ref) = pair_ref.split_unaligned_part_mut(First);
+    first.set(1);
+    pair_ref.unaligned_part_mut(Second).set(u64::MAX);
+
+    assert_eq!(<Pair as PartOffset<Second>>::OFFSET, 2);
+    assert_eq!({ pair.second }, u64::MAX);
+}
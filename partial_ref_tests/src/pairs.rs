@@ -0,0 +1,62 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct World {
+    #[part(auto)]
+    pub positions: Vec<i32>,
+    #[part(auto)]
+    pub velocities: Vec<i32>,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Stats {
+    #[part(auto)]
+    pub steps: usize,
+    #[part(auto)]
+    pub moved: usize,
+}
+
+type StepRef<'a> = PartialPair<
+    partial!('a World, mut Positions, Velocities),
+    partial!('a Stats, mut Steps, mut Moved),
+>;
+
+type CountRef<'a> = PartialPair<partial!('a World, Velocities), partial!('a Stats, mut Moved)>;
+
+fn count(mut pair: CountRef) {
+    let moved = pair
+        .part(Left(Velocities))
+        .iter()
+        .filter(|&&v| v != 0)
+        .count();
+    *pair.part_mut(Right(Moved)) += moved;
+}
+
+fn step(mut pair: StepRef) {
+    let (mut velocities, mut rest): (CountRef, _) = pair.split_borrow();
+    count(velocities.borrow());
+
+    *rest.part_mut(Right(Steps)) += 1;
+    let velocities = velocities.part(Left(Velocities));
+    for (position, velocity) in rest.part_mut(Left(Positions)).iter_mut().zip(velocities) {
+        *position += velocity;
+    }
+}
+
+#[test]
+fn test_pairs() {
+    let mut world = World {
+        positions: vec![0, 0],
+        velocities: vec![1, 0],
+    };
+    let mut stats = Stats::default();
+
+    let mut pair = PartialPair::new(world.into_partial_ref_mut(), stats.into_partial_ref_mut());
+    step(pair.borrow());
+    step(pair.borrow());
+    assert_eq!(pair.part(Right(Steps)), &2);
+    assert_eq!(pair.left().part(Positions), &[2, 0]);
+
+    assert_eq!(stats.moved, 2);
+    assert_eq!(world.positions, [2, 0]);
+}
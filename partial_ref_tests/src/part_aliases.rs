@@ -0,0 +1,42 @@
+use partial_ref::*;
+
+part!(pub StatsOwner);
+part!(pub Counts: Vec<usize>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Tracker {
+    #[part(Counts)]
+    #[part(StatsOwner)]
+    pub counts: Vec<usize>,
+    #[part(auto)]
+    #[part(StatsOwner)]
+    pub total: usize,
+}
+
+fn reset(mut tracker: partial!(Tracker, mut Counts, mut Total, mut StatsOwner)) {
+    tracker.part_mut(Counts).clear();
+    *tracker.part_mut(Total) = 0;
+}
+
+fn count(mut tracker: partial!(Tracker, mut Counts, mut Total), index: usize) {
+    let (counts, mut tracker) = tracker.split_part_mut(Counts);
+    if counts.len() <= index {
+        counts.resize(index + 1, 0);
+    }
+    counts[index] += 1;
+    *tracker.part_mut(Total) += 1;
+}
+
+#[test]
+fn test_part_aliases() {
+    let mut tracker = Tracker::default();
+    let mut tracker_ref = tracker.into_partial_ref_mut();
+
+    count(tracker_ref.borrow(), 2);
+    count(tracker_ref.borrow(), 2);
+    assert_eq!(tracker_ref.part(Counts), &[0, 0, 2]);
+    assert_eq!(*tracker_ref.part(Total), 2);
+
+    reset(tracker_ref.borrow());
+    assert!(tracker_ref.part(Counts).is_empty());
+}
@@ -0,0 +1,54 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Scheduler {
+    #[part(auto)]
+    pub tasks: Vec<u32>,
+    #[part(auto)]
+    pub log: Vec<u32>,
+}
+
+type TaskHandle = RawPartialRef<partial!('static Scheduler, mut Tasks, Log)>;
+type LogHandle = RawPartialRef<partial!('static Scheduler, mut Log)>;
+
+#[test]
+fn test_part_claims() {
+    let mut scheduler = Scheduler::default();
+    let mut scheduler_ref = scheduler.into_partial_ref_mut();
+    let (tasks_ref, log_ref): (partial!(Scheduler, mut Tasks), _) = scheduler_ref.split_borrow();
+
+    let tasks_claim = PartClaim::of(&tasks_ref);
+    let log_claim = PartClaim::of(&log_ref);
+    drop((tasks_claim, log_claim));
+
+    let task_handle =
+        TaskHandle::new(scheduler_ref.borrow::<partial!(Scheduler, mut Tasks, Log), _>());
+    let claim = task_handle.claim();
+    let shared_claim = PartClaim::of(&scheduler_ref.borrow::<partial!(Scheduler, Log), _>());
+    unsafe { task_handle.revive() }.part_mut(Tasks).push(1);
+    drop((claim, shared_claim));
+
+    let log_handle = LogHandle::new(scheduler_ref.borrow::<partial!(Scheduler, mut Log), _>());
+    let _claim = log_handle.claim();
+    unsafe { log_handle.revive() }.part_mut(Log).push(2);
+    drop(_claim);
+
+    assert_eq!(scheduler.tasks, [1]);
+    assert_eq!(scheduler.log, [2]);
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "conflicting claims for part `partial_ref_tests::part_claims::Log`")
+)]
+fn test_conflicting_part_claims() {
+    let mut scheduler = Scheduler::default();
+    let mut scheduler_ref = scheduler.into_partial_ref_mut();
+
+    let task_handle =
+        TaskHandle::new(scheduler_ref.borrow::<partial!(Scheduler, mut Tasks, Log), _>());
+    let log_handle = LogHandle::new(scheduler_ref.borrow::<partial!(Scheduler, mut Log), _>());
+    let _task_claim = task_handle.claim();
+    let _log_claim = log_handle.claim();
+}
@@ -0,0 +1,43 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Meter {
+    #[part(auto)]
+    pub samples: Vec<u32>,
+    #[part(auto)]
+    pub total: u32,
+}
+
+fn first_sample<'a, 'b, Reference, PartIndex>(meter: &'b Reference) -> Option<&'b u32>
+where
+    'a: 'b,
+    Reference: PartialRef<'a, Target = Meter> + PluckConst<'a, Samples, PartIndex>,
+{
+    meter.part(Samples).first()
+}
+
+fn accumulate<'a, Reference, SamplesIndex, TotalIndex>(meter: &mut Reference) -> u32
+where
+    Reference: PartialRef<'a, Target = Meter>
+        + PluckConst<'a, Samples, SamplesIndex>
+        + PluckMut<'a, Total, TotalIndex>,
+{
+    let first = first_sample(meter).copied().unwrap_or(0);
+    let sum: u32 = meter.part(Samples).iter().sum();
+    *meter.part_mut(Total) += sum - first;
+    let total = meter.part_mut(Total);
+    *total += first;
+    *total
+}
+
+#[test]
+fn test_part_lifetimes() {
+    let mut meter = Meter {
+        samples: vec![1, 2, 3],
+        ..Meter::default()
+    };
+    let mut meter_ref = meter.into_partial_ref_mut();
+
+    assert_eq!(accumulate(&mut meter_ref), 6);
+    assert_eq!(accumulate(&mut meter_ref), 12);
+}
@@ -0,0 +1,92 @@
+use partial_ref::*;
+
+part!(pub Owner);
+part!(pub Items<T>: Vec<T>);
+
+#[derive(PartialRefTarget, Default)]
+#[part(Owner)]
+#[part_group(Stats: hits, misses)]
+pub struct Cache<T> {
+    #[part(Items<T>)]
+    pub items: Vec<T>,
+    #[part(auto)]
+    pub r#type: u8,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+#[test]
+fn test_part_metadata() {
+    assert_eq!(
+        <Cache<u32> as PartMetadata>::PARTS,
+        &[
+            PartInfo {
+                name: "Owner",
+                kind: PartKind::Abstract,
+                field: None,
+            },
+            PartInfo {
+                name: "Items<T>",
+                kind: PartKind::Field,
+                field: Some("items"),
+            },
+            PartInfo {
+                name: "Type",
+                kind: PartKind::Field,
+                field: Some("type"),
+            },
+            PartInfo {
+                name: "Stats<T>",
+                kind: PartKind::Group,
+                field: None,
+            },
+        ]
+    );
+    assert_eq!(
+        <Cache<u32> as PartMetadata>::PART_NAMES,
+        &["Owner", "Items<T>", "Type", "Stats<T>"]
+    );
+    assert_eq!(<Cache<u32> as PartMetadata>::PART_COUNT, 4);
+}
+
+#[test]
+fn test_held_parts() {
+    type Reader<'a> = partial!('a Cache<u32>, Items<u32>, Owner);
+    type Writer<'a> = partial!('a Cache<u32>, mut Items<u32>, Type);
+    type Counter<'a> = partial!('a Cache<u32>, mut Stats<u32>, Type);
+
+    assert_eq!(
+        Reader::held_parts(),
+        &[
+            HeldPart {
+                index: 1,
+                mutable: false,
+            },
+            HeldPart {
+                index: 0,
+                mutable: false,
+            },
+        ]
+    );
+    assert_eq!(
+        Writer::held_parts(),
+        &[
+            HeldPart {
+                index: 1,
+                mutable: true,
+            },
+            HeldPart {
+                index: 2,
+                mutable: false,
+            },
+        ]
+    );
+    assert_eq!(
+        <Cache<u32> as PartMetadata>::PARTS[Counter::held_parts()[0].index].name,
+        "Stats<T>"
+    );
+
+    assert!(Reader::conflicts_with::<Writer>());
+    assert!(!Writer::conflicts_with::<Counter>());
+    assert!(!Reader::conflicts_with::<Counter>());
+}
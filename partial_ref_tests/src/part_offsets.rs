@@ -0,0 +1,77 @@
+use std::mem::offset_of;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+#[repr(C)]
+pub struct Header {
+    #[part(auto)]
+    pub tag: u8,
+    #[part(auto)]
+    pub len: u32,
+    #[part(elements(Word))]
+    pub words: [u16; 3],
+}
+
+#[derive(PartialRefTarget)]
+pub struct Pair<T>(#[part(First<T>)] pub T, #[part(Second<T>)] pub T);
+
+part!(pub First<T>: T);
+part!(pub Second<T>: T);
+
+fn offset_of_part<Target: PartOffset<SomePart>, SomePart: Part>(_part: SomePart) -> usize {
+    Target::OFFSET
+}
+
+fn read_len(header: partial!(Header, Len)) -> u32 {
+    let raw = header.get_raw() as *const u8;
+    unsafe { *(raw.add(<Header as PartOffset<Len>>::OFFSET) as *const u32) }
+}
+
+#[test]
+fn test_part_offsets() {
+    assert_eq!(offset_of_part::<Header, _>(Tag), 0);
+    assert_eq!(offset_of_part::<Header, _>(Len), offset_of!(Header, len));
+    assert_eq!(
+        offset_of_part::<Header, _>(Word::<2>::default()),
+        offset_of!(Header, words) + 4
+    );
+    assert_eq!(
+        offset_of_part::<Pair<u64>, _>(Second::default()),
+        offset_of!(Pair<u64>, 1)
+    );
+
+    let mut header = Header {
+        len: 7,
+        ..Header::default()
+    };
+    let mut header_ref = header.into_partial_ref_mut();
+    assert_eq!(read_len(header_ref.borrow()), 7);
+}
+
+unsafe fn fill(words: *mut u16, len: usize, value: u16) {
+    for index in 0..len {
+        *words.add(index) = value;
+    }
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Buffer {
+    #[part(auto)]
+    pub data: [u16; 4],
+    #[part(auto)]
+    pub used: usize,
+}
+
+#[test]
+fn test_part_ptr() {
+    let mut buffer = Buffer::default();
+    let mut buffer_ref = buffer.into_partial_ref_mut();
+
+    let data = buffer_ref.part_ptr_mut(Data);
+    *buffer_ref.part_mut(Used) = 3;
+    unsafe { fill(data.cast::<u16>(), *buffer_ref.part(Used), 9) };
+    assert_eq!(unsafe { *buffer_ref.part_ptr(Used) }, 3);
+
+    assert_eq!(buffer.data, [9, 9, 9, 0]);
+}
@@ -0,0 +1,58 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Mesh {
+    #[part(auto)]
+    pub vertices: Vec<[f32; 2]>,
+    #[part(auto)]
+    pub labels: Vec<&'static str>,
+    #[part(auto)]
+    pub scale: f32,
+}
+
+type Scaling = part_set!(mut Vertices, Scale);
+type Labeling = part_set!(mut Labels, Vertices, Scale);
+
+fn scale(mut mesh: PartialRefFor<Mesh, Scaling>) {
+    let factor = *mesh.part(Scale);
+    for vertex in mesh.part_mut(Vertices).iter_mut() {
+        vertex[0] *= factor;
+        vertex[1] *= factor;
+    }
+}
+
+fn part_count<Reference: PartsOf>(_reference: &Reference) -> usize {
+    Reference::Parts::LEN
+}
+
+fn read_only<'a, Parts, SubsetIndex>(
+    mesh: &'a mut PartialRefFor<'a, Mesh, Parts>,
+) -> PartialRefFor<'a, Mesh, Parts::ConstSet>
+where
+    Parts: PartSetRef<'a, Mesh>,
+    Parts::ConstSet: PartSetRef<'a, Mesh>,
+    PartialRefFor<'a, Mesh, Parts>:
+        HasSubset<'a, PartialRefFor<'a, Mesh, Parts::ConstSet>, SubsetIndex>,
+{
+    mesh.borrow()
+}
+
+#[test]
+fn test_part_sets() {
+    let mut mesh = Mesh {
+        vertices: vec![[1.0, 2.0], [3.0, 0.5]],
+        scale: 2.0,
+        ..Mesh::default()
+    };
+    let mut mesh_ref = mesh.into_partial_ref_mut();
+
+    let scaling: partial!(Mesh, mut Vertices, Scale) = mesh_ref.borrow();
+    assert_eq!(part_count(&scaling), 2);
+    scale(scaling);
+
+    let mut labeled: PartialRefFor<Mesh, Labeling> = mesh_ref.borrow();
+    let labeled = read_only::<Labeling, _>(&mut labeled);
+    let _: partial!(Mesh, Labels, Vertices, Scale) = labeled;
+    assert_eq!(labeled.part(Vertices), &[[2.0, 4.0], [6.0, 1.0]]);
+    assert_eq!(part_count(&labeled), 3);
+}
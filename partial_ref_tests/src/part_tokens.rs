@@ -0,0 +1,42 @@
+use partial_ref::*;
+
+part_token!(pub Gpu);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Scene {
+    #[part(auto)]
+    pub meshes: Vec<u32>,
+    #[part(auto)]
+    pub uploaded: usize,
+}
+
+fn upload(mut scene: partial!(Scene, mut TokenPart<Gpu>, Meshes, mut Uploaded)) {
+    *scene.part_mut(Uploaded) = scene.part(Meshes).len();
+}
+
+fn count_meshes(scene: partial!(Scene, TokenPart<Gpu>, Meshes)) -> usize {
+    scene.part(Meshes).len()
+}
+
+#[test]
+fn test_part_tokens() {
+    let mut gpu = Gpu::take().unwrap();
+    assert!(Gpu::take().is_none());
+
+    let mut scene = Scene {
+        meshes: vec![1, 2, 3],
+        ..Scene::default()
+    };
+    let scene_ref = scene.into_partial_ref_mut();
+    let mut scene_ref = scene_ref.attach_token(&mut gpu);
+
+    upload(scene_ref.borrow());
+    assert_eq!(count_meshes(scene_ref.borrow()), 3);
+    assert_eq!(*scene_ref.part(Uploaded), 3);
+
+    let scene_ref = scene.into_partial_ref();
+    let mut first_ref = scene_ref.attach_token_const(&gpu);
+    let mut second_ref = scene_ref.attach_token_const(&gpu);
+    assert_eq!(count_meshes(first_ref.borrow()), 3);
+    assert_eq!(count_meshes(second_ref.borrow()), 3);
+}
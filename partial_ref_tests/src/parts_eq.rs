@@ -0,0 +1,57 @@
+use partial_ref::*;
+
+part!(pub Inputs: Vec<i64>);
+part!(pub Options: &'static str);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Build {
+    #[part(Inputs)]
+    pub inputs: Vec<i64>,
+    #[part(Options)]
+    pub options: &'static str,
+    #[part(auto)]
+    pub output: Option<i64>,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct BuildKey {
+    #[part(Options)]
+    pub options: &'static str,
+    #[part(Inputs)]
+    pub inputs: Vec<i64>,
+}
+
+type BuildInputs<'a> = partial!('a Build, Inputs, Options);
+
+fn needs_rebuild(current: BuildInputs, previous: &Build) -> bool {
+    !current.parts_eq(&previous.into_partial_ref())
+}
+
+#[test]
+fn test_parts_eq() {
+    let mut current = Build {
+        inputs: vec![1, 2],
+        options: "release",
+        output: None,
+    };
+    let previous = Build {
+        inputs: vec![1, 2],
+        options: "release",
+        output: Some(3),
+    };
+    let mut current_ref = current.into_partial_ref_mut();
+
+    assert!(!needs_rebuild(current_ref.borrow(), &previous));
+    assert!(!current_ref.parts_eq(&previous.into_partial_ref()));
+
+    current_ref.part_mut(Inputs).push(3);
+    assert!(needs_rebuild(current_ref.borrow(), &previous));
+
+    let key = BuildKey {
+        options: "release",
+        inputs: vec![1, 2, 3],
+    };
+    let options_ref: partial!(Build, mut Options) = current_ref.borrow();
+    assert!(options_ref.parts_eq(&key.into_partial_ref()));
+    assert!(key.into_partial_ref().parts_eq(&current_ref));
+}
@@ -0,0 +1,45 @@
+mod solver {
+    use partial_ref::*;
+
+    #[derive(PartialRefTarget, Default)]
+    #[partial_ref(parts_mod = "parts", doc_hidden)]
+    #[part(auto)]
+    #[part_group(Stats: conflicts, decisions)]
+    pub(super) struct Solver {
+        pub clauses: Vec<u32>,
+        pub(super) trail: Vec<u32>,
+        pub conflicts: usize,
+        pub decisions: usize,
+    }
+
+    pub(super) fn decide(
+        mut solver: partial!(Solver, parts::Clauses, mut parts::Stats),
+        literal: u32,
+    ) {
+        let (stats, solver) = solver.split_group_mut(parts::Stats);
+        if solver.part(parts::Clauses).contains(&literal) {
+            *stats.conflicts += 1;
+        }
+        *stats.decisions += 1;
+    }
+}
+
+use self::solver::parts::*;
+use self::solver::*;
+use partial_ref::*;
+
+#[test]
+fn test_parts_mod() {
+    let mut solver = Solver::default();
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    solver_ref.part_mut(Clauses).push(1);
+    decide(solver_ref.borrow(), 1);
+    decide(solver_ref.borrow(), 2);
+
+    let stats: StatsRef = solver_ref.group(Stats);
+    assert_eq!((*stats.conflicts, *stats.decisions), (1, 2));
+
+    solver_ref.part_mut(Trail).push(3);
+    assert_eq!(solver.trail, vec![3]);
+}
@@ -0,0 +1,60 @@
+use partial_ref::*;
+
+part!(pub Counter: usize);
+part!(pub Log: Vec<String>);
+part!(pub Items<T>: Vec<T>);
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(parts_trait = "ContextParts")]
+pub struct Context {
+    #[part(Counter)]
+    pub counter: usize,
+    #[part(Log)]
+    pub log: Vec<String>,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct TestContext {
+    #[part(Log)]
+    pub log: Vec<String>,
+    #[part(Counter)]
+    pub counter: usize,
+    pub calls: usize,
+}
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(parts_trait = "QueueParts")]
+pub struct Queue<T> {
+    #[part(Items<T>)]
+    pub items: Vec<T>,
+    #[part(Counter)]
+    pub counter: usize,
+}
+
+fn step<Target: ContextParts>(mut context: partial!(Target, mut Counter, mut Log)) {
+    let (counter, mut context) = context.split_part_mut(Counter);
+    *counter += 1;
+    context.part_mut(Log).push(format!("step {}", counter));
+}
+
+fn push<T, Target: QueueParts<T>>(mut queue: partial!(Target, mut Items<T>, mut Counter), item: T) {
+    queue.part_mut(Items::default()).push(item);
+    *queue.part_mut(Counter) += 1;
+}
+
+#[test]
+fn test_parts_trait() {
+    let mut context = Context::default();
+    step(context.into_partial_ref_mut().borrow());
+    step(context.into_partial_ref_mut().borrow());
+    assert_eq!(context.counter, 2);
+
+    let mut test_context = TestContext::default();
+    step(test_context.into_partial_ref_mut().borrow());
+    assert_eq!(test_context.log, vec!["step 1"]);
+
+    let mut queue = Queue::default();
+    push(queue.into_partial_ref_mut().borrow(), 'x');
+    assert_eq!(queue.items, vec!['x']);
+    assert_eq!(queue.counter, 1);
+}
@@ -0,0 +1,60 @@
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+use partial_ref::*;
+
+#[derive(Default)]
+pub struct SelfAware {
+    pub address: usize,
+    pub moves: usize,
+    _pinned: PhantomPinned,
+}
+
+impl SelfAware {
+    fn update(self: Pin<&mut Self>) {
+        let this = unsafe { self.get_unchecked_mut() };
+        let address = this as *mut Self as usize;
+        if this.address != address {
+            this.address = address;
+            this.moves += 1;
+        }
+    }
+}
+
+part!(pub pin Watcher: SelfAware);
+part!(pub Ticks: usize);
+
+#[derive(PartialRefTarget, Default)]
+pub struct State {
+    #[part(pin, Watcher)]
+    pub watcher: SelfAware,
+    #[part(Ticks)]
+    pub ticks: usize,
+}
+
+fn tick(mut state: partial!(State, mut Watcher, mut Ticks)) {
+    let (watcher, mut state) = state.split_pin_part_mut(Watcher);
+    watcher.update();
+    *state.part_mut(Ticks) += 1;
+}
+
+#[test]
+fn test_pinned_fields() {
+    let mut state = Box::pin(State::default());
+
+    let mut state_ref = state.as_mut().into_partial_ref();
+    tick(state_ref.borrow());
+    tick(state_ref.borrow());
+
+    let state_ref = state.as_ref().into_partial_ref();
+    assert_eq!(*state_ref.part(Ticks), 2);
+    assert_eq!(state_ref.pin_part(Watcher).moves, 1);
+}
+
+#[test]
+fn test_pinned_fields_unpinned_ref() {
+    let mut state = State::default();
+    let mut state_ref = state.into_partial_ref_mut();
+    *state_ref.part_mut(Ticks) += 1;
+    assert_eq!(state.ticks, 1);
+}
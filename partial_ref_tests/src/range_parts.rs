@@ -0,0 +1,54 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Mixer {
+    #[part(auto)]
+    pub samples: Vec<f32>,
+    #[part(auto)]
+    pub gain: f32,
+}
+
+type Left = RangePart<Samples, 0, 4>;
+type Right = RangePart<Samples, 4, 8>;
+
+fn apply_gain(mut mixer: partial!(Mixer, mut Left), gain: f32) {
+    for sample in mixer.part_mut(Left::default()) {
+        *sample *= gain;
+    }
+}
+
+fn silence(mut mixer: partial!(Mixer, mut Right)) {
+    mixer.part_mut(Right::default()).fill(0.0);
+}
+
+#[test]
+fn test_range_parts() {
+    let mut mixer = Mixer {
+        samples: vec![1.0; 10],
+        gain: 2.0,
+    };
+    let mut mixer_ref = mixer.into_partial_ref_mut();
+
+    let (mut ranges, rest): (partial!(Mixer, mut Left, mut Right), _) =
+        mixer_ref.split_range_parts_mut(Samples);
+    apply_gain(ranges.borrow(), *rest.part(Gain));
+    silence(ranges.borrow());
+    assert_eq!(ranges.part(Left::default()).len(), 4);
+
+    assert_eq!(
+        mixer.samples,
+        [2.0, 2.0, 2.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0]
+    );
+}
+
+#[test]
+#[should_panic(expected = "range part out of bounds")]
+fn test_range_parts_out_of_bounds() {
+    let mut mixer = Mixer {
+        samples: vec![1.0; 6],
+        gain: 1.0,
+    };
+    let mut mixer_ref = mixer.into_partial_ref_mut();
+
+    let _: (partial!(Mixer, mut Left, mut Right), _) = mixer_ref.split_range_parts_mut(Samples);
+}
@@ -0,0 +1,47 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Engine {
+    #[part(auto)]
+    pub events: Vec<&'static str>,
+    #[part(auto)]
+    pub ticks: u64,
+}
+
+type Handle = RawPartialRef<partial!('static Engine, mut Events, Ticks)>;
+
+// Stands in for a callback registry that can't store lifetimes.
+struct Registry {
+    handles: Vec<Handle>,
+}
+
+impl Registry {
+    fn notify(&self, event: &'static str) {
+        for handle in &self.handles {
+            let mut engine = unsafe { handle.revive() };
+            let ticks = *engine.part(Ticks);
+            if ticks > 0 {
+                engine.part_mut(Events).push(event);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_raw_partial_ref() {
+    let mut engine = Engine {
+        ticks: 1,
+        ..Engine::default()
+    };
+    let mut engine_ref = engine.into_partial_ref_mut();
+    let events_ref: partial!(Engine, mut Events, Ticks) = engine_ref.borrow();
+
+    let registry = Registry {
+        handles: vec![Handle::new(events_ref)],
+    };
+    registry.notify("start");
+    registry.notify("stop");
+    drop(registry);
+
+    assert_eq!(engine.events, ["start", "stop"]);
+}
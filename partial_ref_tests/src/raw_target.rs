@@ -0,0 +1,55 @@
+use std::cell::UnsafeCell;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(raw = "UnsafeCell<Self>")]
+#[part_group(Stats: hits, misses)]
+pub struct Cache {
+    #[part(auto)]
+    pub entries: Vec<u32>,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+pub struct SharedCache {
+    cache: UnsafeCell<Cache>,
+}
+
+impl SharedCache {
+    fn with_ref<R>(&mut self, f: impl FnOnce(partial!(Cache, mut Entries, mut Stats)) -> R) -> R {
+        unsafe { f(PartialRef::from_raw(&mut self.cache as *mut _)) }
+    }
+}
+
+fn lookup(mut cache: partial!(Cache, Entries, mut Stats), value: u32) -> bool {
+    let (entries, mut cache) = cache.split_part(Entries);
+    let stats = cache.group_mut(Stats);
+    if entries.contains(&value) {
+        *stats.hits += 1;
+        true
+    } else {
+        *stats.misses += 1;
+        false
+    }
+}
+
+#[test]
+fn test_raw_target() {
+    let mut shared = SharedCache {
+        cache: UnsafeCell::new(Cache::default()),
+    };
+
+    shared.with_ref(|mut cache| {
+        cache.part_mut(Entries).extend([1, 2, 3]);
+        assert!(lookup(cache.borrow(), 2));
+        assert!(!lookup(cache.borrow(), 4));
+    });
+
+    let mut cache = shared.cache.into_inner();
+    assert_eq!((cache.hits, cache.misses), (1, 1));
+
+    let mut cache_ref = cache.into_partial_ref_mut();
+    assert!(lookup(cache_ref.borrow(), 3));
+    assert_eq!(*cache_ref.group(Stats).hits, 2);
+}
@@ -0,0 +1,70 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Counters {
+    #[part(auto)]
+    pub hits: usize,
+    #[part(auto)]
+    pub misses: usize,
+}
+
+fn hit(mut counters: partial!(Counters, mut Hits, Misses)) {
+    *counters.part_mut(Hits) += 1;
+}
+
+fn record<T>(mut counters: partial!(Counters, mut Hits, Misses), values: &[Option<T>]) -> usize {
+    for value in values {
+        if value.is_some() {
+            hit(counters.reborrow());
+        }
+    }
+    hit(counters.reborrow());
+    *counters.part(Hits)
+}
+
+#[test]
+fn test_reborrow() {
+    let mut counters = Counters::default();
+    let mut counters_ref = counters.into_partial_ref_mut();
+
+    let mut counters_ref: partial!(Counters, mut Hits, Misses) = counters_ref.borrow();
+    assert_eq!(
+        record(counters_ref.reborrow(), &[Some(1), None, Some(2)]),
+        3
+    );
+    assert_eq!(record(counters_ref.reborrow(), &[None::<u8>]), 4);
+    assert_eq!(*counters_ref.part(Misses), 0);
+    assert_eq!(counters.hits, 4);
+}
+
+pub struct HitCounter<'a> {
+    pub counters: partial!('a Counters, mut Hits),
+}
+
+impl<'a> HitCounter<'a> {
+    fn new(counters: partial!('a Counters, mut Hits, mut Misses)) -> Self {
+        HitCounter {
+            counters: counters.convert(),
+        }
+    }
+}
+
+#[test]
+fn test_convert() {
+    let mut counters = Counters::default();
+
+    let mut counter = HitCounter::new(counters.into_partial_ref_mut());
+    *counter.counters.part_mut(Hits) += 2;
+    assert_eq!(counters.hits, 2);
+}
+
+#[test]
+fn test_convert_narrow() {
+    let mut counters = Counters::default();
+    let counters_ref = counters.into_partial_ref_mut();
+
+    let mut counters_ref: partial!(Counters, mut Hits, Misses) = counters_ref.convert();
+    hit(counters_ref.borrow());
+    let counters_ref: partial!(Counters, Hits) = counters_ref.convert();
+    assert_eq!(*counters_ref.part(Hits), 1);
+}
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+
+use partial_ref::*;
+
+part!(pub ref_cell Cache: Vec<u32>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Solver {
+    #[part(Cache)]
+    pub cache: RefCell<Vec<u32>>,
+    #[part(auto)]
+    pub input: Vec<u32>,
+    #[part(auto)]
+    pub lookups: usize,
+}
+
+fn lookup(solver: partial!(Solver, mut Cache, Input), index: usize) -> u32 {
+    if let Some(&value) = solver.cell_part(Cache).get(index) {
+        return value;
+    }
+    let value = solver.part(Input)[index] * 2;
+    let mut cache = solver.cell_part_mut(Cache);
+    cache.resize(index + 1, 0);
+    cache[index] = value;
+    value
+}
+
+fn sum(mut solver: partial!(Solver, mut Cache, Input, mut Lookups)) -> u32 {
+    let mut total = 0;
+    for index in 0..solver.part(Input).len() {
+        total += lookup(solver.borrow(), index);
+        *solver.part_mut(Lookups) += 1;
+    }
+    total
+}
+
+#[test]
+fn test_ref_cell_parts() {
+    let mut solver = Solver {
+        input: vec![1, 2, 3],
+        ..Solver::default()
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    assert_eq!(sum(solver_ref.borrow()), 12);
+
+    {
+        let (mut cache, mut solver_ref) = solver_ref.split_cell_part_mut(Cache);
+        cache.push(7);
+        *solver_ref.part_mut(Lookups) += 1;
+    }
+
+    assert_eq!(*solver_ref.cell_part(Cache), [2, 4, 6, 7]);
+    assert_eq!(solver.lookups, 4);
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_ref_cell_parts_conflict() {
+    let mut solver = Solver::default();
+    let solver_ref = solver.into_partial_ref_mut();
+
+    let _cache = solver_ref.cell_part(Cache);
+    solver_ref.cell_part_mut(Cache).push(1);
+}
@@ -0,0 +1,43 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Solver {
+    #[part(auto)]
+    pub clauses: Vec<Vec<i32>>,
+    #[part(auto)]
+    pub assignment: Vec<bool>,
+    #[part(auto)]
+    pub trail: Vec<i32>,
+}
+
+type SolverRef<'a> = partial!('a Solver, mut Clauses, Assignment, mut Trail);
+type PropagateRef<'a> = partial!('a Solver, Assignment, mut Trail);
+
+pub struct Propagation<'a> {
+    pub propagate: PropagateRef<'a>,
+    pub rest: RemainderOf<'a, SolverRef<'a>, PropagateRef<'a>>,
+}
+
+fn start<'a>(solver: &'a mut SolverRef<'a>) -> Propagation<'a> {
+    let (propagate, rest) = solver.split_borrow();
+    Propagation { propagate, rest }
+}
+
+#[test]
+fn test_remainder_of() {
+    let mut solver = Solver {
+        assignment: vec![true],
+        ..Solver::default()
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+    let mut solver_ref: SolverRef = solver_ref.borrow();
+
+    let mut propagation = start(&mut solver_ref);
+    propagation.propagate.part_mut(Trail).push(1);
+    propagation.rest.part_mut(Clauses).push(vec![-1]);
+    assert_eq!(propagation.rest.part(Assignment), &[true]);
+
+    let _: partial!(Solver, mut Clauses, Assignment) = propagation.rest;
+    assert_eq!(solver.trail, [1]);
+    assert_eq!(solver.clauses, [[-1]]);
+}
@@ -0,0 +1,34 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Propagator {
+    #[part(auto)]
+    pub queue: Vec<u32>,
+    #[part(auto)]
+    pub seen: Vec<bool>,
+    #[part(auto)]
+    pub rounds: usize,
+}
+
+fn propagate(mut propagator: partial!(Propagator, mut Queue, mut Seen, Rounds)) {
+    propagator.reset_parts();
+    let rounds = *propagator.part(Rounds);
+    propagator.part_mut(Queue).extend(0..rounds as u32);
+    propagator.part_mut(Seen).push(true);
+}
+
+#[test]
+fn test_reset_parts() {
+    let mut propagator = Propagator::default();
+    let mut propagator_ref = propagator.into_partial_ref_mut();
+
+    *propagator_ref.part_mut(Rounds) = 2;
+    propagate(propagator_ref.borrow());
+    propagate(propagator_ref.borrow());
+    assert_eq!(propagator_ref.part(Queue), &[0, 1]);
+    assert_eq!(propagator_ref.part(Seen), &[true]);
+
+    propagator_ref.reset_part(Rounds);
+    assert_eq!(propagator.rounds, 0);
+    assert_eq!(propagator.queue, [0, 1]);
+}
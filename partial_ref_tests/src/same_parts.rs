@@ -0,0 +1,41 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Host {
+    #[part(auto)]
+    pub state: Vec<u32>,
+    #[part(auto)]
+    pub config: u32,
+    #[part(auto)]
+    pub secrets: Vec<u8>,
+}
+
+type PluginRef<'a> = partial!('a Host, mut State, Config);
+
+fn run_plugin<'a, Reference, SameIndex>(host: Reference) -> usize
+where
+    Reference: SameParts<'a, PluginRef<'a>, SameIndex>,
+{
+    let mut host = host.into_same_parts();
+    let config = *host.part(Config);
+    host.part_mut(State).push(config);
+    host.part(State).len()
+}
+
+fn reordered(host: partial!(Host, Config, mut State)) -> usize {
+    run_plugin(host)
+}
+
+#[test]
+fn test_same_parts() {
+    let mut host = Host {
+        config: 7,
+        ..Host::default()
+    };
+    let mut host_ref = host.into_partial_ref_mut();
+
+    let plugin_ref: PluginRef = host_ref.borrow();
+    assert_eq!(run_plugin(plugin_ref), 1);
+    assert_eq!(reordered(host_ref.borrow()), 2);
+    assert_eq!(host.state, [7, 7]);
+}
@@ -0,0 +1,65 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Queue {
+    #[part(auto)]
+    pub pending: Vec<u32>,
+    #[part(auto)]
+    pub done: Vec<u32>,
+    #[part(auto)]
+    pub limit: usize,
+}
+
+fn process(mut queue: partial!(Queue, mut Pending, mut Done, Limit)) -> usize {
+    let processed = queue.with_part_mut(Pending, |pending, mut rest| {
+        let limit = *rest.part(Limit);
+        let count = pending.len().min(limit);
+        for item in pending.drain(..count) {
+            rest.part_mut(Done).push(item * 2);
+        }
+        count
+    });
+    let remaining = queue.with_part(Pending, |pending, _| pending.len());
+    processed + remaining
+}
+
+#[test]
+fn test_with_part() {
+    let mut queue = Queue {
+        pending: vec![1, 2, 3],
+        limit: 2,
+        ..Queue::default()
+    };
+    let mut queue_ref = queue.into_partial_ref_mut();
+
+    assert_eq!(process(queue_ref.borrow()), 3);
+    assert_eq!(queue_ref.part(Pending), &[3]);
+    assert_eq!(queue.done, [2, 4]);
+}
+
+fn record_done(mut queue: partial!(Queue, mut Done, Limit), item: u32) {
+    if queue.part(Done).len() < *queue.part(Limit) {
+        queue.part_mut(Done).push(item);
+    }
+}
+
+fn drain_pending(mut queue: partial!(Queue, mut Pending, mut Done, Limit)) {
+    queue.for_each_element_mut(Pending, |item, rest| {
+        record_done(rest.borrow(), *item);
+        *item += 10;
+    });
+}
+
+#[test]
+fn test_for_each_element_mut() {
+    let mut queue = Queue {
+        pending: vec![1, 2, 3],
+        limit: 2,
+        ..Queue::default()
+    };
+    let mut queue_ref = queue.into_partial_ref_mut();
+
+    drain_pending(queue_ref.borrow());
+    assert_eq!(queue_ref.part(Pending), &[11, 12, 13]);
+    assert_eq!(queue.done, [1, 2]);
+}
@@ -0,0 +1,119 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Checkpointed {
+    #[part(auto)]
+    pub assignment: Vec<bool>,
+    #[part(auto)]
+    pub conflicts: u64,
+    #[part(deref, Trail)]
+    pub trail: Box<[usize; 2]>,
+    #[part(elements(Activity))]
+    pub activity: [f32; 2],
+    pub scratch: Vec<usize>,
+}
+
+part!(pub Trail: [usize; 2]);
+
+fn checkpoint(solver: partial!(Checkpointed, Assignment, Conflicts, Trail)) -> String {
+    serde_json::to_string(&solver).unwrap()
+}
+
+#[test]
+fn test_serialize_parts() {
+    let mut solver = Checkpointed {
+        assignment: vec![true, false],
+        conflicts: 3,
+        trail: Box::new([1, 0]),
+        activity: [0.5, 1.5],
+        scratch: vec![7],
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    let json = checkpoint(solver_ref.borrow());
+    assert_eq!(
+        json,
+        r#"{"Assignment":[true,false],"Conflicts":3,"Trail":[1,0]}"#
+    );
+
+    let mut restored = Checkpointed::default();
+    let mut restored_ref = restored.into_partial_ref_mut();
+    let mut parts: partial!(Checkpointed, mut Assignment, mut Conflicts, mut Trail) =
+        restored_ref.borrow();
+    parts
+        .deserialize_parts(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+
+    assert_eq!(restored.assignment, vec![true, false]);
+    assert_eq!(restored.conflicts, 3);
+    assert_eq!(*restored.trail, [1, 0]);
+    assert!(restored.scratch.is_empty());
+}
+
+#[test]
+fn test_serialize_element_parts() {
+    let mut solver = Checkpointed {
+        activity: [0.5, 1.5],
+        ..Checkpointed::default()
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    let mut activity: partial!(Checkpointed, mut Activity<1>, Conflicts) = solver_ref.borrow();
+    let json = serde_json::to_string(&activity).unwrap();
+    assert_eq!(json, r#"{"Activity<1>":1.5,"Conflicts":0}"#);
+
+    activity
+        .deserialize_parts(&mut serde_json::Deserializer::from_str(
+            r#"{"Activity<1>":2.5}"#,
+        ))
+        .unwrap();
+    assert_eq!(solver.activity, [0.5, 2.5]);
+}
+
+#[test]
+fn test_serialize_deserialize_mixed_parts() {
+    let mut solver = Checkpointed {
+        assignment: vec![true],
+        conflicts: 3,
+        ..Checkpointed::default()
+    };
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    let parts: partial!(Checkpointed, mut Assignment, Conflicts) = solver_ref.borrow();
+    let json = serde_json::to_string(&parts).unwrap();
+    assert_eq!(json, r#"{"Assignment":[true],"Conflicts":3}"#);
+
+    let mut restored = Checkpointed::default();
+    let mut restored_ref = restored.into_partial_ref_mut();
+    let mut parts: partial!(Checkpointed, mut Assignment, Conflicts) = restored_ref.borrow();
+    parts
+        .deserialize_parts(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+
+    assert_eq!(restored.assignment, vec![true]);
+    assert_eq!(restored.conflicts, 0);
+}
+
+#[test]
+fn test_deserialize_parts_errors() {
+    let mut solver = Checkpointed::default();
+    let mut solver_ref = solver.into_partial_ref_mut();
+    let mut parts: partial!(Checkpointed, mut Assignment, mut Conflicts) = solver_ref.borrow();
+
+    let mut deserialize = |json: &str| {
+        parts
+            .deserialize_parts(&mut serde_json::Deserializer::from_str(json))
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert!(deserialize(r#"{"Conflicts":1}"#).contains("missing field `Assignment`"));
+    assert!(deserialize(r#"{"Assignment":[],"Trail":[0,0]}"#).contains("unknown part `Trail`"));
+    assert!(deserialize(r#"{"Conflicts":1,"Conflicts":2}"#).contains("duplicate field"));
+    assert!(deserialize(r#"{"Assignment":[true],"Trail":[0,0]}"#).contains("unknown part"));
+    assert!(deserialize(r#"{"Conflicts":1,"Assignment":"x"}"#).contains("invalid type"));
+
+    // Nothing is written when the map is rejected.
+    assert!(solver.assignment.is_empty());
+    assert_eq!(solver.conflicts, 0);
+}
@@ -0,0 +1,80 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Mesh {
+    #[part(auto)]
+    pub levels: Vec<u32>,
+    #[part(auto)]
+    pub weights: [u32; 4],
+    #[part(auto)]
+    pub calls: usize,
+}
+
+fn assign_levels(levels: &mut [u32], mut mesh: partial!(Mesh, mut Calls), level: u32) {
+    *mesh.part_mut(Calls) += 1;
+    if levels.len() <= 1 {
+        for value in levels {
+            *value = level;
+        }
+        return;
+    }
+    let (head, tail) = levels.split_at_mut(levels.len() / 2);
+    assign_levels(head, mesh.borrow(), level + 1);
+    assign_levels(tail, mesh.borrow(), level + 1);
+}
+
+fn assign(mut mesh: partial!(Mesh, mut Levels, mut Calls), mid: usize) {
+    let (head, tail, mut mesh) = mesh.split_slice_part_at(Levels, mid);
+    assign_levels(head, mesh.borrow(), 0);
+    assign_levels(tail, mesh.borrow(), 10);
+}
+
+#[test]
+fn test_split_slice_part_at() {
+    let mut mesh = Mesh {
+        levels: vec![0; 5],
+        ..Mesh::default()
+    };
+    let mut mesh_ref = mesh.into_partial_ref_mut();
+
+    assign(mesh_ref.borrow(), 1);
+    assert_eq!(mesh_ref.part(Levels), &[0, 12, 12, 12, 12]);
+    assert_eq!(*mesh_ref.part(Calls), 8);
+
+    let (head, tail, _) = mesh_ref.split_slice_part_at(Weights, 4);
+    head.copy_from_slice(&[1, 2, 3, 4]);
+    assert!(tail.is_empty());
+    assert_eq!(mesh.weights, [1, 2, 3, 4]);
+}
+
+fn link(mut mesh: partial!(Mesh, mut Levels, mut Calls), a: usize, b: usize) -> bool {
+    let (levels, mut mesh) = mesh.get_disjoint_part_mut(Levels, [a, b]);
+    *mesh.part_mut(Calls) += 1;
+    match levels {
+        Ok([level_a, level_b]) => {
+            std::mem::swap(level_a, level_b);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[test]
+fn test_get_disjoint_part_mut() {
+    let mut mesh = Mesh {
+        levels: vec![1, 2, 3],
+        ..Mesh::default()
+    };
+    let mut mesh_ref = mesh.into_partial_ref_mut();
+
+    assert!(link(mesh_ref.borrow(), 0, 2));
+    assert!(!link(mesh_ref.borrow(), 1, 1));
+    assert!(!link(mesh_ref.borrow(), 1, 3));
+    assert_eq!(*mesh_ref.part(Calls), 3);
+
+    let (levels, _) = mesh_ref.get_disjoint_part_mut(Levels, [2, 0, 2]);
+    assert_eq!(levels.unwrap_err(), DisjointMutError::OverlappingIndices);
+    let (levels, _) = mesh_ref.get_disjoint_part_mut(Levels, [1, 3]);
+    assert_eq!(levels.unwrap_err(), DisjointMutError::IndexOutOfBounds);
+    assert_eq!(mesh.levels, [3, 2, 1]);
+}
@@ -0,0 +1,36 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Heap {
+    #[part(auto)]
+    pub values: Vec<u32>,
+    #[part(auto)]
+    pub total: u32,
+}
+
+fn add(mut heap: partial!(Heap, mut Values, mut Total), value: u32) {
+    heap.part_mut(Values).push(value);
+    *heap.part_mut(Total) += value;
+}
+
+fn total(heap: partial!(Heap, Total)) -> u32 {
+    *heap.part(Total)
+}
+
+#[test]
+fn test_smart_pointers() {
+    let mut boxed = Box::new(Heap::default());
+    add(boxed.into_partial_ref_mut().borrow(), 2);
+    add(boxed.into_partial_ref_mut().borrow(), 3);
+    assert_eq!(total(boxed.into_partial_ref().borrow()), 5);
+    assert_eq!(boxed.values, vec![2, 3]);
+
+    let shared = Rc::new(Heap::default());
+    assert_eq!(total(shared.into_partial_ref().borrow()), 0);
+
+    let shared = Arc::new(*boxed);
+    assert_eq!(total(shared.into_partial_ref().borrow()), 5);
+}
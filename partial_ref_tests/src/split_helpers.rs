@@ -0,0 +1,50 @@
+use partial_ref::*;
+
+part!(pub Weights: Vec<f32>);
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(split_helpers)]
+pub struct Graph {
+    #[part(auto)]
+    pub colors: Vec<usize>,
+    #[part(Weights)]
+    pub weights: Vec<f32>,
+    #[part(auto)]
+    pub r#type: u8,
+}
+
+#[derive(PartialRefTarget)]
+#[partial_ref(split_helpers)]
+pub struct Window<'a, T: 'a>(#[part(Items<'a, T>)] pub &'a [T], #[part(Start)] pub usize);
+
+part!(pub Items<'a, T>: &'a [T]);
+part!(pub Start: usize);
+
+fn recolor(mut graph: partial!(Graph, mut Colors, Weights, Type)) {
+    let (colors, mut graph) = graph.split_colors_mut();
+    let (weights, mut graph) = graph.split_weights();
+    let (kind, _) = graph.split_type();
+    for (color, weight) in colors.iter_mut().zip(weights) {
+        *color += *weight as usize + *kind as usize;
+    }
+}
+
+#[test]
+fn test_split_helpers() {
+    let mut graph = Graph {
+        colors: vec![0, 1],
+        weights: vec![2.0, 3.0],
+        r#type: 1,
+    };
+
+    recolor(graph.into_partial_ref_mut().borrow());
+    assert_eq!(graph.colors, vec![3, 5]);
+
+    let values = [1, 2, 3];
+    let mut window = Window(&values, 0);
+    let mut window_ref = window.into_partial_ref_mut();
+    let (items, mut window_ref) = window_ref.split_0();
+    let (start, _) = window_ref.split_1_mut();
+    *start = items.len();
+    assert_eq!(window.1, 3);
+}
@@ -0,0 +1,37 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget)]
+pub struct Board {
+    #[part(auto)]
+    pub ticks: u64,
+    #[part(auto)]
+    pub leds: [bool; 4],
+}
+
+static BOARD: StaticTarget<Board> = StaticTarget::new(Board {
+    ticks: 0,
+    leds: [false; 4],
+});
+
+fn tick(mut timer: partial!(Board, mut Ticks)) {
+    *timer.part_mut(Ticks) += 1;
+}
+
+fn blink(mut display: partial!(Board, mut Leds, Ticks)) {
+    let index = (*display.part(Ticks) % 4) as usize;
+    display.part_mut(Leds)[index] = true;
+}
+
+#[test]
+fn test_static_target() {
+    assert!(!BOARD.is_taken());
+    let mut board = BOARD.take().unwrap();
+    assert!(BOARD.is_taken());
+    assert!(BOARD.take().is_none());
+
+    tick(board.borrow());
+    tick(board.borrow());
+    blink(board.borrow());
+
+    assert_eq!(*board.part(Leds), [false, false, true, false]);
+}
@@ -0,0 +1,80 @@
+use partial_ref::*;
+
+part!(pub Buffer: Vec<u8>);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Reader {
+    #[part(Buffer)]
+    pub input: Vec<u8>,
+    #[part(auto)]
+    pub position: usize,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Writer {
+    #[part(Buffer)]
+    pub output: Vec<u8>,
+    #[part(auto)]
+    pub backlog: Vec<u8>,
+}
+
+fn recycle(mut reader: partial!(Reader, mut Buffer), mut writer: partial!(Writer, mut Buffer)) {
+    swap_parts(&mut reader, Buffer, &mut writer, Buffer);
+}
+
+#[test]
+fn test_swap_parts() {
+    let mut reader = Reader {
+        input: vec![1, 2],
+        position: 2,
+    };
+    let mut writer = Writer {
+        output: vec![3],
+        backlog: vec![4, 5],
+    };
+    let mut reader_ref = reader.into_partial_ref_mut();
+    let mut writer_ref = writer.into_partial_ref_mut();
+
+    recycle(reader_ref.borrow(), writer_ref.borrow());
+    assert_eq!(reader_ref.part(Buffer), &[3]);
+    assert_eq!(writer_ref.part(Buffer), &[1, 2]);
+
+    swap_parts(&mut reader_ref, Buffer, &mut writer_ref, Backlog);
+    assert_eq!(reader.input, [4, 5]);
+    assert_eq!(writer.backlog, [3]);
+}
+
+#[test]
+fn test_swap_parts_of_same_target() {
+    let mut writer = Writer {
+        output: vec![1],
+        backlog: vec![2],
+    };
+    let mut writer_ref = writer.into_partial_ref_mut();
+
+    let (mut output, mut backlog): (partial!(Writer, mut Buffer), _) = writer_ref.split_borrow();
+    swap_parts(&mut output, Buffer, &mut backlog, Backlog);
+    assert_eq!(writer.output, [2]);
+    assert_eq!(writer.backlog, [1]);
+}
+
+fn flush(mut writer: partial!(Writer, mut Buffer, mut Backlog)) {
+    writer.swap_between(Buffer, Backlog);
+}
+
+#[test]
+fn test_swap_between() {
+    let mut writer = Writer {
+        output: vec![1],
+        backlog: vec![2, 3],
+    };
+    let mut writer_ref = writer.into_partial_ref_mut();
+
+    flush(writer_ref.borrow());
+    assert_eq!(writer_ref.part(Buffer), &[2, 3]);
+    assert_eq!(writer_ref.part(Backlog), &[1]);
+
+    writer_ref.swap_between(Backlog, Buffer);
+    assert_eq!(writer.output, [1]);
+    assert_eq!(writer.backlog, [2, 3]);
+}
@@ -0,0 +1,73 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct ClauseDatabase {
+    #[part(auto)]
+    pub clauses: Vec<Vec<i32>>,
+    #[part(auto)]
+    pub garbage: usize,
+}
+
+part!(pub ClauseDb: ClauseDatabase);
+
+type Irred = TaggedPart<ClauseDb, 0>;
+type Redundant = TaggedPart<ClauseDb, 1>;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Solver {
+    #[part(TaggedPart<ClauseDb, 0>)]
+    pub irred: ClauseDatabase,
+    #[part(TaggedPart<ClauseDb, 1>)]
+    pub redundant: ClauseDatabase,
+    #[part(auto)]
+    pub conflicts: usize,
+}
+
+fn add_clause<const N: usize>(
+    mut solver: partial!(Solver, mut TaggedPart<ClauseDb, N>),
+    clause: &[i32],
+) where
+    Solver: HasPart<TaggedPart<ClauseDb, N>>,
+{
+    solver
+        .part_mut(TaggedPart::<ClauseDb, N>::default())
+        .clauses
+        .push(clause.to_vec());
+}
+
+fn clause_count<const N: usize>(solver: partial!(Solver, TaggedPart<ClauseDb, N>)) -> usize
+where
+    Solver: HasPart<TaggedPart<ClauseDb, N>>,
+{
+    solver
+        .part(TaggedPart::<ClauseDb, N>::default())
+        .clauses
+        .len()
+}
+
+fn delete_clause(mut solver: partial!(Solver, mut Redundant | Clauses, mut Redundant | Garbage)) {
+    let (clauses, mut solver) = solver.split_part_mut(Redundant::default() | Clauses);
+    clauses.pop();
+    *solver.part_mut(Redundant::default() | Garbage) += 1;
+}
+
+#[test]
+fn test_tagged_parts() {
+    let mut solver = Solver::default();
+    let mut solver_ref = solver.into_partial_ref_mut();
+
+    add_clause::<0>(solver_ref.borrow(), &[1, 2]);
+    add_clause::<1>(solver_ref.borrow(), &[-1, 3]);
+    add_clause::<1>(solver_ref.borrow(), &[2, 3]);
+    delete_clause(solver_ref.borrow());
+    assert_eq!(clause_count::<0>(solver_ref.borrow()), 1);
+    assert_eq!(clause_count::<1>(solver_ref.borrow()), 1);
+
+    let (irred, mut solver_ref) = solver_ref.split_part(Irred::default());
+    let redundant = solver_ref.part_mut(Redundant::default());
+    redundant.clauses.extend(irred.clauses.iter().cloned());
+
+    assert_eq!(solver.irred.clauses, [vec![1, 2]]);
+    assert_eq!(solver.redundant.clauses, [vec![-1, 3], vec![1, 2]]);
+    assert_eq!((solver.irred.garbage, solver.redundant.garbage), (0, 1));
+}
@@ -0,0 +1,85 @@
+use std::thread;
+
+use partial_ref::*;
+
+part!(pub thread_local Scratch: Vec<u32>);
+
+#[derive(PartialRefTarget, Default)]
+#[part(thread_local(Scratch))]
+pub struct Sorter {
+    #[part(auto)]
+    pub values: Vec<u32>,
+    #[part(auto)]
+    pub passes: usize,
+}
+
+fn merge_halves(mut sorter: partial!(Sorter, mut Values, mut Passes, mut Scratch)) {
+    sorter.with_local_part_mut(Scratch, |scratch, mut sorter| {
+        let values = sorter.part_mut(Values);
+        let mid = values.len() / 2;
+        values[..mid].sort_unstable();
+        values[mid..].sort_unstable();
+
+        scratch.clear();
+        let (mut left, mut right) = (0, mid);
+        while left < mid || right < values.len() {
+            if right == values.len() || (left < mid && values[left] <= values[right]) {
+                scratch.push(values[left]);
+                left += 1;
+            } else {
+                scratch.push(values[right]);
+                right += 1;
+            }
+        }
+        values.copy_from_slice(scratch);
+        *sorter.part_mut(Passes) += 1;
+    });
+}
+
+fn scratch_capacity(mut sorter: partial!(Sorter, Scratch)) -> usize {
+    sorter.with_local_part(Scratch, |scratch, _| scratch.capacity())
+}
+
+#[test]
+fn test_thread_local_parts() {
+    let mut first = Sorter {
+        values: vec![5, 1, 4, 2, 3],
+        ..Sorter::default()
+    };
+    let mut second = Sorter {
+        values: vec![9, 7, 8],
+        ..Sorter::default()
+    };
+    let mut first_ref = first.into_partial_ref_mut();
+    let mut second_ref = second.into_partial_ref_mut();
+
+    merge_halves(first_ref.borrow());
+    merge_halves(second_ref.borrow());
+
+    // Both targets share the scratch space of this thread.
+    assert!(scratch_capacity(first_ref.borrow()) >= 5);
+    second_ref.with_local_part(Scratch, |scratch, _| assert_eq!(*scratch, [7, 8, 9]));
+
+    let other_thread = thread::spawn(|| {
+        let mut sorter = Sorter::default();
+        scratch_capacity(sorter.into_partial_ref_mut().borrow())
+    });
+    assert_eq!(other_thread.join().unwrap(), 0);
+
+    assert_eq!(first.values, [1, 2, 3, 4, 5]);
+    assert_eq!(second.values, [7, 8, 9]);
+    assert_eq!(first.passes + second.passes, 2);
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_shared_scratch_borrow() {
+    let mut first = Sorter::default();
+    let mut second = Sorter::default();
+    let mut first_ref = first.into_partial_ref_mut();
+    let mut second_ref = second.into_partial_ref_mut();
+
+    first_ref.with_local_part_mut(Scratch, |_, _| {
+        second_ref.with_local_part_mut(Scratch, |_, _| ());
+    });
+}
@@ -0,0 +1,46 @@
+use std::marker::PhantomData;
+
+use partial_ref::*;
+
+part!(pub Name: String);
+part!(pub Visits: usize);
+
+#[derive(PartialRefTarget, Default)]
+pub struct Page {
+    #[part(Name)]
+    pub name: String,
+    #[part(Visits)]
+    pub visits: usize,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct TrackedPage(#[part(transparent)] pub Page);
+
+#[derive(PartialRefTarget)]
+pub struct Tagged<Tag> {
+    #[part(transparent)]
+    pub page: Page,
+    pub tag: PhantomData<Tag>,
+}
+
+fn visit(mut page: partial!(Page, Name, mut Visits)) -> usize {
+    let (name, mut page) = page.split_part(Name);
+    *page.part_mut(Visits) += name.len();
+    *page.part(Visits)
+}
+
+#[test]
+fn test_transparent() {
+    let mut tracked = TrackedPage::default();
+    tracked.0.name = "home".to_owned();
+
+    assert_eq!(visit(tracked.into_partial_ref_mut().borrow()), 4);
+
+    let mut tagged = Tagged::<u8> {
+        page: Page::default(),
+        tag: PhantomData,
+    };
+    let mut tagged_ref = tagged.into_partial_ref_mut();
+    assert_eq!(visit(tagged_ref.borrow()), 0);
+    assert_eq!(tagged_ref.part(Visits), &0);
+}
@@ -0,0 +1,112 @@
+use std::marker::PhantomData;
+
+use partial_ref::*;
+
+/// A part type for `Option` fields, which are absent when `None`.
+pub struct OptionalField<FieldType>(PhantomData<FieldType>);
+
+impl<FieldType> PartType for OptionalField<FieldType> {
+    type Ptr = *const Option<FieldType>;
+    type PtrMut = *mut Option<FieldType>;
+}
+
+impl<FieldType> TryPartType for OptionalField<FieldType> {
+    type FieldType = FieldType;
+
+    unsafe fn try_field_ptr(ptr: *const Option<FieldType>) -> Option<*const FieldType> {
+        (*ptr).as_ref().map(|field| field as *const FieldType)
+    }
+
+    unsafe fn try_field_ptr_mut(ptr: *mut Option<FieldType>) -> Option<*mut FieldType> {
+        (*ptr).as_mut().map(|field| field as *mut FieldType)
+    }
+}
+
+#[derive(Default)]
+pub struct Cached;
+
+impl Part for Cached {
+    type PartType = OptionalField<u64>;
+}
+
+impl PartSpec<Cached> for Cached {}
+
+impl FieldTypeOf<Cached, OptionalField<u64>> for Option<u64> {}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Lookup {
+    #[part(Cached)]
+    pub cached: Option<u64>,
+    #[part(auto)]
+    pub queries: usize,
+}
+
+fn query(mut lookup: partial!(Lookup, mut Cached, mut Queries)) -> Option<u64> {
+    *lookup.try_part_mut(Queries)? += 1;
+    let cached = lookup.try_part_mut(Cached)?;
+    *cached += 1;
+    Some(*cached)
+}
+
+#[test]
+fn test_try_part() {
+    let mut lookup = Lookup::default();
+    let mut lookup_ref = lookup.into_partial_ref_mut();
+
+    assert_eq!(query(lookup_ref.borrow()), None);
+    assert_eq!(lookup_ref.try_part(Cached), None);
+    assert_eq!(lookup_ref.try_part(Queries), Some(&1));
+
+    lookup.cached = Some(4);
+    let mut lookup_ref = lookup.into_partial_ref_mut();
+    assert_eq!(query(lookup_ref.borrow()), Some(5));
+    assert_eq!(lookup_ref.try_part(Cached), Some(&5));
+    assert_eq!(lookup.queries, 2);
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct ProfileData {
+    #[part(auto)]
+    pub samples: Vec<u64>,
+    #[part(auto)]
+    pub dropped: usize,
+}
+
+#[derive(PartialRefTarget, Default)]
+pub struct Context {
+    #[part(auto)]
+    pub profile: Option<ProfileData>,
+    #[part(auto)]
+    pub steps: usize,
+}
+
+fn record(mut profile: partial!(ProfileData, mut Samples), sample: u64) {
+    profile.part_mut(Samples).push(sample);
+}
+
+fn step(mut context: partial!(Context, mut Profile, mut Steps)) -> bool {
+    let (profile, mut context) = context.try_split_part_mut(Profile);
+    *context.part_mut(Steps) += 1;
+    match profile {
+        Some(mut profile) => {
+            record(profile.borrow(), *context.part(Steps) as u64);
+            true
+        }
+        None => false,
+    }
+}
+
+#[test]
+fn test_try_split_part() {
+    let mut context = Context::default();
+    let mut context_ref = context.into_partial_ref_mut();
+
+    assert!(!step(context_ref.borrow()));
+    assert!(context_ref.try_split_part(Profile).0.is_none());
+
+    *context_ref.part_mut(Profile) = Some(ProfileData::default());
+    assert!(step(context_ref.borrow()));
+    let (profile, context_ref) = context_ref.try_split_part(Profile);
+    assert_eq!(profile.unwrap().part(Samples), &[2]);
+    assert_eq!(*context_ref.part(Steps), 2);
+}
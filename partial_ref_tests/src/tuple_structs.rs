@@ -0,0 +1,41 @@
+use partial_ref::*;
+
+part!(pub First: u32);
+part!(pub Second: Vec<u32>);
+part!(pub Params<'a>: &'a [u32]);
+
+#[derive(PartialRefTarget)]
+pub struct Pair(#[part(First)] u32, u8, #[part(Second)] Vec<u32>);
+
+#[derive(PartialRefTarget)]
+pub struct WithParams<'a>(#[part(Params<'a>)] &'a [u32]);
+
+fn push_first(mut pair: partial!(Pair, First, mut Second)) {
+    let first = *pair.part(First);
+    pair.part_mut(Second).push(first);
+}
+
+#[test]
+fn test_tuple_struct() {
+    let mut pair = Pair(1, 2, vec![]);
+    let mut pair_ref = pair.into_partial_ref_mut();
+
+    push_first(pair_ref.borrow());
+
+    let (second, mut pair_ref) = pair_ref.split_part_mut(Second);
+    *pair_ref.part_mut(First) = 3;
+    second.push(*pair_ref.part(First));
+
+    assert_eq!(pair.0, 3);
+    assert_eq!(pair.1, 2);
+    assert_eq!(pair.2, vec![1, 3]);
+}
+
+#[test]
+fn test_tuple_struct_lifetime() {
+    let values = [1, 2, 3];
+    let mut with_params = WithParams(&values);
+    let with_params_ref = with_params.into_partial_ref_mut();
+
+    assert_eq!(with_params_ref.part(Params).len(), 3);
+}
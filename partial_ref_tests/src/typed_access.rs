@@ -0,0 +1,37 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+pub struct Scores<'a> {
+    #[part(auto)]
+    pub points: Vec<u32>,
+    #[part(auto)]
+    pub bonus: Vec<u32>,
+    #[part(auto)]
+    pub names: &'a [&'a str],
+}
+
+fn sum_of<'a, SomePart, Reference, PartIndex>(scores: &'a Reference) -> u32
+where
+    SomePart: FieldPartType<FieldType = Vec<u32>>,
+    Reference: PartialRef<'a> + PluckConst<'a, SomePart, PartIndex>,
+    Reference::Target: HasPart<SomePart> + 'a,
+{
+    scores.part_of::<SomePart, _>().iter().sum()
+}
+
+#[test]
+fn test_typed_access() {
+    let names = ["a", "b"];
+    let mut scores = Scores {
+        names: &names,
+        ..Scores::default()
+    };
+    let mut scores_ref = scores.into_partial_ref_mut();
+
+    scores_ref.part_mut_of::<Points, _>().extend([1, 2]);
+    scores_ref.part_mut_of::<Bonus, _>().push(5);
+
+    assert_eq!(sum_of::<Points, _, _>(&scores_ref), 3);
+    assert_eq!(sum_of::<Bonus, _, _>(&scores_ref), 5);
+    assert_eq!(scores_ref.part_of::<Names<'_>, _>().len(), 2);
+}
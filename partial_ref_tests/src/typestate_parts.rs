@@ -0,0 +1,43 @@
+use partial_ref::*;
+
+part!(pub Search);
+
+pub struct Propagating;
+pub struct Deciding;
+
+#[derive(PartialRefTarget, Default)]
+#[part(Search)]
+pub struct Solver {
+    #[part(auto)]
+    pub trail: Vec<i32>,
+}
+
+type Propagate<'a> = partial!('a Solver, mut Typestate<Search, Propagating>, mut Trail);
+type Decide<'a> = partial!('a Solver, mut Typestate<Search, Deciding>, mut Trail);
+
+fn propagate(mut solver: Propagate) -> Decide {
+    let last = solver.part(Trail).last().copied().unwrap_or(0);
+    solver.part_mut(Trail).push(last + 1);
+    solver.transition_state(Search).convert()
+}
+
+fn decide(mut solver: Decide, literal: i32) -> Propagate {
+    solver.part_mut(Trail).push(literal);
+    solver.transition_state(Search).convert()
+}
+
+fn run(solver: partial!(Solver, mut Search, mut Trail), literals: &[i32]) {
+    let mut solver: Propagate = solver.enter_state(Search).convert();
+    for &literal in literals {
+        solver = decide(propagate(solver), literal);
+    }
+}
+
+#[test]
+fn test_typestate_parts() {
+    let mut solver = Solver::default();
+
+    run(solver.into_partial_ref_mut().borrow(), &[5, -3]);
+
+    assert_eq!(solver.trail, [1, 5, 6, -3]);
+}
@@ -0,0 +1,40 @@
+use std::mem::ManuallyDrop;
+
+use partial_ref::*;
+
+part!(pub union Bits: u32);
+
+#[derive(PartialRefTarget)]
+#[part(Tag)]
+pub union Value {
+    #[part(Bits)]
+    pub bits: u32,
+    #[part(auto)]
+    pub float: f32,
+    #[part(auto)]
+    pub items: ManuallyDrop<[u8; 4]>,
+}
+
+part!(pub Tag);
+
+fn set_float(mut value: partial!(Value, mut Float, Tag), x: f32) {
+    unsafe {
+        *value.part_unchecked_mut(Float) = x;
+    }
+}
+
+#[test]
+fn test_union_parts() {
+    let mut value = Value { bits: 0 };
+    let mut value_ref = value.into_partial_ref_mut();
+
+    assert_eq!(<Value as PartOffset<Float>>::OFFSET, 0);
+
+    set_float(value_ref.borrow(), 1.0);
+    assert_eq!(unsafe { *value_ref.part_unchecked(Bits) }, 1.0f32.to_bits());
+
+    unsafe {
+        *value_ref.part_unchecked_mut(Bits) = u32::from_ne_bytes([1, 2, 3, 4]);
+    }
+    assert_eq!(**unsafe { value_ref.part_unchecked(Items) }, [1, 2, 3, 4]);
+}
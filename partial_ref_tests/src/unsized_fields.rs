@@ -0,0 +1,43 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget)]
+#[repr(C)]
+#[part(auto)]
+pub struct Buffer<T> {
+    pub len: usize,
+    pub data: [T],
+}
+
+part!(pub Id: u32);
+part!(pub Name: str);
+
+#[derive(PartialRefTarget)]
+pub struct Label {
+    #[part(Id)]
+    pub id: u32,
+    #[part(Name)]
+    pub name: str,
+}
+
+fn fill(mut buffer: partial!(Buffer<usize>, Len, mut Data<usize>)) {
+    let (len, mut buffer) = buffer.split_part(Len);
+    for (index, value) in buffer.part_mut(Data::default()).iter_mut().enumerate() {
+        *value = *len * index;
+    }
+}
+
+#[test]
+fn test_unsized_fields() {
+    // A buffer with a length of 3 followed by 3 elements.
+    let mut storage = [3usize, 0, 0, 0];
+    let buffer = unsafe {
+        &mut *(std::ptr::slice_from_raw_parts_mut(storage.as_mut_ptr(), 3) as *mut Buffer<usize>)
+    };
+    let mut buffer_ref = buffer.into_partial_ref_mut();
+
+    fill(buffer_ref.borrow());
+
+    assert_eq!(buffer_ref.part(Data::default()), &[0, 3, 6]);
+    assert_eq!(<Buffer<usize> as PartOffset<Len>>::OFFSET, 0);
+    assert_eq!(storage, [3, 0, 3, 6]);
+}
@@ -0,0 +1,60 @@
+use partial_ref::*;
+
+#[derive(PartialRefTarget, Default)]
+#[partial_ref(view)]
+pub struct Context<T> {
+    #[part(auto)]
+    pub values: Vec<T>,
+    #[part(auto)]
+    pub total: usize,
+    #[part(deref, Limit)]
+    pub limit: Box<usize>,
+    #[part(elements(Counter))]
+    pub counters: [u32; 2],
+    pub scratch: Vec<T>,
+}
+
+part!(pub Limit: usize);
+
+fn downstream<T: Clone>(view: ContextViewMut<T>) {
+    if view.values.len() < *view.limit {
+        view.values.extend(view.values.clone());
+    }
+    *view.total = view.values.len();
+    *view.counters[1] += 1;
+}
+
+#[test]
+fn test_view_mut() {
+    let mut context = Context {
+        values: vec![1],
+        limit: Box::new(2),
+        ..Context::default()
+    };
+    let mut context_ref = context.into_partial_ref_mut();
+
+    downstream(context_ref.to_view_mut());
+    downstream(context_ref.to_view_mut());
+
+    let view = context_ref.to_view();
+    assert_eq!(view.values, &[1, 1]);
+    assert_eq!(*view.total, 2);
+    assert_eq!(view.counters, [&0, &2]);
+    assert_eq!(context.counters, [0, 2]);
+}
+
+#[test]
+fn test_view_from_subset() {
+    let mut context = Context::<u8>::default();
+    let mut context_ref = context.into_partial_ref_mut();
+
+    let view_ref: partial!(
+        Context<u8>,
+        Counter<1>,
+        mut Values<u8>,
+        Limit,
+        Total,
+        Counter<0>,
+    ) = context_ref.borrow();
+    assert_eq!(*view_ref.to_view().limit, 0);
+}
@@ -0,0 +1,72 @@
+use std::ptr::{addr_of, addr_of_mut};
+
+use partial_ref::*;
+
+part!(pub Left: [f32]);
+part!(pub Right: [f32]);
+
+#[derive(PartialRefTarget, Default)]
+#[part(virtual(Left, Right))]
+pub struct Stereo {
+    samples: Vec<f32>,
+    #[part(auto)]
+    pub gain: f32,
+}
+
+unsafe impl VirtualPart<Stereo> for Left {
+    unsafe fn virtual_ptr(ptr: *const Stereo) -> *const [f32] {
+        let samples = &*addr_of!((*ptr).samples);
+        SliceField::range_ptr(samples, 0, samples.len() / 2)
+    }
+
+    unsafe fn virtual_ptr_mut(ptr: *mut Stereo) -> *mut [f32] {
+        let samples = &mut *addr_of_mut!((*ptr).samples);
+        let len = samples.len();
+        SliceField::range_ptr_mut(samples, 0, len / 2)
+    }
+}
+
+unsafe impl VirtualPart<Stereo> for Right {
+    unsafe fn virtual_ptr(ptr: *const Stereo) -> *const [f32] {
+        let samples = &*addr_of!((*ptr).samples);
+        SliceField::range_ptr(samples, samples.len() / 2, samples.len())
+    }
+
+    unsafe fn virtual_ptr_mut(ptr: *mut Stereo) -> *mut [f32] {
+        let samples = &mut *addr_of_mut!((*ptr).samples);
+        let len = samples.len();
+        SliceField::range_ptr_mut(samples, len / 2, len)
+    }
+}
+
+fn amplify(mut channel: partial!(Stereo, mut Left, Gain)) {
+    let gain = *channel.part(Gain);
+    for sample in channel.part_mut(Left) {
+        *sample *= gain;
+    }
+}
+
+fn balance(mut stereo: partial!(Stereo, mut Left, mut Right)) {
+    let (left, mut stereo) = stereo.split_part_mut(Left);
+    for (left, right) in left.iter_mut().zip(stereo.part_mut(Right)) {
+        let mean = (*left + *right) / 2.0;
+        *left = mean;
+        *right = mean;
+    }
+}
+
+#[test]
+fn test_virtual_parts() {
+    let mut stereo = Stereo {
+        samples: vec![1.0, 2.0, 3.0, 5.0],
+        gain: 2.0,
+    };
+    let mut stereo_ref = stereo.into_partial_ref_mut();
+
+    amplify(stereo_ref.borrow());
+    assert_eq!(stereo_ref.part(Left), [2.0, 4.0]);
+    assert_eq!(stereo_ref.part(Right), [3.0, 5.0]);
+
+    balance(stereo_ref.borrow());
+    assert_eq!(stereo.samples, [2.5, 4.5, 2.5, 4.5]);
+}
@@ -0,0 +1,104 @@
+use std::fmt::Debug;
+use std::mem::{size_of, size_of_val};
+
+use partial_ref::*;
+
+use crate::enum_targets::Job;
+
+#[derive(PartialRefTarget)]
+pub struct Inventory<T> {
+    #[part(auto)]
+    pub items: Vec<T>,
+    #[part(auto)]
+    pub label: &'static str,
+    #[part(deref, Owner)]
+    pub owner: Box<u32>,
+    #[part(elements(Shelf))]
+    pub shelves: [u8; 2],
+}
+
+part!(pub Owner: u32);
+
+#[derive(Default)]
+struct Dump(Vec<String>);
+
+impl<T: Debug + ?Sized> PartVisitor<T> for Dump {
+    fn visit(&mut self, name: &'static str, field: &T) {
+        self.0.push(format!("{}={:?}", name, field));
+    }
+}
+
+#[derive(Default)]
+struct Size(usize);
+
+impl<T: ?Sized> PartVisitor<T> for Size {
+    fn visit(&mut self, _name: &'static str, field: &T) {
+        self.0 += size_of_val(field);
+    }
+}
+
+#[test]
+fn test_visit_struct_parts() {
+    let inventory = Inventory {
+        items: vec![1u16, 2],
+        label: "pantry",
+        owner: Box::new(7),
+        shelves: [3, 4],
+    };
+
+    let mut dump = Dump::default();
+    inventory.visit_parts(&mut dump);
+    assert_eq!(
+        dump.0,
+        vec![
+            "Items<T>=[1, 2]",
+            "Label=\"pantry\"",
+            "Owner=7",
+            "Shelf<0>=3",
+            "Shelf<1>=4",
+        ]
+    );
+
+    let mut size = Size::default();
+    inventory.visit_parts(&mut size);
+    assert_eq!(
+        size.0,
+        size_of::<Vec<u16>>() + size_of::<&str>() + size_of::<u32>() + 2
+    );
+}
+
+#[test]
+fn test_visit_variant_parts() {
+    let mut dump = Dump::default();
+    Job::Running {
+        progress: 2,
+        log: vec!["started".to_owned()],
+    }
+    .visit_parts(&mut dump);
+    Job::Finished(0).visit_parts(&mut dump);
+    Job::Waiting.visit_parts(&mut dump);
+
+    assert_eq!(dump.0, vec!["Progress=2", "Log=[\"started\"]", "Status=0"]);
+}
+
+#[test]
+fn test_for_each_part() {
+    let mut inventory = Inventory {
+        items: vec![1u16, 2],
+        label: "pantry",
+        owner: Box::new(7),
+        shelves: [3, 4],
+    };
+    let mut inventory_ref = inventory.into_partial_ref_mut();
+
+    let part_ref: partial!(Inventory<u16>, Shelf<1>, mut Items<u16>, Owner) =
+        inventory_ref.borrow();
+
+    let mut dump = Dump::default();
+    part_ref.for_each_part(&mut dump);
+    assert_eq!(dump.0, vec!["Shelf<1>=4", "Items<T>=[1, 2]", "Owner=7"]);
+
+    let mut size = Size::default();
+    part_ref.for_each_part(&mut size);
+    assert_eq!(size.0, 1 + size_of::<Vec<u16>>() + size_of::<u32>());
+}